@@ -222,6 +222,35 @@ pub fn parallel_arg_with_default(default: &'static str) -> Arg {
         .help("Number of threads for parallel processing")
 }
 
+/// `--dry-run` flag (print the pipeline steps without running them).
+pub fn dry_run_arg() -> Arg {
+    Arg::new("dry_run")
+        .long("dry-run")
+        .action(ArgAction::SetTrue)
+        .help("Print the steps and the files they would create, then exit")
+}
+
+/// `--resume` flag (skip pipeline stages whose outputs are up to date).
+pub fn resume_arg() -> Arg {
+    Arg::new("resume")
+        .long("resume")
+        .action(ArgAction::SetTrue)
+        .help("Skip stages whose outputs exist and are newer than their inputs")
+}
+
+/// `--force` flag (run every pipeline stage, overriding `--resume`).
+pub fn force_arg() -> Arg {
+    Arg::new("force")
+        .long("force")
+        .action(ArgAction::SetTrue)
+        .help("Run every stage even if its outputs are up to date; overrides --resume")
+}
+
+/// Whether `--resume` is in effect (set and not overridden by `--force`).
+pub fn get_resume(args: &ArgMatches) -> bool {
+    args.get_flag("resume") && !args.get_flag("force")
+}
+
 /// `--no-ns` flag (output size without Ns).
 pub fn no_ns_arg() -> Arg {
     Arg::new("no_ns")
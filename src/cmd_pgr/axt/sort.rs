@@ -1,6 +1,6 @@
 use anyhow::Context;
-use clap::{Arg, ArgAction, ArgMatches, Command};
-use pgr::libs::fmt::axt::{write_axt, AxtReader};
+use clap::{builder, Arg, ArgAction, ArgMatches, Command};
+use pgr::libs::fmt::axt::{write_axt, AxtReader, AxtSortBy};
 use std::io::Write;
 
 /// Build the clap subcommand for sort.
@@ -12,7 +12,10 @@ pub fn make_subcommand() -> Command {
 Sorts AXT files by target, query, or score.
 
 Notes:
-* --by-query and --by-score are mutually exclusive
+* --by selects the sort key: tName (default), qName or score
+* Ties are broken by the start coordinate, so the output order is stable
+* --by-query and --by-score are shorthands for --by qName and --by score
+* Supports both plain text and gzipped (.gz) input and output
 
 Examples:
 1. Sort by target (default):
@@ -23,10 +26,25 @@ Examples:
 
 3. Sort by score (descending):
    pgr axt sort in.axt --by-score -o out.axt
+
+4. Sort a gzipped file by query name, writing gzipped output:
+   pgr axt sort in.axt.gz --by qName -o out.axt.gz
 "###,
         )
         .arg(crate::cmd_pgr::args::infile_arg().help("Input AXT file. [stdin] for standard input"))
         .arg(crate::cmd_pgr::args::outfile_arg())
+        .arg(
+            Arg::new("by")
+                .long("by")
+                .num_args(1)
+                .value_parser([
+                    builder::PossibleValue::new("tName"),
+                    builder::PossibleValue::new("qName"),
+                    builder::PossibleValue::new("score"),
+                ])
+                .conflicts_with_all(["by_query", "by_score"])
+                .help("Sort key [default: tName]"),
+        )
         .arg(
             crate::cmd_pgr::args::by_query_arg("Sort by query position, not target")
                 .conflicts_with("by_score"),
@@ -45,19 +63,24 @@ Examples:
                 .help("Renumber AXT records"),
         )
 }
+
 /// Execute the sort command.
 pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let input = crate::cmd_pgr::args::get_infile(args);
     let output = crate::cmd_pgr::args::get_outfile(args);
-    let by_query = args.get_flag("by_query");
-    let by_score = args.get_flag("by_score");
     let renumber = args.get_flag("renumber");
 
+    let by = match args.get_one::<String>("by").map(|s| s.as_str()) {
+        Some("qName") => AxtSortBy::Query,
+        Some("score") => AxtSortBy::Score,
+        Some(_) => AxtSortBy::Target,
+        None if args.get_flag("by_score") => AxtSortBy::Score,
+        None if args.get_flag("by_query") => AxtSortBy::Query,
+        None => AxtSortBy::Target,
+    };
+
     let reader =
         pgr::reader(input).with_context(|| format!("Failed to open reader for {}", input))?;
-    let mut writer =
-        pgr::writer(output).with_context(|| format!("Failed to open writer for {}", output))?;
-
     let mut axt_reader = AxtReader::new(reader);
 
     let mut axts = Vec::new();
@@ -65,24 +88,19 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         axts.push(result?);
     }
 
+    pgr::libs::fmt::axt::sort_axts(&mut axts, by, renumber);
+
+    let mut writer =
+        pgr::gz_writer(output).with_context(|| format!("Failed to open writer for {}", output))?;
     for header in &axt_reader.headers {
         writeln!(writer, "{}", header)?;
     }
-
-    let by = if by_score {
-        pgr::libs::fmt::axt::AxtSortBy::Score
-    } else if by_query {
-        pgr::libs::fmt::axt::AxtSortBy::Query
-    } else {
-        pgr::libs::fmt::axt::AxtSortBy::Target
-    };
-
-    pgr::libs::fmt::axt::sort_axts(&mut axts, by, renumber);
-
     for axt in &axts {
         write_axt(&mut writer, axt)?;
     }
 
-    writer.flush()?;
+    writer
+        .finish()
+        .with_context(|| format!("Failed to finish {}", output))?;
     Ok(())
 }
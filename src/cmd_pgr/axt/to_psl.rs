@@ -9,6 +9,10 @@ pub fn make_subcommand() -> Command {
             r###"
 Where tSizes and qSizes are tab-delimited files with <seqName> <size> columns.
 
+Notes:
+* A minus-strand record whose query is missing from `--q-sizes` is an error,
+  as its coordinates can't be converted to the plus strand
+
 Examples:
 1. Convert axt to psl:
    pgr axt to-psl in.axt -t t.sizes -q q.sizes -o out.psl
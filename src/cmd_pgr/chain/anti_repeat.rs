@@ -21,6 +21,10 @@ Notes:
 * Target and query inputs must be 2bit files (use `pgr 2bit` to convert)
 * `--min-score` (default: 5000) drops chains below this score
 * `--no-check-score` (default: 200000) skips checks above this score
+* `--repeat-penalty` (default: 1.0) scales the repeat down-weighting: the score is
+  multiplied by `2 * (1 - penalty * repeat_fraction)`, floored at 0; chains falling
+  below `--min-score` are dropped. Use 0 to disable the adjustment: the raw score
+  is compared with `--min-score`
 
 Examples:
 1. Basic repeat filtering:
@@ -32,6 +36,9 @@ Examples:
 3. Lower the no-check threshold:
    pgr chain anti-repeat --target-2bit t.2bit --query-2bit q.2bit in.chain --no-check-score 100000 -o out.chain
 
+4. Penalize repeat-heavy chains more aggressively:
+   pgr chain anti-repeat --target-2bit t.2bit --query-2bit q.2bit in.chain --repeat-penalty 1.5 -o out.chain
+
 "###,
         )
         .arg(crate::cmd_pgr::args::target_2bit_arg())
@@ -48,6 +55,13 @@ Examples:
                 .value_parser(clap::value_parser!(usize))
                 .help("Score above which no checks are performed"),
         )
+        .arg(
+            Arg::new("repeat_penalty")
+                .long("repeat-penalty")
+                .default_value("1.0")
+                .value_parser(clap::value_parser!(f64))
+                .help("Scaling factor for the repeat down-weighting"),
+        )
 }
 /// Execute the anti-repeat command.
 pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
@@ -57,6 +71,12 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let output_path = crate::cmd_pgr::args::get_outfile(args);
     let min_score = *args.get_one::<f64>("min_score").unwrap();
     let no_check_score = *args.get_one::<usize>("no_check_score").unwrap();
+    let repeat_penalty = *args.get_one::<f64>("repeat_penalty").unwrap();
+    anyhow::ensure!(
+        repeat_penalty >= 0.0,
+        "--repeat-penalty must be non-negative: {}",
+        repeat_penalty
+    );
 
     let mut target_2bit = TwoBitFile::open(target_path)
         .with_context(|| format!("Failed to open 2bit file {}", target_path))?;
@@ -78,7 +98,13 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
             continue;
         }
 
-        if check_chain(&chain, &mut target_2bit, &mut query_2bit, min_score) {
+        if check_chain(
+            &chain,
+            &mut target_2bit,
+            &mut query_2bit,
+            min_score,
+            repeat_penalty,
+        ) {
             chain.write(&mut writer)?;
         }
     }
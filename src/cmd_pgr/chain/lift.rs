@@ -0,0 +1,114 @@
+use anyhow::Context;
+use clap::{value_parser, Arg, ArgMatches, Command};
+use pgr::libs::chain::{read_chains, ChainLift};
+use std::io::Write;
+
+/// Build the clap subcommand for lift.
+pub fn make_subcommand() -> Command {
+    Command::new("lift")
+        .about("Lifts target ranges onto the query through chains")
+        .after_help(
+            r###"
+Lifts ranges on the target genome onto the query genome, similar to UCSC `liftOver`.
+
+Each range is lifted through the overlapping chain that aligns the most of its
+bases. The lifted result is split at block boundaries, and bases falling in
+chain gaps are dropped.
+
+Output is tab-separated: the input range followed by one lifted range.
+A range split across several blocks produces several lines.
+
+Notes:
+* Ranges use the `chr:start-end` format (1-based, inclusive); `chr(-):start-end` flips the output strand
+* Ranges can be given on the command line or read from a file with `-r`
+* Ranges whose aligned fraction is below `--min-match` are reported on stderr and skipped
+* Query coordinates are always on the forward strand; a `(-)` marks ranges on the reverse strand
+
+Examples:
+1. Lift a single range:
+   pgr chain lift in.chain "chr1:1000-2000"
+
+2. Lift ranges from a file, allowing partial matches:
+   pgr chain lift in.chain -r ranges.txt --min-match 0.5 -o lifted.tsv
+
+"###,
+        )
+        .arg(
+            Arg::new("infile")
+                .required(true)
+                .index(1)
+                .help("Input chain file"),
+        )
+        .arg(crate::cmd_pgr::args::ranges_arg())
+        .arg(crate::cmd_pgr::args::rgfile_arg())
+        .arg(
+            Arg::new("min_match")
+                .long("min-match")
+                .num_args(1)
+                .default_value("0.95")
+                .value_parser(value_parser!(f64))
+                .help("Minimum fraction of bases that must be aligned"),
+        )
+        .arg(crate::cmd_pgr::args::outfile_arg())
+}
+
+/// Execute the lift command.
+pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
+    let infile = args.get_one::<String>("infile").unwrap();
+    let min_match = *args.get_one::<f64>("min_match").unwrap();
+    anyhow::ensure!(
+        (0.0..=1.0).contains(&min_match),
+        "--min-match must be between 0 and 1"
+    );
+
+    let ranges = crate::cmd_pgr::args::collect_ranges(args)?;
+
+    let chains = read_chains(
+        pgr::reader(infile).with_context(|| format!("Failed to open reader for {}", infile))?,
+    )?;
+    let lifter = ChainLift::new(chains);
+
+    let outfile = crate::cmd_pgr::args::get_outfile(args);
+    let mut writer =
+        pgr::writer(outfile).with_context(|| format!("Failed to open writer for {}", outfile))?;
+
+    for el in ranges.iter() {
+        let rg = intspan::Range::from_str(el);
+        anyhow::ensure!(rg.is_valid(), "invalid range: {}", el);
+
+        // 1-based inclusive -> 0-based half-open
+        let start = (*rg.start() - 1) as u64;
+        let end = *rg.end() as u64;
+        let len = end - start;
+
+        let result = match lifter.lift(rg.chr(), start, end) {
+            Some(r) if r.matched as f64 / len as f64 >= min_match => r,
+            Some(r) => {
+                log::warn!(
+                    "{}: only {} of {} bases aligned, skipped",
+                    el,
+                    r.matched,
+                    len
+                );
+                continue;
+            }
+            None => {
+                log::warn!("{}: not in any chain, skipped", el);
+                continue;
+            }
+        };
+
+        let flip = rg.strand() == "-";
+        for piece in result.pieces {
+            let mut lifted =
+                intspan::Range::from(&piece.q_name, piece.q_start as i32 + 1, piece.q_end as i32);
+            if (piece.q_strand == '-') != flip {
+                *lifted.strand_mut() = "-".to_string();
+            }
+            writer.write_fmt(format_args!("{}\t{}\n", el, lifted))?;
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
@@ -1,4 +1,5 @@
 pub mod anti_repeat;
+pub mod lift;
 pub mod net;
 pub mod pre_net;
 pub mod sort;
@@ -17,6 +18,7 @@ pub fn make_subcommand() -> Command {
         .subcommand(sort::make_subcommand())
         .subcommand(pre_net::make_subcommand())
         .subcommand(net::make_subcommand())
+        .subcommand(lift::make_subcommand())
 }
 /// Execute the chain command.
 pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
@@ -27,6 +29,7 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         Some(("sort", sub_matches)) => sort::execute(sub_matches),
         Some(("pre-net", sub_matches)) => pre_net::execute(sub_matches),
         Some(("net", sub_matches)) => net::execute(sub_matches),
+        Some(("lift", sub_matches)) => lift::execute(sub_matches),
         _ => Ok(()),
     }
 }
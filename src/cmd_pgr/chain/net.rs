@@ -16,9 +16,13 @@ genomes.
 Notes:
 * Input chain file must already be sorted by score descending (use `pgr chain sort`); otherwise the command returns an error
 * Outputs two net files: one in target orientation, one in query orientation
+* The query net is netted on the query chromosomes (for reciprocal nets); its
+  fills use plus-strand query coordinates
 * Use `--min-space` to control the minimum gap size to fill (default: 25)
 * Use `--min-fill` to control the minimum fill to record (default: min-space / 2)
 * Use `--min-score` to filter low-scoring chains (default: 2000)
+* Use `--q-min-score` to apply a different threshold to the query net (default: --min-score)
+* The defaults match UCSC chainNet; scores must not be negative
 * Use `--incl-hap` to include haplotype chains (names containing `_hap` or `_alt`)
 
 Examples:
@@ -62,6 +66,13 @@ Examples:
                 .help("Minimum fill to record (default: min_space / 2)"),
         )
         .arg(crate::cmd_pgr::args::min_score_arg("2000"))
+        .arg(
+            Arg::new("q_min_score")
+                .long("q-min-score")
+                .num_args(1)
+                .value_parser(clap::value_parser!(f64))
+                .help("Minimum score for the query net (default: --min-score)"),
+        )
         .arg(crate::cmd_pgr::args::incl_hap_arg())
 }
 /// Execute the net command.
@@ -78,8 +89,23 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         .copied()
         .unwrap_or(min_space / 2);
     let min_score = *args.get_one::<f64>("min_score").unwrap();
+    let q_min_score = args
+        .get_one::<f64>("q_min_score")
+        .copied()
+        .unwrap_or(min_score);
     let incl_hap = args.get_flag("incl_hap");
 
+    anyhow::ensure!(
+        min_score >= 0.0,
+        "--min-score must not be negative: {}",
+        min_score
+    );
+    anyhow::ensure!(
+        q_min_score >= 0.0,
+        "--q-min-score must not be negative: {}",
+        q_min_score
+    );
+
     let t_sizes = pgr::read_sizes::<u64>(target_sizes_path)?;
     let q_sizes = pgr::read_sizes::<u64>(query_sizes_path)?;
 
@@ -106,7 +132,7 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         }
         last_score = chain.header.score;
 
-        if chain.header.score < min_score {
+        if chain.header.score < min_score.min(q_min_score) {
             continue;
         }
 
@@ -118,7 +144,7 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         t_net.add_chain(chain.clone(), min_space, min_fill, min_score);
 
         // Add to Q net
-        q_net.add_chain_as_q(chain, min_space, min_fill, min_score);
+        q_net.add_chain_as_q(chain, min_space, min_fill, q_min_score);
     }
 
     // Finish and write T net
@@ -137,7 +163,7 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         &q_net,
         true,
         &reader.header_comments,
-        min_score,
+        q_min_score,
         min_fill,
     )?;
 
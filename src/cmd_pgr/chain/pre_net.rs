@@ -16,6 +16,10 @@ Notes:
 * `--pad` (default: 1) adds extra padding around blocks to reduce trash
 * `--incl-hap` retains haplotype chains (names containing `_hap` or `_alt`)
 * `--dots N` prints a progress dot every N processed chains
+* `--min-score` drops chains scoring below the threshold
+* `--min-coverage` drops chains whose aligned bases cover less than this
+  fraction of the larger of the target/query spans
+* Chains lying wholly inside regions used by higher-scoring chains are removed
 
 Examples:
 1. Basic pre-net filtering:
@@ -27,6 +31,9 @@ Examples:
 3. Pad blocks by 10 bp:
    pgr chain pre-net in.chain t.sizes q.sizes --pad 10 -o out.chain
 
+4. Drop weak or sparse chains:
+   pgr chain pre-net in.chain t.sizes q.sizes --min-score 3000 --min-coverage 0.2 -o out.chain
+
 "###,
         )
         .arg(crate::cmd_pgr::args::infile_arg_required_with_help(
@@ -49,6 +56,14 @@ Examples:
                 .help("Extra to pad around blocks to decrease trash"),
         )
         .arg(crate::cmd_pgr::args::incl_hap_arg())
+        .arg(crate::cmd_pgr::args::min_score_arg("0"))
+        .arg(
+            Arg::new("min_coverage")
+                .long("min-coverage")
+                .default_value("0")
+                .value_parser(clap::value_parser!(f64))
+                .help("Minimum fraction of the larger span covered by aligned blocks"),
+        )
 }
 /// Execute the pre-net command.
 pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
@@ -60,6 +75,8 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let dots = args.get_one::<usize>("dots").copied();
     let pad = args.get_one::<u64>("pad").copied().unwrap_or(1);
     let incl_hap = args.get_flag("incl_hap");
+    let min_score = *args.get_one::<f64>("min_score").unwrap();
+    let min_coverage = *args.get_one::<f64>("min_coverage").unwrap();
 
     let mut t_hash: HashMap<String, BitMap> = pgr::read_sizes::<u64>(target_sizes_path)?
         .into_iter()
@@ -78,6 +95,8 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         pad,
         incl_hap,
         dots,
+        min_score,
+        min_coverage,
     };
     pgr::libs::chain::pre_net(reader, writer, &mut t_hash, &mut q_hash, &opts)
 }
@@ -1,5 +1,5 @@
 use anyhow::Context;
-use clap::{Arg, ArgMatches, Command};
+use clap::{builder, Arg, ArgMatches, Command};
 use pgr::libs::chain::ChainReader;
 use std::collections::HashMap;
 use std::fs;
@@ -15,20 +15,23 @@ Splits chains into separate files based on target (default) or query sequence
 name. Each output file is named `<seq>.chain` and placed in the output directory.
 
 Notes:
-* Use `--by-query` to split on the query sequence name instead of target
+* Use `--by qName` (or `--by-query`) to split on the query sequence name instead
+  of target; `--by tName` is the default
 * Use `--lump N` to group sequences into at most N output files by hashing the
   first integer run in the sequence name (falls back to a stable hash when no
   digits are present); useful for parallelizing downstream steps
 * `--lump N` caps the number of output files at N; the actual number may be smaller
   if the input contains fewer distinct buckets
 * The output directory is created if it does not exist
+* Chains are streamed to their files; the path of each written file is printed
+  to stdout as a manifest, in order of first appearance
 
 Examples:
 1. Split by target sequence:
    pgr chain split in.chain out_dir/
 
 2. Split by query sequence:
-   pgr chain split in.chain out_dir/ --by-query
+   pgr chain split in.chain out_dir/ --by qName
 
 3. Lump into 100 buckets:
    pgr chain split in.chain out_dir/ --lump 100
@@ -37,6 +40,17 @@ Examples:
         )
         .arg(crate::cmd_pgr::args::infiles_arg("chain"))
         .arg(crate::cmd_pgr::args::outdir_arg_required())
+        .arg(
+            Arg::new("by")
+                .long("by")
+                .num_args(1)
+                .value_parser([
+                    builder::PossibleValue::new("tName"),
+                    builder::PossibleValue::new("qName"),
+                ])
+                .conflicts_with("by_query")
+                .help("Header field to split on"),
+        )
         .arg(crate::cmd_pgr::args::by_query_arg(
             "Split on query (default is on target)",
         ))
@@ -51,7 +65,8 @@ Examples:
 pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let out_dir = args.get_one::<String>("outdir").unwrap();
     let chain_files: Vec<_> = args.get_many::<String>("infiles").unwrap().collect();
-    let split_on_q = args.get_flag("by_query");
+    let split_on_q = args.get_flag("by_query")
+        || args.get_one::<String>("by").map(String::as_str) == Some("qName");
     let lump = args.get_one::<usize>("lump").copied();
     if let Some(l) = lump {
         anyhow::ensure!(l > 0, "--lump must be positive: {}", l);
@@ -62,6 +77,7 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
 
     // Cache open file handles
     let mut file_cache: HashMap<String, Box<dyn Write>> = HashMap::new();
+    let mut manifest = pgr::writer("stdout")?;
 
     for file_path in chain_files {
         let reader = ChainReader::new(
@@ -100,9 +116,10 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
                     let path_str = path
                         .to_str()
                         .ok_or_else(|| anyhow::anyhow!("non-UTF-8 path"))?;
-                    e.insert(Box::new(pgr::writer(path_str).with_context(|| {
-                        format!("Failed to open writer for {}", path_str)
-                    })?))
+                    let writer = pgr::writer(path_str)
+                        .with_context(|| format!("Failed to open writer for {}", path_str))?;
+                    writeln!(manifest, "{}", path_str)?;
+                    e.insert(Box::new(writer))
                 }
             };
 
@@ -114,6 +131,7 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     for writer in file_cache.values_mut() {
         writer.flush()?;
     }
+    manifest.flush()?;
 
     Ok(())
 }
@@ -0,0 +1,67 @@
+use clap::{ArgMatches, Command};
+use std::io::Write;
+
+/// Build the clap subcommand for eval.
+pub fn make_subcommand() -> Command {
+    Command::new("eval")
+        .about("Evaluates a clustering with silhouette scores")
+        .after_help(
+            r###"
+This command scores a clustering against the distance matrix it was built from.
+
+* Input: a PHYLIP distance matrix, full or lower-triangular
+* --clusters: one cluster per line, members separated by tabs or spaces
+* Output: `cluster<TAB>size<TAB>silhouette`, one row per cluster in file order,
+  then an `all` row with the mean over every clustered taxon
+
+Notes:
+* Silhouette of a taxon: (b - a) / max(a, b), where
+    * a is its mean distance to the other members of its cluster
+    * b is its smallest mean distance to another cluster
+* Taxa in singleton clusters score 0
+* Clusters are numbered from 1 in the order of the file
+* Taxa of the matrix absent from the cluster file are ignored
+* A member absent from the matrix, or listed twice, is an error
+
+Examples:
+1. Score a clustering:
+   pgr clust eval input.phy --clusters clusters.tsv
+
+"###,
+        )
+        .arg(crate::cmd_pgr::args::infile_arg().help("Input PHYLIP distance matrix"))
+        .arg(
+            clap::Arg::new("clusters")
+                .long("clusters")
+                .num_args(1)
+                .required(true)
+                .help("Cluster file, one cluster per line"),
+        )
+        .arg(crate::cmd_pgr::args::outfile_arg())
+}
+
+/// Execute the eval command.
+pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
+    let infile = crate::cmd_pgr::args::get_infile(args);
+    let opt_clusters = args.get_one::<String>("clusters").unwrap();
+
+    let (names, matrix) = pgr::libs::fmt::phylip::read_matrix(infile)?;
+    let clusters = pgr::libs::clust::read_clusters(opt_clusters)?;
+    anyhow::ensure!(!clusters.is_empty(), "{}: no clusters", opt_clusters);
+    let indices = pgr::libs::clust::cluster_indices(&names, &clusters)?;
+
+    let scores = pgr::libs::clust::silhouette(&matrix, &indices);
+
+    let mut writer = pgr::writer(crate::cmd_pgr::args::get_outfile(args))?;
+    writer.write_fmt(format_args!("cluster\tsize\tsilhouette\n"))?;
+    for (i, s) in scores.iter().enumerate() {
+        let mean = s.iter().sum::<f64>() / s.len() as f64;
+        writer.write_fmt(format_args!("{}\t{}\t{:.4}\n", i + 1, s.len(), mean))?;
+    }
+    let all: Vec<f64> = scores.iter().flatten().copied().collect();
+    let mean = all.iter().sum::<f64>() / all.len() as f64;
+    writer.write_fmt(format_args!("all\t{}\t{:.4}\n", all.len(), mean))?;
+
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,19 @@
+// Subcommand modules for the `clust` command.
+pub mod eval;
+
+use clap::{ArgMatches, Command};
+/// Build the clap subcommand for clust.
+pub fn make_subcommand() -> Command {
+    Command::new("clust")
+        .about("Operates on clusterings")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .subcommand(eval::make_subcommand())
+}
+/// Execute the clust command.
+pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
+    match args.subcommand() {
+        Some(("eval", sub_matches)) => eval::execute(sub_matches),
+        _ => Ok(()),
+    }
+}
@@ -34,10 +34,13 @@ Examples:
 3. Treat input as a list file and calculate distances:
    pgr dist hv list.txt --list-files
 
-4. Use 4 threads for parallel processing:
+4. Trade resolution for speed with a smaller sketch:
+   pgr dist hv file1.fa file2.fa -k 11 --sketch 1024
+
+5. Use 4 threads for parallel processing:
    pgr dist hv input.fa --parallel 4
 
-5. Perform six-frame translation on a FA file and match to another
+6. Perform six-frame translation on a FA file and match to another
     pgr fa six-frame input.fa |
         pgr dist hv stdin match.fa
 
@@ -51,6 +54,7 @@ Examples:
             clap::Arg::new("dim")
                 .long("dim")
                 .short('d')
+                .visible_alias("sketch")
                 .num_args(1)
                 .default_value("4096")
                 .value_parser(clap::value_parser!(usize))
@@ -68,9 +72,13 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let opt_kmer = *args.get_one::<usize>("kmer").unwrap();
     let opt_window = *args.get_one::<usize>("window").unwrap();
     let opt_dim = *args.get_one::<usize>("dim").unwrap();
-    anyhow::ensure!(opt_kmer > 0, "--kmer must be positive: {}", opt_kmer);
+    pgr::libs::hash::check_kmer(opt_hasher, opt_kmer)?;
     anyhow::ensure!(opt_window > 0, "--window must be positive: {}", opt_window);
-    anyhow::ensure!(opt_dim > 0, "--dim must be positive: {}", opt_dim);
+    anyhow::ensure!(
+        opt_dim > 0 && opt_dim % 32 == 0,
+        "--dim must be a positive multiple of 32: {}",
+        opt_dim
+    );
 
     let is_sim = args.get_flag("sim");
     let is_list = args.get_flag("list_files");
@@ -34,6 +34,31 @@ This command calculates pairwise distances between sequences in FA file(s) using
     * It generates canonical k-mers, meaning that a sequence and its reverse complement
       are generating the same k-mer set.
 
+* MinHash (--minhash):
+    * Instead of minimizers, builds a bottom-k sketch (--sketch hashes) of canonical k-mers
+      for each sequence, estimating Jaccard and Mash distance as in Mash.
+    * DNA only; `--kmer` must be <= 32, and `--hasher`/`--window` are ignored.
+    * A sequence and its reverse complement give distance 0.
+    * With --merge, `<inter>` and `<union>` are the shared and sampled hash counts.
+
+* d2* (--d2star):
+    * An alignment-free composition distance for draft genomes: counts all
+      canonical k-mers and compares them after removing the counts expected
+      from the base composition (Reinert et al. 2009)
+    * Output: <sequence1> <sequence2> <d2star>, from 0 (same composition) to 1
+    * --sim reports 1 - d2star
+    * DNA only; `--kmer` must be <= 32, and `--hasher`/`--window` are ignored
+    * A sequence and its reverse complement give distance 0
+
+* Aligned proteins (--alphabet protein):
+    * Input sequences must be aligned (same length); no k-mers are used
+    * Pairs without comparable residues are skipped with a warning
+    * Only columns where both residues are standard amino acids are compared;
+      gaps, stops and ambiguity codes (X, B, Z, ...) are treated as missing
+    * Output: <sequence1> <sequence2> <poisson_distance> <identity> <sites>
+    * The Poisson-corrected distance is -ln(1 - p), p being 1 - identity
+    * --merge and --minhash are not available
+
 * To get accurate pairwise sequence identities, use clustalo
   https://lh3.github.io/2018/11/25/on-the-definition-of-sequence-identity
 
@@ -82,6 +107,15 @@ Examples:
 6. Use 4 threads for parallel processing:
    pgr dist seq input.fa --parallel 4
 
+7. MinHash sketches of canonical 21-mers:
+   pgr dist seq input.fa --minhash -k 21 --sketch 1000
+
+8. d2* distances of 12-mer profiles between two sets of genomes:
+   pgr dist seq set1.fa set2.fa --d2star -k 12 --merge
+
+9. Distances between aligned proteins:
+   pgr dist seq proteins.aln.fa --alphabet protein
+
 "###,
         )
         .arg(crate::cmd_pgr::args::pair_infiles_arg())
@@ -101,6 +135,38 @@ Examples:
                 .action(clap::ArgAction::SetTrue)
                 .help("Merge all sequences within a file into a single set for comparison"),
         )
+        .arg(
+            clap::Arg::new("minhash")
+                .long("minhash")
+                .action(clap::ArgAction::SetTrue)
+                .help("Estimate distances from bottom-k MinHash sketches of canonical k-mers"),
+        )
+        .arg(
+            clap::Arg::new("d2star")
+                .long("d2star")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("minhash")
+                .help("Compute the d2* distance of canonical k-mer profiles"),
+        )
+        .arg(
+            clap::Arg::new("sketch")
+                .long("sketch")
+                .num_args(1)
+                .default_value("1000")
+                .value_parser(clap::value_parser!(usize))
+                .help("Sketch size for --minhash"),
+        )
+        .arg(
+            clap::Arg::new("alphabet")
+                .long("alphabet")
+                .action(clap::ArgAction::Set)
+                .value_parser([
+                    clap::builder::PossibleValue::new("dna"),
+                    clap::builder::PossibleValue::new("protein"),
+                ])
+                .default_value("dna")
+                .help("Sequence alphabet; protein compares aligned sequences"),
+        )
         .arg(crate::cmd_pgr::args::list_arg())
         .arg(crate::cmd_pgr::args::parallel_arg())
         .arg(crate::cmd_pgr::args::outfile_arg())
@@ -111,15 +177,42 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let opt_hasher = args.get_one::<String>("hasher").unwrap();
     let opt_kmer = *args.get_one::<usize>("kmer").unwrap();
     let opt_window = *args.get_one::<usize>("window").unwrap();
+    let opt_sketch = *args.get_one::<usize>("sketch").unwrap();
     anyhow::ensure!(opt_kmer > 0, "--kmer must be positive: {}", opt_kmer);
     anyhow::ensure!(opt_window > 0, "--window must be positive: {}", opt_window);
 
     let is_sim = args.get_flag("sim");
     let is_zero = args.get_flag("zero");
     let is_merge = args.get_flag("merge");
+    let is_minhash = args.get_flag("minhash");
+    let is_d2star = args.get_flag("d2star");
+    let is_protein = args.get_one::<String>("alphabet").unwrap() == "protein";
     let is_list = args.get_flag("list_files");
     let opt_parallel = *args.get_one::<usize>("parallel").unwrap();
 
+    if is_protein {
+        anyhow::ensure!(
+            !is_merge && !is_minhash && !is_d2star,
+            "--merge, --minhash and --d2star are not available with --alphabet protein"
+        );
+    }
+
+    if is_d2star {
+        anyhow::ensure!(
+            opt_kmer <= 32,
+            "--kmer must be <= 32 with --d2star: {}",
+            opt_kmer
+        );
+    }
+    if is_minhash {
+        anyhow::ensure!(
+            opt_kmer <= 32,
+            "--kmer must be <= 32 with --minhash: {}",
+            opt_kmer
+        );
+        anyhow::ensure!(opt_sketch > 0, "--sketch must be positive: {}", opt_sketch);
+    }
+
     let infiles = crate::cmd_pgr::args::collect_infiles(args);
 
     let (sender, writer_thread) = pgr::libs::par::spawn_writer_and_pool(
@@ -127,15 +220,7 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         opt_parallel,
     )?;
 
-    let (entries1, entries2) = pgr::libs::par::load_two_sets(&infiles, is_list, |paths| {
-        pgr::libs::par::load_entries(paths, |p| {
-            pgr::libs::hash::load_minimizers(p, opt_hasher, opt_kmer, opt_window, is_merge)
-        })
-    })?;
-
-    pgr::libs::par::par_run_pairs(&entries1, &entries2, &sender, |e1, e2| {
-        let d = pgr::libs::hash::set_distances(&e1.set, &e2.set, opt_kmer);
-
+    let format_line = |name1: &str, name2: &str, d: pgr::libs::hash::SetDistances| {
         if !is_zero && d.jaccard == 0. {
             return None;
         }
@@ -149,24 +234,88 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         let line = if is_merge {
             format!(
                 "{}\t{}\t{}\t{}\t{}\t{}\t{:.4}\t{:.4}\t{:.4}\n",
-                e1.name,
-                e2.name,
-                d.total1,
-                d.total2,
-                d.inter,
-                d.union,
-                dist,
-                d.jaccard,
-                d.containment
+                name1, name2, d.total1, d.total2, d.inter, d.union, dist, d.jaccard, d.containment
             )
         } else {
             format!(
                 "{}\t{}\t{:.4}\t{:.4}\t{:.4}\n",
-                e1.name, e2.name, dist, d.jaccard, d.containment
+                name1, name2, dist, d.jaccard, d.containment
             )
         };
         Some(line)
-    });
+    };
+
+    if is_protein {
+        let (entries1, entries2) = pgr::libs::par::load_two_sets(&infiles, is_list, |paths| {
+            pgr::libs::par::load_entries(paths, pgr::libs::fmt::fa::load_records)
+        })?;
+
+        // Unaligned input is an error, not an empty table
+        if let Some(first) = entries1.first() {
+            let len = first.1.len();
+            if let Some(e) = entries1.iter().chain(&entries2).find(|e| e.1.len() != len) {
+                anyhow::bail!(
+                    "--alphabet protein needs aligned sequences: {} has length {}, {} has {}",
+                    first.0,
+                    len,
+                    e.0,
+                    e.1.len()
+                );
+            }
+        }
+
+        pgr::libs::par::par_run_pairs(&entries1, &entries2, &sender, |e1, e2| {
+            let (p, sites) = match pgr::libs::alignment::pair_aa_d(&e1.1, &e2.1) {
+                Ok(d) => d,
+                Err(err) => {
+                    log::warn!("skipped {} vs {}: {}", e1.0, e2.0, err);
+                    return None;
+                }
+            };
+            Some(format!(
+                "{}\t{}\t{:.4}\t{:.4}\t{}\n",
+                e1.0,
+                e2.0,
+                pgr::libs::alignment::poisson_distance(p),
+                1.0 - p,
+                sites
+            ))
+        });
+    } else if is_d2star {
+        let (entries1, entries2) = pgr::libs::par::load_two_sets(&infiles, is_list, |paths| {
+            pgr::libs::par::load_entries(paths, |p| {
+                pgr::libs::hash::load_profiles(p, opt_kmer, is_merge)
+            })
+        })?;
+
+        pgr::libs::par::par_run_pairs(&entries1, &entries2, &sender, |e1, e2| {
+            let d = pgr::libs::hash::d2star(e1, e2, opt_kmer);
+            let d = if is_sim { 1.0 - d } else { d };
+            Some(format!("{}\t{}\t{:.4}\n", e1.name, e2.name, d))
+        });
+    } else if is_minhash {
+        let (entries1, entries2) = pgr::libs::par::load_two_sets(&infiles, is_list, |paths| {
+            pgr::libs::par::load_entries(paths, |p| {
+                pgr::libs::hash::load_sketches(p, opt_kmer, opt_sketch, is_merge)
+            })
+        })?;
+
+        pgr::libs::par::par_run_pairs(&entries1, &entries2, &sender, |e1, e2| {
+            let d = pgr::libs::hash::sketch_distances(&e1.sketch, &e2.sketch, opt_sketch, opt_kmer);
+            format_line(&e1.name, &e2.name, d)
+        });
+    } else {
+        let (entries1, entries2) = pgr::libs::par::load_two_sets(&infiles, is_list, |paths| {
+            pgr::libs::par::load_entries(paths, |p| {
+                pgr::libs::hash::load_minimizers(p, opt_hasher, opt_kmer, opt_window, is_merge)
+            })
+        })?;
+
+        pgr::libs::par::par_run_pairs(&entries1, &entries2, &sender, |e1, e2| {
+            let d = pgr::libs::hash::set_distances(&e1.set, &e2.set, opt_kmer);
+            format_line(&e1.name, &e2.name, d)
+        });
+    }
 
     // Drop the sender to signal the writer thread to exit
     drop(sender);
@@ -0,0 +1,186 @@
+use clap::{ArgMatches, Command};
+use std::io::Write;
+
+/// Build the clap subcommand for vector.
+pub fn make_subcommand() -> Command {
+    Command::new("vector")
+        .about("Calculates distances/similarities between numeric vectors")
+        .after_help(
+            r###"
+This command calculates pairwise distances between vectors in TSV file(s).
+
+* Input format:
+    <name>\t<v1>,<v2>,<v3>,...
+
+* The outputs are printed in the following format:
+    <name1> <name2> <score>
+
+* Modes (--mode):
+    * euclid: Euclidean distance; --sim converts it to 1 / e^D
+    * cosine: Cosine similarity (-1 to 1)
+    * jaccard: Weighted Jaccard similarity
+    * --dis converts a similarity to 1 - S
+
+* --binary treats all non-zero values as 1 (presence/absence matrices)
+
+* PCA (--pca <k>):
+    * Instead of pairwise scores, projects all vectors onto the first k principal
+      components of the centered data
+    * Output: a `name PC1 .. PCk` header, then one row per vector
+    * The fraction of variance explained by each component is logged at the info level,
+      e.g. `PC1: 0.9876 of the variance`; RUST_LOG=warn hides it
+    * Only one input set is accepted; all vectors must have the same length
+
+Examples:
+1. Cosine similarity:
+   pgr dist vector features.tsv --mode cosine
+
+2. Jaccard distance of presence/absence vectors:
+   pgr dist vector presence.tsv --mode jaccard --binary --dis
+
+3. Compare two files with 4 threads:
+   pgr dist vector set1.tsv set2.tsv -p 4
+
+4. First two principal components:
+   pgr dist vector features.tsv --pca 2
+
+"###,
+        )
+        .arg(
+            crate::cmd_pgr::args::pair_infiles_arg()
+                .help("Input vector/list file(s). [stdin] for standard input"),
+        )
+        .arg(
+            clap::Arg::new("mode")
+                .long("mode")
+                .action(clap::ArgAction::Set)
+                .value_parser([
+                    clap::builder::PossibleValue::new("euclid"),
+                    clap::builder::PossibleValue::new("cosine"),
+                    clap::builder::PossibleValue::new("jaccard"),
+                ])
+                .default_value("euclid")
+                .help("Distance/similarity mode"),
+        )
+        .arg(
+            clap::Arg::new("binary")
+                .long("binary")
+                .action(clap::ArgAction::SetTrue)
+                .help("Treat non-zero values as 1"),
+        )
+        .arg(
+            clap::Arg::new("sim")
+                .long("sim")
+                .action(clap::ArgAction::SetTrue)
+                .help("Convert distance to similarity (1 / e^D)"),
+        )
+        .arg(
+            clap::Arg::new("dis")
+                .long("dis")
+                .action(clap::ArgAction::SetTrue)
+                .help("Convert similarity to dissimilarity (1 - S)"),
+        )
+        .arg(
+            clap::Arg::new("pca")
+                .long("pca")
+                .num_args(1)
+                .value_parser(clap::value_parser!(usize))
+                .conflicts_with_all(["mode", "sim", "dis"])
+                .help("Output coordinates on the first <k> principal components"),
+        )
+        .arg(crate::cmd_pgr::args::list_arg())
+        .arg(crate::cmd_pgr::args::parallel_arg())
+        .arg(crate::cmd_pgr::args::outfile_arg())
+}
+
+/// Execute the vector command.
+pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
+    let opt_mode = args.get_one::<String>("mode").unwrap().to_string();
+    let is_binary = args.get_flag("binary");
+    let is_sim = args.get_flag("sim");
+    let is_dis = args.get_flag("dis");
+    let is_list = args.get_flag("list_files");
+    let opt_parallel = *args.get_one::<usize>("parallel").unwrap();
+
+    let infiles = crate::cmd_pgr::args::collect_infiles(args);
+
+    if let Some(&opt_pca) = args.get_one::<usize>("pca") {
+        anyhow::ensure!(opt_pca > 0, "--pca must be positive: {}", opt_pca);
+        anyhow::ensure!(infiles.len() == 1, "--pca accepts only one input set");
+        return execute_pca(args, infiles[0], is_list, is_binary, opt_pca);
+    }
+
+    let (sender, writer_thread) = pgr::libs::par::spawn_writer_and_pool(
+        crate::cmd_pgr::args::get_outfile(args),
+        opt_parallel,
+    )?;
+
+    let (entries1, entries2) = pgr::libs::par::load_two_sets(&infiles, is_list, |paths| {
+        pgr::libs::par::load_entries(paths, |p| pgr::libs::linalg::load_vectors(p, is_binary))
+    })?;
+
+    pgr::libs::par::par_run_pairs(&entries1, &entries2, &sender, |e1, e2| {
+        let score =
+            pgr::libs::linalg::vector_score(&e1.list, &e2.list, &opt_mode, is_sim, is_dis).ok()?;
+        Some(format!("{}\t{}\t{:.4}\n", e1.name, e2.name, score))
+    });
+
+    // Drop the sender to signal the writer thread to exit
+    drop(sender);
+    // Wait for the writer thread to finish
+    writer_thread.join().map_err(|e| {
+        let msg = e
+            .downcast_ref::<String>()
+            .map(|s| s.as_str())
+            .or_else(|| e.downcast_ref::<&str>().copied())
+            .unwrap_or("<non-string panic payload>");
+        anyhow::anyhow!("writer thread panicked: {}", msg)
+    })?;
+
+    Ok(())
+}
+
+fn execute_pca(
+    args: &ArgMatches,
+    infile: &str,
+    is_list: bool,
+    is_binary: bool,
+    opt_pca: usize,
+) -> anyhow::Result<()> {
+    let paths = pgr::libs::par::resolve_paths(infile, is_list)?;
+    let entries =
+        pgr::libs::par::load_entries(&paths, |p| pgr::libs::linalg::load_vectors(p, is_binary))?;
+    anyhow::ensure!(!entries.is_empty(), "no vectors in {}", infile);
+
+    let dim = entries[0].list.len();
+    for e in &entries {
+        anyhow::ensure!(
+            e.list.len() == dim,
+            "vector {} has {} values, expected {}",
+            e.name,
+            e.list.len(),
+            dim
+        );
+    }
+
+    let matrix: Vec<Vec<f64>> = entries
+        .iter()
+        .map(|e| e.list.iter().map(|&v| v as f64).collect())
+        .collect();
+    let (coords, ratios) = pgr::libs::linalg::pca(&matrix, opt_pca);
+
+    let mut writer = pgr::writer(crate::cmd_pgr::args::get_outfile(args))?;
+    let header: Vec<String> = (1..=ratios.len()).map(|i| format!("PC{}", i)).collect();
+    writer.write_fmt(format_args!("name\t{}\n", header.join("\t")))?;
+    for (e, row) in entries.iter().zip(&coords) {
+        let values: Vec<String> = row.iter().map(|v| format!("{:.4}", v)).collect();
+        writer.write_fmt(format_args!("{}\t{}\n", e.name, values.join("\t")))?;
+    }
+
+    for (h, r) in header.iter().zip(&ratios) {
+        log::info!("{}: {:.4} of the variance", h, r);
+    }
+
+    writer.flush()?;
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use anyhow::Context;
-use clap::{ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 use std::io::Write;
 
 /// Build the clap subcommand for count.
@@ -14,6 +14,15 @@ It outputs a TSV table with the following columns:
 * len: Count of valid bases (A+C+G+T+N; IUPAC codes counted as N)
 * A, C, G, T, N: Count of each base
 
+With --comp, it reports per-record composition instead:
+* seq: Sequence name
+* len: Sequence length
+* GC%: GC percentage over A/C/G/T bases
+* N: Count of N bases
+* lower: Count of soft-masked (lowercase) bases
+* other: Count of IUPAC ambiguity codes and other characters
+The `total` summary line is only printed with --total in this mode.
+
 Notes:
 * Supports both plain text and gzipped (.gz) files
 * Reads from stdin if input file is 'stdin'
@@ -24,9 +33,25 @@ Examples:
 
 2. Count base statistics for multiple FASTA files:
    pgr fa count input1.fa input2.fa
+
+3. Per-record GC%, N and soft-masked counts with a summary line:
+   pgr fa count input.fa --comp --total
 "###,
         )
         .arg(crate::cmd_pgr::args::infiles_arg("FASTA"))
+        .arg(
+            Arg::new("comp")
+                .long("comp")
+                .action(ArgAction::SetTrue)
+                .help("Report GC%, N, soft-masked and other counts per record"),
+        )
+        .arg(
+            Arg::new("total")
+                .long("total")
+                .action(ArgAction::SetTrue)
+                .requires("comp")
+                .help("Also print a summary line over all records (with --comp)"),
+        )
         .arg(crate::cmd_pgr::args::outfile_arg())
 }
 
@@ -36,6 +61,10 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let mut writer =
         pgr::writer(outfile).with_context(|| format!("Failed to open writer for {}", outfile))?;
 
+    if args.get_flag("comp") {
+        return write_composition(args, &mut writer);
+    }
+
     // Init
     let mut total_len = 0usize;
     let mut total_base_cnt = [0usize; 5]; // A, C, G, T, N
@@ -93,3 +122,48 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     writer.flush()?;
     Ok(())
 }
+
+fn write_composition(args: &ArgMatches, writer: &mut impl Write) -> anyhow::Result<()> {
+    let is_total = args.get_flag("total");
+    let mut total = pgr::libs::fasta::stat::Composition::default();
+
+    writer.write_fmt(format_args!("#seq\tlen\tGC%\tN\tlower\tother\n"))?;
+
+    for infile in args.get_many::<String>("infiles").unwrap() {
+        let mut fa_in = pgr::libs::fmt::fa::reader(infile)
+            .with_context(|| format!("Failed to open reader for {}", infile))?;
+
+        for result in fa_in.records() {
+            let record = result?;
+            let name = String::from_utf8(record.name().into())?;
+            let comp = pgr::libs::fasta::stat::Composition::from_seq(record.sequence().as_ref());
+
+            write_comp_line(writer, &name, &comp)?;
+            total.add(&comp);
+        }
+    }
+
+    if is_total {
+        write_comp_line(writer, "total", &total)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_comp_line(
+    writer: &mut impl Write,
+    name: &str,
+    comp: &pgr::libs::fasta::stat::Composition,
+) -> anyhow::Result<()> {
+    writer.write_fmt(format_args!(
+        "{}\t{}\t{:.2}\t{}\t{}\t{}\n",
+        name,
+        comp.len,
+        comp.gc_pct(),
+        comp.n,
+        comp.lower,
+        comp.other,
+    ))?;
+    Ok(())
+}
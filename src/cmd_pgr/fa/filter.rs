@@ -16,6 +16,8 @@ Filters:
 * --max-len N: Keep sequences <= N bp
 * --max-n N: Keep sequences with <= N ambiguous bases (N/IUPAC)
 * --uniq: Remove duplicate sequence IDs
+* --name-regex RE: Keep sequences whose name matches RE
+* --invert: With --name-regex, keep sequences whose name does NOT match
 
 Formatters:
 * --upper: Convert sequences to uppercase
@@ -29,6 +31,8 @@ Notes:
 * Supports both plain text and gzipped (.gz) files
 * Reads from stdin if input file is 'stdin'
 * For duplicate IDs, keeps the first occurrence
+* --name-regex is matched against the original name, before --simplify;
+  use anchors (^...$) for whole-name matches
 * Not all faFilter options have been implemented
   Wildcards for names can be easily implemented with `pgr fa some`
 
@@ -42,6 +46,9 @@ Examples:
 3. Process multiple files:
    pgr fa filter *.fa --uniq --simplify -o output.fa
 
+4. Keep only numbered chromosomes:
+   pgr fa filter input.fa --name-regex '^chr\d+$'
+
 "###,
         )
         .arg(crate::cmd_pgr::args::infiles_arg("FASTA"))
@@ -62,6 +69,19 @@ Examples:
                 .action(ArgAction::SetTrue)
                 .help("Unique, removes duplicated ids, keeping the first"),
         )
+        .arg(
+            Arg::new("name_regex")
+                .long("name-regex")
+                .num_args(1)
+                .help("Pass sequences whose name matches this regex"),
+        )
+        .arg(
+            Arg::new("invert")
+                .long("invert")
+                .action(ArgAction::SetTrue)
+                .requires("name_regex")
+                .help("Invert --name-regex, passing sequences whose name does not match"),
+        )
         .arg(crate::cmd_pgr::args::upper_arg())
         .arg(
             Arg::new("iupac")
@@ -102,6 +122,15 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         opt_line
     );
 
+    // Compile once, and fail before touching any input
+    let opt_regex = match args.get_one::<String>("name_regex") {
+        Some(pat) => {
+            Some(regex::Regex::new(pat).with_context(|| format!("Invalid --name-regex: {}", pat))?)
+        }
+        None => None,
+    };
+    let is_invert = args.get_flag("invert");
+
     let is_uniq = args.get_flag("uniq");
     let is_upper = args.get_flag("upper");
     let is_iupac = args.get_flag("iupac");
@@ -122,6 +151,11 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
             let record = result?;
 
             let mut name = String::from_utf8(record.name().into())?;
+            if let Some(re) = &opt_regex {
+                if re.is_match(&name) == is_invert {
+                    continue;
+                }
+            }
             if is_simplify {
                 name = pgr::libs::io::simplify_name(&name).to_string();
             }
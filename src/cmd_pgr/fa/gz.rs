@@ -20,6 +20,9 @@ Notes:
 * Preserves original file
 * Default thread count is 1
 * Index creation is automatic
+* The output is always BGZF: a series of gzip members ending with the BGZF EOF
+  marker, so it can be indexed by `samtools faidx` and read by plain `gzip -d`
+* `--level` is an alias of `--compress-level`
 
 Output files:
 * <infile>.gz: Compressed file
@@ -50,6 +53,7 @@ Examples:
         .arg(
             Arg::new("compress_level")
                 .long("compress-level")
+                .visible_alias("level")
                 .value_parser(value_parser!(i32))
                 .num_args(1)
                 .default_value("-1")
@@ -0,0 +1,57 @@
+use clap::{ArgMatches, Command};
+
+/// Build the clap subcommand for index.
+pub fn make_subcommand() -> Command {
+    Command::new("index")
+        .about("Creates a samtools-compatible .fai index")
+        .after_help(
+            r###"
+This command scans a FASTA file once and writes a faidx index.
+
+Output format (one line per record):
+    name  length  offset  linebases  linewidth
+
+* length: number of bases
+* offset: byte offset of the first base
+* linebases: bases per line
+* linewidth: bytes per line, including the line terminator
+
+Notes:
+* The default output is <infile>.fai
+* Supports plain FASTA and BGZF compressed files (.gz); offsets are uncompressed offsets
+* Cannot read from stdin; plain gzip (.gz but not BGZF) is rejected
+* All lines of a record, except the last, must have the same length, and the last
+  may not be longer. Otherwise the record can't be indexed and an error is reported
+* `pgr fa range --fai` uses the index to seek directly to a region
+
+Examples:
+1. Index a FASTA file, writing input.fa.fai:
+   pgr fa index input.fa
+
+2. Write the index to stdout:
+   pgr fa index input.fa -o stdout
+
+"###,
+        )
+        .arg(crate::cmd_pgr::args::infile_arg_required_with_help(
+            "Input FASTA file to index",
+        ))
+        .arg(crate::cmd_pgr::args::outfile_arg_optional())
+}
+
+/// Execute the index command.
+pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
+    let infile = args.get_one::<String>("infile").unwrap();
+    anyhow::ensure!(infile != "stdin", "Cannot index stdin");
+
+    let outfile = if args.contains_id("outfile") {
+        crate::cmd_pgr::args::get_outfile(args).to_string()
+    } else {
+        format!("{}.fai", infile)
+    };
+
+    let entries = pgr::libs::loc::create_fai(infile)?;
+    pgr::libs::loc::write_fai(&entries, &outfile)?;
+
+    Ok(())
+}
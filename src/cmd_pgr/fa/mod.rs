@@ -2,6 +2,7 @@ pub mod count;
 pub mod dedup;
 pub mod filter;
 pub mod gz;
+pub mod index;
 pub mod mask;
 pub mod masked;
 pub mod n50;
@@ -15,6 +16,7 @@ pub mod size;
 pub mod some;
 pub mod split;
 pub mod to_2bit;
+pub mod translate;
 pub mod window;
 
 use clap::{ArgMatches, Command};
@@ -27,8 +29,8 @@ pub fn make_subcommand() -> Command {
 
 * info: size / count / masked / n50
 * records: one / some / order / split / window
-* transform: replace / rc / filter / dedup / mask / six-frame / to-2bit
-* indexing: gz / range
+* transform: replace / rc / filter / dedup / mask / six-frame / translate / to-2bit
+* indexing: gz / index / range
 
 "###,
         )
@@ -38,6 +40,7 @@ pub fn make_subcommand() -> Command {
         .subcommand(dedup::make_subcommand())
         .subcommand(filter::make_subcommand())
         .subcommand(gz::make_subcommand())
+        .subcommand(index::make_subcommand())
         .subcommand(mask::make_subcommand())
         .subcommand(masked::make_subcommand())
         .subcommand(n50::make_subcommand())
@@ -51,6 +54,7 @@ pub fn make_subcommand() -> Command {
         .subcommand(split::make_subcommand())
         .subcommand(six_frame::make_subcommand())
         .subcommand(to_2bit::make_subcommand())
+        .subcommand(translate::make_subcommand())
         .subcommand(window::make_subcommand())
 }
 /// Execute the fa command.
@@ -60,6 +64,7 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         Some(("dedup", sub_matches)) => dedup::execute(sub_matches),
         Some(("filter", sub_matches)) => filter::execute(sub_matches),
         Some(("gz", sub_matches)) => gz::execute(sub_matches),
+        Some(("index", sub_matches)) => index::execute(sub_matches),
         Some(("mask", sub_matches)) => mask::execute(sub_matches),
         Some(("masked", sub_matches)) => masked::execute(sub_matches),
         Some(("n50", sub_matches)) => n50::execute(sub_matches),
@@ -73,6 +78,7 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         Some(("some", sub_matches)) => some::execute(sub_matches),
         Some(("split", sub_matches)) => split::execute(sub_matches),
         Some(("to-2bit", sub_matches)) => to_2bit::execute(sub_matches),
+        Some(("translate", sub_matches)) => translate::execute(sub_matches),
         Some(("window", sub_matches)) => window::execute(sub_matches),
         _ => Ok(()),
     }
@@ -1,5 +1,5 @@
 use anyhow::Context;
-use clap::{ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 use std::collections::BTreeMap;
 use std::io::Write;
 
@@ -18,7 +18,8 @@ Notes:
 * All sequences are loaded into memory
 * Supports both plain text and gzipped (.gz) files
 * Reads from stdin if input file is 'stdin'
-* Missing sequences in the input file are silently skipped
+* Names in the list but absent from the input file are reported to stderr
+* With --append-missing, records not in the list follow in their original order
 
 Examples:
 1. Extract sequences in order specified by list.txt:
@@ -27,12 +28,21 @@ Examples:
 2. Process gzipped files:
    pgr fa order input.fa.gz list.txt -o output.fa.gz
 
+3. Put listed sequences first and keep the rest:
+   pgr fa order input.fa list.txt --append-missing
+
 "###,
         )
         .arg(crate::cmd_pgr::args::infile_arg_required_with_help(
             "Input FASTA file to process",
         ))
         .arg(crate::cmd_pgr::args::fa_name_list_arg(true))
+        .arg(
+            Arg::new("append_missing")
+                .long("append-missing")
+                .action(ArgAction::SetTrue)
+                .help("Append records not in the list, in their original order"),
+        )
         .arg(crate::cmd_pgr::args::outfile_arg())
 }
 
@@ -51,8 +61,11 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
             .into_iter()
             .collect();
 
+    let is_append = args.get_flag("append_missing");
+
     // Load records into a BTreeMap for efficient lookup
     let mut record_of = BTreeMap::new();
+    let mut unlisted = vec![];
 
     for result in fa_in.records() {
         let record = result?;
@@ -60,15 +73,23 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
 
         if list.contains(&name) {
             record_of.insert(name, record);
+        } else if is_append {
+            unlisted.push(record);
         }
     }
 
     for name in list.iter() {
         if let Some(record) = record_of.get(name) {
             fa_out.write_record(record)?;
+        } else {
+            log::warn!("{} not found in {}", name, infile);
         }
     }
 
+    for record in &unlisted {
+        fa_out.write_record(record)?;
+    }
+
     fa_out.get_mut().flush()?;
 
     Ok(())
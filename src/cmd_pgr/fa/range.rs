@@ -13,6 +13,8 @@ This command extracts sequence regions from FASTA files using genomic coordinate
 
 Range format:
     seq_name(strand):start-end
+    Several ranges may be joined by commas in one argument:
+    "chr1:1-100,chr1(-):200-300"
 
 Notes:
 * Cannot read from stdin or plain gzip
@@ -21,9 +23,13 @@ Notes:
 * Automatic index creation (.loc)
 * LRU caching for better performance
 * Reverse complement for negative strand
+* Ranges extending past the sequence end are clamped with a warning
 * All coordinates (<start> and <end>) are based on the positive strand
 * Sort range file for better performance
 * Cache size affects memory usage
+* With `--fai`, a samtools-style index (e.g. from `pgr fa index`) is used to seek
+  directly to each region instead of loading whole records; `--cache` and the
+  .loc index are not used then
 
 Examples:
 1. Single range:
@@ -31,6 +37,7 @@ Examples:
 
 2. Multiple ranges:
    pgr fa range input.fa "chr1:1-1000" "chr2(-):2000-3000"
+   pgr fa range input.fa "chr1:1-1000,chr2(-):2000-3000"
 
 3. From range file with larger cache:
    pgr fa range input.fa -r ranges.txt -c 10
@@ -38,6 +45,10 @@ Examples:
 4. Force update the index file:
    pgr fa range input.fa "chr1:1-1000" --update
 
+5. Seek through a .fai index:
+   pgr fa index input.fa
+   pgr fa range input.fa "chr1:1-1000" --fai input.fa.fai
+
 "###,
         )
         .arg(crate::cmd_pgr::args::infile_arg_required_with_help(
@@ -62,6 +73,13 @@ Examples:
                 .action(ArgAction::SetTrue)
                 .help("Force update the .loc index file"),
         )
+        .arg(
+            Arg::new("fai")
+                .long("fai")
+                .num_args(1)
+                .conflicts_with("update")
+                .help("Seek regions through this .fai index"),
+        )
 }
 
 /// Execute the range command.
@@ -72,7 +90,18 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let mut fa_out = pgr::libs::fmt::fa::writer(outfile)
         .with_context(|| format!("Failed to open writer for {}", outfile))?;
 
-    let ranges = crate::cmd_pgr::args::collect_ranges(args)?;
+    let ranges: Vec<String> = crate::cmd_pgr::args::collect_ranges(args)?
+        .iter()
+        .flat_map(|el| el.split(','))
+        .map(|el| el.trim().to_string())
+        .filter(|el| !el.is_empty())
+        .collect();
+
+    if let Some(fai_file) = args.get_one::<String>("fai") {
+        extract_by_fai(infile, fai_file, &ranges, &mut fa_out)?;
+        fa_out.get_mut().flush()?;
+        return Ok(());
+    }
 
     let opt_cache = *args.get_one::<std::num::NonZeroUsize>("cache").unwrap();
     let mut cache: lru::LruCache<String, noodles_fasta::Record> = lru::LruCache::new(opt_cache);
@@ -104,6 +133,25 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
             continue;
         }
 
+        let seq_len = record.sequence().len() as i32;
+        let rg = if *rg.end() > seq_len {
+            if *rg.start() > seq_len {
+                log::warn!(
+                    "[{}] starts beyond {} ({} bp), skipped",
+                    el,
+                    seq_id,
+                    seq_len
+                );
+                continue;
+            }
+            log::warn!("[{}] clamped to the end of {} ({} bp)", el, seq_id, seq_len);
+            let mut clamped = intspan::Range::from(&seq_id, *rg.start(), seq_len);
+            *clamped.strand_mut() = rg.strand().to_string();
+            clamped
+        } else {
+            rg
+        };
+
         let definition = noodles_fasta::record::Definition::new(rg.to_string(), None);
         let sequence = loc::slice_record(record, &rg)?;
         let record_rg = noodles_fasta::Record::new(definition, sequence);
@@ -115,3 +163,65 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Extract `ranges` by seeking through a `.fai` index, reading only the
+/// bytes of each region.
+fn extract_by_fai<W: Write>(
+    infile: &str,
+    fai_file: &str,
+    ranges: &[String],
+    fa_out: &mut noodles_fasta::io::Writer<W>,
+) -> anyhow::Result<()> {
+    let fai_of = loc::load_fai(fai_file)?;
+    let mut reader = loc::open_input(infile, pgr::is_bgzf(infile))?;
+
+    for el in ranges {
+        let rg = intspan::Range::from_str(el);
+        let seq_id = rg.chr().to_string();
+        let Some(entry) = fai_of.get(&seq_id) else {
+            log::warn!("{} for [{}] not found in the .fai index file", seq_id, el);
+            continue;
+        };
+        let seq_len = entry.length as i32;
+
+        // name only
+        if *rg.start() == 0 {
+            let bases = if entry.length == 0 {
+                vec![]
+            } else {
+                loc::fetch_fai_bases(&mut reader, entry, 1, entry.length)?
+            };
+            let record = pgr::libs::fmt::fa::new_record(&seq_id, &bases);
+            fa_out.write_record(&record)?;
+            continue;
+        }
+
+        if *rg.start() > seq_len {
+            log::warn!(
+                "[{}] starts beyond {} ({} bp), skipped",
+                el,
+                seq_id,
+                seq_len
+            );
+            continue;
+        }
+        let rg = if *rg.end() > seq_len {
+            log::warn!("[{}] clamped to the end of {} ({} bp)", el, seq_id, seq_len);
+            let mut clamped = intspan::Range::from(&seq_id, *rg.start(), seq_len);
+            *clamped.strand_mut() = rg.strand().to_string();
+            clamped
+        } else {
+            rg
+        };
+
+        let mut bases =
+            loc::fetch_fai_bases(&mut reader, entry, *rg.start() as u64, *rg.end() as u64)?;
+        if rg.strand() == "-" {
+            bases = pgr::libs::nt::rev_comp(&bases).collect();
+        }
+        let record = pgr::libs::fmt::fa::new_record(&rg.to_string(), &bases);
+        fa_out.write_record(&record)?;
+    }
+
+    Ok(())
+}
@@ -20,7 +20,12 @@ Notes:
 * Empty lines and lines starting with '#' are ignored in list
 * Supports both plain text and gzipped (.gz) files
 * Reads from stdin if input file is 'stdin'
-* Non-IUPAC characters are preserved as-is
+* Characters other than IUPAC codes, '-' and spaces are an error
+* Names that are ranges, e.g. `chr(+):10-20`, have their strand flipped
+  (`chr(-):10-20`) instead of getting a prefix. Coordinates are kept, as all
+  coordinates are based on the positive strand
+* `-c` takes precedence: every name is kept as-is, ranges included
+* `--annotate` appends '(RC)' to other names instead of prepending 'RC_'
 
 Examples:
 1. Reverse complement all sequences:
@@ -32,6 +37,9 @@ Examples:
 3. Keep original names (no 'RC_' prefix):
    pgr fa rc input.fa -c -o output.fa
 
+4. Mark plain names with an '(RC)' suffix:
+   pgr fa rc input.fa --annotate -o output.fa
+
 "###,
         )
         .arg(crate::cmd_pgr::args::infile_arg_required_with_help(
@@ -43,7 +51,14 @@ Examples:
                 .long("consistent")
                 .short('c')
                 .action(ArgAction::SetTrue)
-                .help("Keep the name consistent (don't prepend 'RC_')"),
+                .help("Keep all names unchanged, ranges included (don't prepend 'RC_')"),
+        )
+        .arg(
+            Arg::new("annotate")
+                .long("annotate")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("consistent")
+                .help("Append '(RC)' to names that are not ranges, instead of prepending 'RC_'"),
         )
         .arg(crate::cmd_pgr::args::outfile_arg())
 }
@@ -55,6 +70,7 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         .with_context(|| format!("Failed to open reader for {}", infile))?;
 
     let is_consistent = args.get_flag("consistent");
+    let is_annotate = args.get_flag("annotate");
 
     let outfile = crate::cmd_pgr::args::get_outfile(args);
     let mut fa_out = pgr::libs::fmt::fa::writer(outfile)
@@ -77,17 +93,29 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
             continue;
         }
 
+        let seq: &[u8] = record.sequence().as_ref();
+        if let Some(&b) = seq
+            .iter()
+            .find(|&&b| pgr::libs::nt::NT_COMP[b as usize] == 255)
+        {
+            anyhow::bail!("{}: invalid base '{}'", name, char::from(b));
+        }
+
+        let rg = intspan::Range::from_str(&name);
         let new_name = if is_consistent {
             name
+        } else if rg.is_valid() {
+            let mut rg = rg;
+            let strand = if rg.strand() == "-" { "+" } else { "-" };
+            *rg.strand_mut() = strand.to_string();
+            rg.to_string()
+        } else if is_annotate {
+            format!("{}(RC)", name)
         } else {
             format!("RC_{}", name)
         };
 
-        let seq_rc: Vec<u8> = record
-            .sequence()
-            .complement()
-            .rev()
-            .collect::<Result<_, _>>()?;
+        let seq_rc: Vec<u8> = pgr::libs::nt::rev_comp(seq).collect();
         let record_rc = pgr::libs::fmt::fa::new_record_preserving_desc(&new_name, &record, &seq_rc);
         fa_out.write_record(&record_rc)?;
     }
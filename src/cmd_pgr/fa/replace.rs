@@ -5,7 +5,7 @@ use std::io::Write;
 /// Build the clap subcommand for replace.
 pub fn make_subcommand() -> Command {
     Command::new("replace")
-        .about("Replaces headers of a FASTA file based on a TSV mapping")
+        .about("Replaces headers of a FASTA file based on a TSV mapping or a regex")
         .after_help(
             r###"
 This command replaces sequence headers in a FASTA file based on a TSV mapping file.
@@ -18,6 +18,11 @@ Notes:
 * The TSV file should contain two or more columns: the original name and the replacement name
 * If more than two columns are provided, the sequence will be duplicated for each replacement name
 * Multiple lines of the same original_name will also duplicate the record
+* `--map` is an alias of `--replace-tsv`
+* `--regex PAT REPL` substitutes all matches of PAT in the name with REPL; `$1`, `${name}`
+  refer to capture groups. It is applied after the TSV mapping
+* Only names are changed; descriptions and sequences are untouched
+* Names not in the TSV file pass through unchanged (unless --some)
 * Supports both plain text and gzipped (.gz) files
 * Reads from stdin if input file is 'stdin'
 
@@ -28,16 +33,31 @@ Examples:
 2. Only output sequences listed in the TSV file (like `pgr fa some`):
    pgr fa replace input.fa --replace-tsv replace.tsv --some -o output.fa
 
+3. Rename by a regex, e.g. `NC_000001.11` -> `NC_000001`:
+   pgr fa replace input.fa --regex '\.\d+$' '' -o output.fa
+
 "###,
         )
         .arg(crate::cmd_pgr::args::infile_arg_required_with_help(
             "Input FASTA file to process",
         ))
-        .arg(crate::cmd_pgr::args::replace_tsv_arg())
+        .arg(
+            crate::cmd_pgr::args::replace_tsv_arg()
+                .visible_alias("map")
+                .required_unless_present("regex"),
+        )
+        .arg(
+            Arg::new("regex")
+                .long("regex")
+                .num_args(2)
+                .value_names(["PAT", "REPL"])
+                .help("Substitute regex matches in names"),
+        )
         .arg(
             Arg::new("some")
                 .long("some")
                 .action(ArgAction::SetTrue)
+                .requires("replace_tsv")
                 .help("Only output sequences listed in the TSV file, like `pgr fa some`"),
         )
         .arg(crate::cmd_pgr::args::outfile_arg())
@@ -49,10 +69,29 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let mut fa_in = pgr::libs::fmt::fa::reader(infile)
         .with_context(|| format!("Failed to open reader for {}", infile))?;
 
-    let replace_of =
-        pgr::libs::io::read_replace_tsv(args.get_one::<String>("replace_tsv").unwrap())?;
+    let replace_of = match args.get_one::<String>("replace_tsv") {
+        Some(tsv) => pgr::libs::io::read_replace_tsv(tsv)?,
+        None => Default::default(),
+    };
     let is_some = args.get_flag("some");
 
+    // Compile once, and fail before reading any record
+    let opt_regex = match args.get_many::<String>("regex") {
+        Some(vals) => {
+            let vals: Vec<&String> = vals.collect();
+            let re = regex::Regex::new(vals[0])
+                .with_context(|| format!("Invalid --regex pattern: {}", vals[0]))?;
+            Some((re, vals[1].to_string()))
+        }
+        None => None,
+    };
+    let rename = |name: &str| -> String {
+        match &opt_regex {
+            Some((re, repl)) => re.replace_all(name, repl.as_str()).into_owned(),
+            None => name.to_string(),
+        }
+    };
+
     let outfile = crate::cmd_pgr::args::get_outfile(args);
     let mut fa_out = pgr::libs::fmt::fa::writer(outfile)
         .with_context(|| format!("Failed to open writer for {}", outfile))?;
@@ -64,14 +103,24 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         if let Some(new_names) = replace_of.get(&name) {
             for el in new_names {
                 let record_replace = pgr::libs::fmt::fa::new_record_preserving_desc(
-                    el,
+                    &rename(el),
                     &record,
                     &record.sequence()[..],
                 );
                 fa_out.write_record(&record_replace)?;
             }
         } else if !is_some {
-            fa_out.write_record(&record)?;
+            let new_name = rename(&name);
+            if new_name == name {
+                fa_out.write_record(&record)?;
+            } else {
+                let record_replace = pgr::libs::fmt::fa::new_record_preserving_desc(
+                    &new_name,
+                    &record,
+                    &record.sequence()[..],
+                );
+                fa_out.write_record(&record_replace)?;
+            }
         }
     }
 
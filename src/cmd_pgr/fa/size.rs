@@ -1,5 +1,5 @@
 use anyhow::Context;
-use clap::{ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 use std::io::Write;
 
 /// Build the clap subcommand for size.
@@ -14,6 +14,10 @@ and its length in a tab-separated format.
 Notes:
 * Supports both plain text and gzipped (.gz) files
 * Reads from stdin if input file is 'stdin'
+* --sort asc/desc orders records by size; ties keep their input order
+* Without --sort, each record is written as soon as it is read
+* --cumulative appends a third column with the running total
+* --total appends a final `total<TAB>sum` line
 
 Examples:
 1. Count bases in a single FASTA file:
@@ -25,11 +29,34 @@ Examples:
 3. Save the output to a file:
    pgr fa size input.fa -o output.tsv
 
+4. The largest contigs with running totals:
+   pgr fa size input.fa --sort desc --cumulative --total
+
 "###,
         )
         .arg(crate::cmd_pgr::args::infiles_arg("FASTA"))
         .arg(crate::cmd_pgr::args::outfile_arg())
         .arg(crate::cmd_pgr::args::no_ns_arg())
+        .arg(
+            Arg::new("sort")
+                .long("sort")
+                .action(ArgAction::Set)
+                .value_parser(["none", "asc", "desc"])
+                .default_value("none")
+                .help("Sort records by size"),
+        )
+        .arg(
+            Arg::new("cumulative")
+                .long("cumulative")
+                .action(ArgAction::SetTrue)
+                .help("Append a running total column"),
+        )
+        .arg(
+            Arg::new("total")
+                .long("total")
+                .action(ArgAction::SetTrue)
+                .help("Append a final line with the sum of sizes"),
+        )
 }
 
 /// Execute the size command.
@@ -38,6 +65,14 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let mut writer =
         pgr::writer(outfile).with_context(|| format!("Failed to open writer for {}", outfile))?;
     let no_ns = args.get_flag("no_ns");
+    let opt_sort = args.get_one::<String>("sort").unwrap().as_str();
+    let is_cumulative = args.get_flag("cumulative");
+    let is_total = args.get_flag("total");
+
+    // Records are only held in memory when they have to be sorted
+    let is_sorted = opt_sort != "none";
+    let mut sizes: Vec<(String, usize)> = vec![];
+    let mut sum = 0;
 
     for infile in args.get_many::<String>("infiles").unwrap() {
         let mut fa_in = pgr::libs::fmt::fa::reader(infile)
@@ -54,10 +89,43 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
                 seq.len()
             };
 
-            writer.write_fmt(format_args!("{}\t{}\n", name, len))?;
+            if is_sorted {
+                sizes.push((name, len));
+            } else {
+                sum += len;
+                write_size(&mut writer, &name, len, sum, is_cumulative)?;
+            }
         }
     }
 
+    match opt_sort {
+        "asc" => sizes.sort_by_key(|(_, len)| *len),
+        "desc" => sizes.sort_by_key(|(_, len)| std::cmp::Reverse(*len)),
+        _ => {}
+    }
+
+    for (name, len) in &sizes {
+        sum += len;
+        write_size(&mut writer, name, *len, sum, is_cumulative)?;
+    }
+    if is_total {
+        writer.write_fmt(format_args!("total\t{}\n", sum))?;
+    }
+
     writer.flush()?;
     Ok(())
 }
+
+fn write_size<W: Write>(
+    writer: &mut W,
+    name: &str,
+    len: usize,
+    sum: usize,
+    is_cumulative: bool,
+) -> std::io::Result<()> {
+    if is_cumulative {
+        writer.write_fmt(format_args!("{}\t{}\t{}\n", name, len, sum))
+    } else {
+        writer.write_fmt(format_args!("{}\t{}\n", name, len))
+    }
+}
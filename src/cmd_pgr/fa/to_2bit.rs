@@ -14,6 +14,8 @@ This command converts FASTA files to 2bit format.
 Notes:
 * Supports both plain text and gzipped (.gz) files
 * Reads from stdin if input file is 'stdin'
+* Lowercase (soft-masked) bases are stored as mask blocks, so `pgr 2bit to-fa`
+  restores the original case; --no-mask stores everything as uppercase
 
 Examples:
 1. Convert FASTA to 2bit:
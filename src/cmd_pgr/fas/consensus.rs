@@ -1,5 +1,5 @@
 use anyhow::Context;
-use clap::{Arg, ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 use pgr::libs::fmt::fas::{consensus_block, run_pipeline, ConsensusOptions};
 
 /// Build the clap subcommand for consensus.
@@ -55,6 +55,13 @@ Examples:
                     .help("Name of the consensus"),
             )
             .arg(crate::cmd_pgr::args::outgroup_arg())
+            .arg(
+                Arg::new("dot")
+                    .long("dot")
+                    .action(ArgAction::SetTrue)
+                    .hide(true)
+                    .help("Output the built-in POA graph of each block in DOT format (debugging)"),
+            )
             .arg(crate::cmd_pgr::args::parallel_arg())
             .arg(crate::cmd_pgr::args::outfile_arg()),
         true,
@@ -77,6 +84,7 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         engine: args.get_one::<String>("engine").unwrap().clone(),
         params: crate::cmd_pgr::args::get_poa_params(args),
         algo_code,
+        dot: args.get_flag("dot"),
     };
 
     let infiles: Vec<String> = args
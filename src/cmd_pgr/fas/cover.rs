@@ -1,6 +1,7 @@
 use anyhow::Context;
-use clap::{value_parser, Arg, ArgMatches, Command};
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
 use std::collections::BTreeMap;
+use std::io::Write;
 
 /// Build the clap subcommand for cover.
 pub fn make_subcommand() -> Command {
@@ -16,6 +17,9 @@ Notes:
 * The output is in JSON format, showing the coverage of sequences on chromosomes
 * Optionally, you can specify a species name to limit the output to that species
 * `--trim` trims alignment borders inward (default: 0); for lastz results, try --trim 10
+* `--min-cov N` only counts alignment columns where at least N sequences have a base;
+  gapped positions of each species are then left out
+* `--ranges` outputs the merged intervals as ranges, one per line, instead of JSON
 
 Examples:
 1. Calculate coverage for all species:
@@ -27,7 +31,10 @@ Examples:
 3. Trim alignment borders to avoid overlaps:
    pgr fas cover tests/fas/example.fas --trim 10
 
-4. Output results to a file:
+4. Merged intervals shared by at least 3 species, as ranges:
+   pgr fas cover tests/fas/example.fas --min-cov 3 --ranges
+
+5. Output results to a file:
    pgr fas cover tests/fas/example.fas -o output.json
 
 "###,
@@ -44,12 +51,27 @@ Examples:
                 .default_value("0")
                 .help("Trim align borders to avoid overlaps"),
         )
+        .arg(
+            Arg::new("min_cov")
+                .long("min-cov")
+                .num_args(1)
+                .value_parser(value_parser!(usize))
+                .default_value("1")
+                .help("Minimum number of sequences present in a column"),
+        )
+        .arg(
+            Arg::new("ranges")
+                .long("ranges")
+                .action(ArgAction::SetTrue)
+                .help("Output merged intervals as ranges instead of JSON"),
+        )
         .arg(crate::cmd_pgr::args::outfile_arg())
 }
 
 /// Execute the cover command.
 pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let opt_trim = *args.get_one::<i32>("trim").unwrap();
+    let opt_min_cov = *args.get_one::<usize>("min_cov").unwrap();
     let opt_name = args
         .get_one::<String>("name")
         .map(|s| s.as_str())
@@ -60,7 +82,33 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     for infile in args.get_many::<String>("infiles").unwrap() {
         let mut reader =
             pgr::reader(infile).with_context(|| format!("Failed to open reader for {}", infile))?;
-        pgr::libs::fmt::fas::aggregate_coverage_into(&mut reader, &mut res_of, opt_name, opt_trim)?;
+        pgr::libs::fmt::fas::aggregate_coverage_into(
+            &mut reader,
+            &mut res_of,
+            opt_name,
+            opt_trim,
+            opt_min_cov,
+        )?;
+    }
+
+    if args.get_flag("ranges") {
+        let outfile = crate::cmd_pgr::args::get_outfile(args);
+        let mut writer = pgr::writer(outfile)
+            .with_context(|| format!("Failed to open writer for {}", outfile))?;
+        for (name, set_of) in &res_of {
+            for (chr, ints) in set_of {
+                for (lower, upper) in ints.spans() {
+                    // With --name, ranges are reported without the species prefix
+                    if opt_name.is_empty() {
+                        writer.write_fmt(format_args!("{}.{}:{}-{}\n", name, chr, lower, upper))?;
+                    } else {
+                        writer.write_fmt(format_args!("{}:{}-{}\n", chr, lower, upper))?;
+                    }
+                }
+            }
+        }
+        writer.flush()?;
+        return Ok(());
     }
 
     let out_json = if !opt_name.is_empty() {
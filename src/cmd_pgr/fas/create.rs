@@ -1,14 +1,16 @@
 use anyhow::Context;
-use clap::{ArgMatches, Command};
+use clap::{Arg, ArgMatches, Command};
+use pgr::libs::chain::ChainReader;
+use pgr::libs::fmt::twobit::TwoBitFile;
 use std::io::Write;
 
 /// Build the clap subcommand for create.
 pub fn make_subcommand() -> Command {
     Command::new("create")
-        .about("Creates block FA files from links of ranges")
+        .about("Creates block FA files from links of ranges or chains")
         .after_help(
             r###"
-Creates block FA files from links of ranges.
+Creates block FA files from links of ranges, or from chains plus 2bit files.
 
 Notes:
 * Supports both plain text and gzipped (.gz) files
@@ -17,6 +19,12 @@ Notes:
 * Two styles of FA headers are supported:
   * `>chr` for single-genome self-alignments
   * `>name.chr` for multiple genomes
+* `--from-chain` builds one two-species block per chain instead, reading the aligned
+  sequences from `--t-2bit` and `--q-2bit`
+  * Gaps are inserted according to the chain's dt/dq columns
+  * Minus-strand queries are reverse-complemented; their ranges are reported in
+    forward-strand coordinates with a `-` strand
+  * Species names default to `target` and `query` (`--t-name`, `--q-name`)
 
 Examples:
 1. Create block FA files for a single genome:
@@ -25,15 +33,49 @@ Examples:
 2. Create block FA files for a specific species:
    pgr fas create tests/fas/I.connect.tsv -g tests/fas/genome.fa --name S288c
 
+3. Create blocks from a chain file:
+   pgr fas create --from-chain in.chain --t-2bit target.2bit --q-2bit query.2bit --t-name S288c --q-name RM11_1a
+
 "###,
         )
-        .arg(crate::cmd_pgr::args::genome_arg())
-        .arg(crate::cmd_pgr::args::infiles_arg_with_help(
-            "Input file(s) containing links of ranges",
-        ))
+        .arg(
+            crate::cmd_pgr::args::genome_arg()
+                .required(false)
+                .required_unless_present("from_chain"),
+        )
+        .arg(
+            crate::cmd_pgr::args::infiles_arg_with_help("Input file(s) containing links of ranges")
+                .required(false)
+                .required_unless_present("from_chain")
+                .conflicts_with("from_chain"),
+        )
         .arg(crate::cmd_pgr::args::fas_name_arg(
             "Set a species name for ranges (default: inferred from header)",
         ))
+        .arg(
+            Arg::new("from_chain")
+                .long("from-chain")
+                .num_args(1)
+                .requires_all(["t_2bit", "q_2bit"])
+                .conflicts_with_all(["genome", "name"])
+                .help("Create blocks from a chain file instead of links"),
+        )
+        .arg(
+            Arg::new("t_2bit")
+                .long("t-2bit")
+                .num_args(1)
+                .requires("from_chain")
+                .help("Target 2bit file for --from-chain"),
+        )
+        .arg(
+            Arg::new("q_2bit")
+                .long("q-2bit")
+                .num_args(1)
+                .requires("from_chain")
+                .help("Query 2bit file for --from-chain"),
+        )
+        .arg(crate::cmd_pgr::args::t_name_arg(Some("target")))
+        .arg(crate::cmd_pgr::args::q_name_arg(Some("query")))
         .arg(crate::cmd_pgr::args::outfile_arg())
 }
 
@@ -42,6 +84,13 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let outfile = crate::cmd_pgr::args::get_outfile(args);
     let mut writer =
         pgr::writer(outfile).with_context(|| format!("Failed to open writer for {}", outfile))?;
+
+    if let Some(chain_path) = args.get_one::<String>("from_chain") {
+        create_from_chain(args, chain_path, &mut writer)?;
+        writer.flush()?;
+        return Ok(());
+    }
+
     let opt_genome = args.get_one::<String>("genome").unwrap();
     let opt_name: &str = args
         .get_one::<String>("name")
@@ -57,3 +106,33 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     writer.flush()?;
     Ok(())
 }
+
+fn create_from_chain<W: Write>(
+    args: &ArgMatches,
+    chain_path: &str,
+    writer: &mut W,
+) -> anyhow::Result<()> {
+    let t_path = args.get_one::<String>("t_2bit").unwrap();
+    let q_path = args.get_one::<String>("q_2bit").unwrap();
+    let t_name = args.get_one::<String>("t_name").unwrap();
+    let q_name = args.get_one::<String>("q_name").unwrap();
+
+    let mut t_2bit =
+        TwoBitFile::open(t_path).with_context(|| format!("Failed to open 2bit file {}", t_path))?;
+    let mut q_2bit =
+        TwoBitFile::open(q_path).with_context(|| format!("Failed to open 2bit file {}", q_path))?;
+
+    let reader = ChainReader::new(
+        pgr::reader(chain_path)
+            .with_context(|| format!("Failed to open reader for {}", chain_path))?,
+    );
+    for chain_res in reader {
+        let chain = chain_res?;
+        let block =
+            pgr::libs::fmt::fas::chain_to_block(&chain, &mut t_2bit, &mut q_2bit, t_name, q_name)
+                .with_context(|| format!("Failed to build block for chain {}", chain.header.id))?;
+        block.write(writer)?;
+    }
+
+    Ok(())
+}
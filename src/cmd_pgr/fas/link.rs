@@ -1,5 +1,5 @@
 use anyhow::Context;
-use clap::{Arg, ArgAction, ArgGroup, ArgMatches, Command};
+use clap::{builder::PossibleValue, Arg, ArgAction, ArgGroup, ArgMatches, Command};
 use itertools::Itertools;
 use std::io::Write;
 
@@ -17,6 +17,10 @@ Notes:
 * By default, the tool outputs multi-lateral links (all ranges in a block)
 * Use `--pair` to output bilateral (pairwise) links
 * Use `--best` to output nearest-neighbor bilateral links based on sequence distance (deduplicated)
+* Use `--edges` to output a weighted edge list (`rangeA  rangeB  weight`), e.g. for `clust mcl`
+    * `--weight overlap`: number of aligned columns where both ranges have a base (default)
+    * `--weight identity`: fraction of identical bases among comparable columns
+    * Pairs without any aligned base are omitted
 
 Examples:
 1. Output multi-lateral links:
@@ -28,7 +32,10 @@ Examples:
 3. Output nearest-neighbor bilateral links:
    pgr fas link tests/fas/example.fas --best
 
-4. Output results to a file:
+4. Output a weighted edge list:
+   pgr fas link tests/fas/example.fas --edges --weight identity
+
+5. Output results to a file:
    pgr fas link tests/fas/example.fas -o output.tsv
 
 "###,
@@ -46,10 +53,28 @@ Examples:
                 .action(ArgAction::SetTrue)
                 .help("Output nearest-neighbor bilateral links"),
         )
+        .arg(
+            Arg::new("edges")
+                .long("edges")
+                .action(ArgAction::SetTrue)
+                .help("Output bilateral links as a weighted edge list"),
+        )
+        .arg(
+            Arg::new("weight")
+                .long("weight")
+                .num_args(1)
+                .default_value("overlap")
+                .value_parser([
+                    PossibleValue::new("overlap"),
+                    PossibleValue::new("identity"),
+                ])
+                .requires("edges")
+                .help("Edge weight for --edges"),
+        )
         .arg(crate::cmd_pgr::args::outfile_arg())
         .group(
             ArgGroup::new("link_mode")
-                .args(["pair", "best"])
+                .args(["pair", "best", "edges"])
                 .multiple(false),
         )
 }
@@ -61,6 +86,8 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         pgr::writer(outfile).with_context(|| format!("Failed to open writer for {}", outfile))?;
     let is_pair = args.get_flag("pair");
     let is_best = args.get_flag("best");
+    let is_edges = args.get_flag("edges");
+    let weight = args.get_one::<String>("weight").unwrap();
 
     for infile in args.get_many::<String>("infiles").unwrap() {
         let mut reader =
@@ -84,6 +111,25 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
                 for (i, j) in best_pair {
                     writer.write_all(format!("{}\t{}\n", headers[i], headers[j]).as_ref())?;
                 }
+            } else if is_edges {
+                for (i, j) in (0..headers.len()).tuple_combinations() {
+                    let seq1 = block.entries[i].seq();
+                    let seq2 = block.entries[j].seq();
+                    let overlap = pgr::libs::alignment::pair_overlap(seq1, seq2)?;
+                    if overlap == 0 {
+                        continue;
+                    }
+                    let w = match weight.as_str() {
+                        "identity" => match pgr::libs::alignment::pair_d(seq1, seq2) {
+                            Ok(d) => format!("{:.4}", 1.0 - d),
+                            // Only ambiguous bases overlap
+                            Err(_) => continue,
+                        },
+                        _ => overlap.to_string(),
+                    };
+                    writer
+                        .write_all(format!("{}\t{}\t{}\n", headers[i], headers[j], w).as_ref())?;
+                }
             } else {
                 // Output multi-lateral links
                 writer.write_all(format!("{}\n", headers.join("\t")).as_ref())?;
@@ -8,6 +8,7 @@ pub mod create;
 pub mod filter;
 pub mod join;
 pub mod link;
+pub mod logo;
 pub mod multiz;
 pub mod name;
 pub mod refine;
@@ -30,7 +31,7 @@ pub fn make_subcommand() -> Command {
         .after_help(
             r###"Subcommand groups:
 
-* info: check / cover / link / name / stat
+* info: check / cover / link / logo / name / stat
 * subset: filter / slice / subset
 * transform: concat / consensus / join / multiz / refine / replace
 * file: create / separate / split
@@ -48,6 +49,7 @@ pub fn make_subcommand() -> Command {
         .subcommand(filter::make_subcommand())
         .subcommand(join::make_subcommand())
         .subcommand(link::make_subcommand())
+        .subcommand(logo::make_subcommand())
         .subcommand(multiz::make_subcommand())
         .subcommand(name::make_subcommand())
         .subcommand(refine::make_subcommand())
@@ -73,6 +75,7 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         Some(("filter", sub_matches)) => filter::execute(sub_matches),
         Some(("join", sub_matches)) => join::execute(sub_matches),
         Some(("link", sub_matches)) => link::execute(sub_matches),
+        Some(("logo", sub_matches)) => logo::execute(sub_matches),
         Some(("multiz", sub_matches)) => multiz::execute(sub_matches),
         Some(("name", sub_matches)) => name::execute(sub_matches),
         Some(("refine", sub_matches)) => refine::execute(sub_matches),
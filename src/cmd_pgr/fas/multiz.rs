@@ -14,6 +14,14 @@ Notes:
 * Takes two or more .fas inputs that share a reference name.
 * Automatically derives windows from reference coverage with radius padding.
 * Supports core (intersection) and union modes on windows and species.
+* Blocks are merged only when their references are identical once gaps are removed;
+  `--min-identity` (e.g. 0.99) also accepts references differing by substitutions
+* When the banded DP runs along the edge of its band, the band is doubled and the
+  alignment retried, up to `--max-band`; widened windows are reported on stderr
+* Windows are merged independently; `--parallel` merges them on several threads.
+  The output order always follows the windows, regardless of the thread count.
+* `--polish` re-aligns the rows of each merged block with the builtin POA aligner,
+  removing gap-only columns and inconsistent gap placement left by progressive merging
 
 Examples:
 1. Core mode merge with default radius:
@@ -24,6 +32,12 @@ Examples:
 
 3. Write merged blocks to a file:
    pgr fas multiz -r S288c tests/fas/S288cvsRM11_1a.slice.fas tests/fas/S288cvsSpar.slice.fas -o merged.fas
+
+4. Merge windows with 4 threads:
+   pgr fas multiz -r S288c --parallel 4 tests/fas/S288cvsRM11_1a.slice.fas tests/fas/S288cvsSpar.slice.fas
+
+5. Polish merged blocks with POA:
+   pgr fas multiz -r S288c --polish tests/fas/S288cvsRM11_1a.slice.fas tests/fas/S288cvsSpar.slice.fas
 "###,
         )
         .arg(
@@ -46,6 +60,14 @@ Examples:
                 .default_value("30")
                 .help("Banded DP radius around the reference diagonal"),
         )
+        .arg(
+            Arg::new("max_band")
+                .long("max-band")
+                .value_parser(value_parser!(usize))
+                .num_args(1)
+                .default_value("480")
+                .help("Maximum band radius when widening the banded DP"),
+        )
         .arg(
             Arg::new("min_width")
                 .long("min-width")
@@ -54,6 +76,14 @@ Examples:
                 .default_value("1")
                 .help("Minimum window width to consider for merging"),
         )
+        .arg(
+            Arg::new("min_identity")
+                .long("min-identity")
+                .value_parser(value_parser!(f64))
+                .num_args(1)
+                .default_value("1.0")
+                .help("Minimum identity between ungapped references to merge blocks"),
+        )
         .arg(crate::cmd_pgr::args::mode_arg(
             "core",
             &["core", "union"],
@@ -91,6 +121,13 @@ Examples:
                 .value_parser(value_parser!(i32))
                 .help("Gap penalty for scoring matrix"),
         )
+        .arg(
+            Arg::new("polish")
+                .long("polish")
+                .action(clap::ArgAction::SetTrue)
+                .help("Re-align each merged block with POA"),
+        )
+        .arg(crate::cmd_pgr::args::parallel_arg())
         .arg(crate::cmd_pgr::args::outfile_arg())
 }
 
@@ -98,7 +135,13 @@ Examples:
 pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let ref_name = args.get_one::<String>("ref_name").unwrap().to_string();
     let radius = *args.get_one::<usize>("radius").unwrap();
+    let max_band = *args.get_one::<usize>("max_band").unwrap();
     let min_width = *args.get_one::<usize>("min_width").unwrap();
+    let min_identity = *args.get_one::<f64>("min_identity").unwrap();
+    anyhow::ensure!(
+        (0.0..=1.0).contains(&min_identity),
+        "--min-identity must be between 0 and 1"
+    );
     let mode_str = args.get_one::<String>("mode").unwrap();
     let gap_model_str = args.get_one::<String>("gap_model").unwrap();
     let score_matrix = args.get_one::<String>("score_scheme").cloned();
@@ -125,7 +168,9 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let cfg = pgr::libs::fas_multiz::FasMultizConfig {
         ref_name: ref_name.clone(),
         radius,
+        max_band,
         min_width,
+        min_identity,
         mode,
         match_score,
         mismatch_score,
@@ -134,6 +179,8 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         gap_open,
         gap_extend,
         score_matrix,
+        parallel: *args.get_one::<usize>("parallel").unwrap(),
+        polish: args.get_flag("polish"),
     };
 
     let infiles: Vec<String> = args
@@ -149,10 +196,7 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         pgr::writer(outfile).with_context(|| format!("Failed to open writer for {}", outfile))?;
 
     for block in blocks {
-        for entry in &block.entries {
-            writer.write_all(entry.to_string().as_ref())?;
-        }
-        writer.write_all(b"\n")?;
+        block.write(&mut writer)?;
     }
 
     writer.flush()?;
@@ -1,5 +1,5 @@
 use anyhow::Context;
-use clap::{ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgGroup, ArgMatches, Command};
 use indexmap::IndexMap;
 use std::io::Write;
 
@@ -16,6 +16,11 @@ Notes:
 * Reads from stdin if input file is 'stdin'
 * By default, the subcommand outputs a list of unique species names
 * Use `--count` to also output the number of occurrences of each species name
+* Use `--check-dup` to report blocks containing a species name more than once,
+  as `block_index  name` (0-based block index)
+* Use `--rename-dup` to output the blocks with repeated names made unique by
+  appending `_1`, `_2`, ...; the first occurrence keeps its name
+    * An underscore is used because a dot separates the name from the chromosome in headers
 
 Examples:
 1. Output all species names:
@@ -24,13 +29,36 @@ Examples:
 2. Output species names with occurrence counts:
    pgr fas name tests/fas/example.fas --count
 
+3. Report blocks with duplicated names:
+   pgr fas name tests/fas/example.fas --check-dup
+
+4. Make names unique within each block:
+   pgr fas name tests/fas/example.fas --rename-dup -o renamed.fas
+
 "###,
         )
         .arg(crate::cmd_pgr::args::infiles_arg("block FA"))
         .arg(crate::cmd_pgr::args::count_arg(
             "Output species names with occurrence counts",
         ))
+        .arg(
+            Arg::new("check_dup")
+                .long("check-dup")
+                .action(ArgAction::SetTrue)
+                .help("Report blocks containing duplicated names"),
+        )
+        .arg(
+            Arg::new("rename_dup")
+                .long("rename-dup")
+                .action(ArgAction::SetTrue)
+                .help("Output blocks with duplicated names made unique"),
+        )
         .arg(crate::cmd_pgr::args::outfile_arg())
+        .group(
+            ArgGroup::new("name_mode")
+                .args(["count", "check_dup", "rename_dup"])
+                .multiple(false),
+        )
 }
 
 /// Execute the name command.
@@ -39,6 +67,31 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let mut writer =
         pgr::writer(outfile).with_context(|| format!("Failed to open writer for {}", outfile))?;
     let is_count = args.get_flag("count");
+    let is_check_dup = args.get_flag("check_dup");
+    let is_rename_dup = args.get_flag("rename_dup");
+
+    if is_check_dup || is_rename_dup {
+        let mut block_idx = 0;
+        for infile in args.get_many::<String>("infiles").unwrap() {
+            let mut reader = pgr::reader(infile)
+                .with_context(|| format!("Failed to open reader for {}", infile))?;
+            for block_result in pgr::libs::fmt::fas::iter_fas_blocks(&mut reader) {
+                let mut block = block_result?;
+                if is_check_dup {
+                    for name in block.duplicate_names() {
+                        writeln!(writer, "{}\t{}", block_idx, name)?;
+                    }
+                } else {
+                    block.rename_duplicates();
+                    block.write(&mut writer)?;
+                }
+                block_idx += 1;
+            }
+        }
+
+        writer.flush()?;
+        return Ok(());
+    }
 
     let mut counts: IndexMap<String, i32> = IndexMap::new();
     for infile in args.get_many::<String>("infiles").unwrap() {
@@ -4,7 +4,7 @@ use pgr::libs::fmt::fas::{refine_block, run_pipeline, RefineOptions};
 
 /// Build the clap subcommand for refine.
 pub fn make_subcommand() -> Command {
-    Command::new("refine")
+    let cmd = Command::new("refine")
         .about(
             "Realigns block FA files with built-in or external programs and trims unwanted regions",
         )
@@ -19,10 +19,19 @@ Notes:
     * `builtin` (default): built-in Rust POA implementation.
     * `clustalw`, `mafft`, `muscle`, `spoa`: external commands.
     * `none`: skip realigning (useful for trimming only).
+* Existing gaps are removed before `builtin` realigns a block
+* Scoring parameters for `builtin`:
+    * `--match` (default: 5), `--mismatch` (default: -4)
+    * `--gap-open` (default: -8), `--gap-extend` (default: -6)
 * `--chop` trims head/tail indels (default: 0, disabled)
 * `--quick` aligns only indel-adjacent regions (useful for .axt/.maf conversions)
     * `--indel-pad` enlarges indel regions in quick mode (default: 50)
     * `--fill` fills holes between indels in quick mode (default: 50)
+* `--codon` aligns translated sequences and maps them back to codons
+    * Gaps then always come in multiples of three, avoiding frameshift artifacts
+    * The first sequence is the reference and is read in frame 1
+    * Blocks with an out-of-frame reference (internal stop codon), or any
+      sequence length not divisible by 3, are aligned as nucleotides
 * Parallel mode (`-p`) may change output order
 
 Examples:
@@ -32,12 +41,18 @@ Examples:
 2. Realign using mafft with 4 threads:
    pgr fas refine tests/fas/refine.fas --engine mafft --parallel 4
 
-3. Quick alignment for files converted from pairwise alignments:
+3. Realign using builtin with a stiffer gap penalty:
+   pgr fas refine tests/fas/refine.fas --gap-open -12
+
+4. Quick alignment for files converted from pairwise alignments:
    pgr fas refine tests/fas/refine.fas --quick --parallel 4
 
-4. Output results to a file:
+5. Output results to a file:
    pgr fas refine tests/fas/refine.fas -o output.fas
 
+6. Codon-aware realignment of coding blocks:
+   pgr fas refine tests/fas/refine.fas --codon
+
 "###,
         )
         .arg(crate::cmd_pgr::args::infiles_arg("block FA"))
@@ -61,6 +76,13 @@ Examples:
                 .action(ArgAction::SetTrue)
                 .help("Quick mode, only aligns indel adjacent regions"),
         )
+        .arg(
+            Arg::new("is_codon")
+                .long("codon")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("is_quick")
+                .help("Codon-aware mode, keeps gaps in multiples of three"),
+        )
         .arg(
             Arg::new("indel_pad")
                 .long("indel-pad")
@@ -78,7 +100,9 @@ Examples:
                 .help("In quick mode, fill holes between indel"),
         )
         .arg(crate::cmd_pgr::args::parallel_arg())
-        .arg(crate::cmd_pgr::args::outfile_arg())
+        .arg(crate::cmd_pgr::args::outfile_arg());
+
+    crate::cmd_pgr::args::add_poa_args(cmd, false)
 }
 
 /// Execute the refine command.
@@ -93,8 +117,10 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         has_outgroup: args.get_flag("outgroup"),
         chop: *args.get_one::<usize>("chop").unwrap(),
         is_quick: args.get_flag("is_quick"),
+        is_codon: args.get_flag("is_codon"),
         pad: *args.get_one::<usize>("indel_pad").unwrap(),
         fill: *args.get_one::<usize>("fill").unwrap(),
+        params: crate::cmd_pgr::args::get_poa_params(args),
     };
 
     let infiles: Vec<String> = args
@@ -1,11 +1,11 @@
 use anyhow::Context;
-use clap::{ArgMatches, Command};
+use clap::{Arg, ArgMatches, Command};
 use std::io::Write;
 
 /// Build the clap subcommand for replace.
 pub fn make_subcommand() -> Command {
     Command::new("replace")
-        .about("Replaces headers in block FA files")
+        .about("Replaces headers or sequences in block FA files")
         .after_help(
             r###"
 Replaces headers in block FA files using a TSV file.
@@ -18,6 +18,12 @@ Notes:
     * Two fields: `original_name<TAB>new_name` replaces the matching header
     * Three or more fields: duplicates the entire alignment block once for every replacement name after the first
 * If a block contains multiple matching headers (including duplicate headers), the block is kept unchanged and a warning is emitted
+* `--with-consensus <species>` instead replaces that species' sequence with the block
+  consensus, as `pgr fas consensus` computes it with default options:
+    * Gaps are kept, so the row stays aligned with the others
+    * The header keeps its name, chromosome, strand and start; the end follows the
+      ungapped length of the consensus
+    * Blocks without the species are left unchanged
 
 Examples:
 1. Replace species names in a block FA file:
@@ -26,9 +32,24 @@ Examples:
 2. Output results to a file:
    pgr fas replace tests/fas/example.fas --replace-tsv tests/fas/replace.tsv -o output.fas
 
+3. Replace the S288c row with the block consensus:
+   pgr fas replace tests/fas/example.fas --with-consensus S288c
+
 "###,
         )
-        .arg(crate::cmd_pgr::args::replace_tsv_arg())
+        .arg(
+            crate::cmd_pgr::args::replace_tsv_arg()
+                .required(false)
+                .required_unless_present("with_consensus")
+                .conflicts_with("with_consensus"),
+        )
+        .arg(
+            Arg::new("with_consensus")
+                .long("with-consensus")
+                .num_args(1)
+                .value_name("species")
+                .help("Replace the sequence of this species with the block consensus"),
+        )
         .arg(crate::cmd_pgr::args::infiles_arg("block FA"))
         .arg(crate::cmd_pgr::args::outfile_arg())
 }
@@ -39,6 +60,22 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let mut writer =
         pgr::writer(outfile).with_context(|| format!("Failed to open writer for {}", outfile))?;
 
+    if let Some(species) = args.get_one::<String>("with_consensus") {
+        for infile in args.get_many::<String>("infiles").unwrap() {
+            let mut reader = pgr::reader(infile)
+                .with_context(|| format!("Failed to open reader for {}", infile))?;
+
+            for block_result in pgr::libs::fmt::fas::iter_fas_blocks(&mut reader) {
+                let mut block = block_result?;
+                pgr::libs::fmt::fas::replace_with_consensus(&mut block, species)?;
+                block.write(&mut writer)?;
+            }
+        }
+
+        writer.flush()?;
+        return Ok(());
+    }
+
     let replace_of =
         pgr::libs::io::read_replace_tsv(args.get_one::<String>("replace_tsv").unwrap())?;
 
@@ -1,8 +1,10 @@
 use anyhow::Context;
 use clap::{Arg, ArgAction, ArgMatches, Command};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::{BufWriter, Write};
 
+use pgr::libs::fmt::fas::FasBlock;
+
 /// Build the clap subcommand for separate.
 pub fn make_subcommand() -> Command {
     Command::new("separate")
@@ -17,6 +19,11 @@ Notes:
 * Dashes ('-') in sequences are removed
 * If the target file already exists, it will be overwritten
 * Optionally, sequences can be reverse-complemented if the chromosome strand is '-'
+* `--template` sets the output path relative to the output directory, overriding `--suffix`;
+  placeholders: {species}, {chr}, {start}, {end}; subdirectories are created as needed
+* A template without {species} is rejected if the input has more than one species;
+  the check runs before any file is written
+* With {start} or {end}, each file is closed as soon as its block is written
 
 Examples:
 1. Separate block FA files by species:
@@ -31,6 +38,9 @@ Examples:
 4. Output to stdout:
    pgr fas separate tests/fas/example.fas
 
+5. One directory per species, one file per chromosome:
+   pgr fas separate tests/fas/example.fas -o output_dir --template "{species}/{chr}.fa"
+
 "###,
         )
         .arg(crate::cmd_pgr::args::infiles_arg("block FA"))
@@ -41,6 +51,12 @@ Examples:
                 .action(ArgAction::SetTrue)
                 .help("Reverse-complement sequences when chromosome strand is '-'"),
         )
+        .arg(
+            Arg::new("template")
+                .long("template")
+                .num_args(1)
+                .help("Output filename template, e.g. \"{species}/{chr}.fa\""),
+        )
         .arg(crate::cmd_pgr::args::outdir_arg())
 }
 
@@ -52,61 +68,140 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     }
 
     let opt_suffix = args.get_one::<String>("suffix").unwrap();
-    let is_rc = args.get_flag("rc");
+    let default_template = format!("{{species}}{}", opt_suffix);
+    let template = args
+        .get_one::<String>("template")
+        .unwrap_or(&default_template);
 
-    let mut file_of: BTreeMap<String, BufWriter<std::fs::File>> = BTreeMap::new();
+    let mut separator = Separator {
+        outdir,
+        template,
+        is_rc: args.get_flag("rc"),
+        is_per_block: template.contains("{start}") || template.contains("{end}"),
+        file_of: BTreeMap::new(),
+        opened: BTreeSet::new(),
+    };
     let stdout = std::io::stdout();
     let mut out = stdout.lock();
+
+    // Without {species}, all blocks are read first so that a second species
+    // is rejected before any file is written
+    let check_species = outdir != "stdout" && !template.contains("{species}");
+    let mut pending: Vec<FasBlock> = vec![];
+    let mut first_species: Option<String> = None;
+
     for infile in args.get_many::<String>("infiles").unwrap() {
         let mut reader =
             pgr::reader(infile).with_context(|| format!("Failed to open reader for {}", infile))?;
 
         for block_result in pgr::libs::fmt::fas::iter_fas_blocks(&mut reader) {
             let block = block_result?;
-            for (idx, entry) in block.entries.iter().enumerate() {
-                let entry_name = &block.names[idx];
-
-                let (range_str, seq) = if is_rc && entry.range().strand() == "-" {
-                    let mut range = entry.range().clone();
-                    *range.strand_mut() = "+".to_string();
-                    let rc_seq = pgr::libs::nt::rev_comp(entry.seq()).collect::<Vec<u8>>();
-                    (
-                        range.to_string(),
-                        pgr::libs::fmt::fas::format_sequence(&rc_seq, true, false),
-                    )
-                } else {
-                    (
-                        entry.range().to_string(),
-                        pgr::libs::fmt::fas::format_sequence(entry.seq(), true, false),
-                    )
-                };
-
-                if outdir == "stdout" {
-                    writeln!(out, ">{}\n{}", range_str, seq)?;
+            if !check_species {
+                separator.write_block(&block, &mut out)?;
+                continue;
+            }
+
+            for name in &block.names {
+                if let Some(first) = &first_species {
+                    anyhow::ensure!(
+                        first == name,
+                        "--template \"{}\" lacks {{species}} but the input has multiple species ({}, {})",
+                        template,
+                        first,
+                        name
+                    );
                 } else {
-                    let file_key = pgr::libs::io::sanitize_filename(entry_name);
-                    let fh = if let Some(fh) = file_of.get_mut(&file_key) {
-                        fh
-                    } else {
-                        let path = std::path::Path::new(outdir)
-                            .join(format!("{}{}", file_key, opt_suffix));
-                        let file = std::fs::OpenOptions::new()
-                            .create(true)
-                            .write(true)
-                            .truncate(true)
-                            .open(path)?;
-                        file_of.entry(file_key).or_insert(BufWriter::new(file))
-                    };
-                    writeln!(fh, ">{}\n{}", range_str, seq)?;
+                    first_species = Some(name.to_string());
                 }
             }
+            pending.push(block);
         }
     }
 
-    for fh in file_of.values_mut() {
+    for block in &pending {
+        separator.write_block(block, &mut out)?;
+    }
+
+    for fh in separator.file_of.values_mut() {
         fh.flush()?;
     }
     out.flush()?;
 
     Ok(())
 }
+
+/// Writes the entries of blocks to stdout or to per-template files.
+struct Separator<'a> {
+    outdir: &'a str,
+    template: &'a str,
+    is_rc: bool,
+    /// With {start} or {end}, every file holds a single block and is closed right away
+    is_per_block: bool,
+    file_of: BTreeMap<String, BufWriter<std::fs::File>>,
+    /// Files created by this run; reopening one appends instead of truncating
+    opened: BTreeSet<String>,
+}
+
+impl Separator<'_> {
+    fn write_block(&mut self, block: &FasBlock, out: &mut impl Write) -> anyhow::Result<()> {
+        for (idx, entry) in block.entries.iter().enumerate() {
+            let entry_name = &block.names[idx];
+
+            let (range_str, seq) = if self.is_rc && entry.range().strand() == "-" {
+                let mut range = entry.range().clone();
+                *range.strand_mut() = "+".to_string();
+                let rc_seq = pgr::libs::nt::rev_comp(entry.seq()).collect::<Vec<u8>>();
+                (
+                    range.to_string(),
+                    pgr::libs::fmt::fas::format_sequence(&rc_seq, true, false),
+                )
+            } else {
+                (
+                    entry.range().to_string(),
+                    pgr::libs::fmt::fas::format_sequence(entry.seq(), true, false),
+                )
+            };
+
+            if self.outdir == "stdout" {
+                writeln!(out, ">{}\n{}", range_str, seq)?;
+                continue;
+            }
+
+            let file_key = render_template(self.template, entry_name, entry.range());
+            if !self.file_of.contains_key(&file_key) {
+                let path = std::path::Path::new(self.outdir).join(&file_key);
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                let is_new = self.opened.insert(file_key.clone());
+                let file = std::fs::OpenOptions::new()
+                    .create(true)
+                    .write(true)
+                    .truncate(is_new)
+                    .append(!is_new)
+                    .open(&path)
+                    .with_context(|| format!("Failed to open {}", path.display()))?;
+                self.file_of.insert(file_key.clone(), BufWriter::new(file));
+            }
+            let fh = self.file_of.get_mut(&file_key).unwrap();
+            writeln!(fh, ">{}\n{}", range_str, seq)?;
+        }
+
+        if self.is_per_block {
+            for mut fh in std::mem::take(&mut self.file_of).into_values() {
+                fh.flush()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Fill the placeholders of an output filename template.
+fn render_template(template: &str, species: &str, range: &intspan::Range) -> String {
+    template
+        .replace("{species}", &pgr::libs::io::sanitize_filename(species))
+        .replace("{chr}", &pgr::libs::io::sanitize_filename(range.chr()))
+        .replace("{start}", &range.start().to_string())
+        .replace("{end}", &range.end().to_string())
+}
@@ -16,6 +16,11 @@ Notes:
 * Reads from stdin if input file is 'stdin'
 * By default, each alignment block is written to a separate file
 * Use `--chr` to split files by chromosome
+* Use `--by-chrom <name>` to split files by the chromosome of the reference row
+  * The reference row is the one whose species is <name>
+  * Files are named after the chromosome alone, e.g. `I.fas`
+  * Blocks without a reference row are written to `unassigned.fas`
+  * Blocks are written as they are read, so memory use stays flat
 * Use `--simple` to simplify headers by keeping only species names
 
 Examples:
@@ -34,6 +39,9 @@ Examples:
 5. Output to stdout:
    pgr fas split tests/fas/example.fas
 
+6. Split by the chromosomes of S288c:
+   pgr fas split tests/fas/example.fas -o output_dir --by-chrom S288c
+
 "###,
         )
         .arg(crate::cmd_pgr::args::infiles_arg("block FA"))
@@ -44,6 +52,14 @@ Examples:
                 .action(ArgAction::SetTrue)
                 .help("Split files by chromosomes"),
         )
+        .arg(
+            Arg::new("by_chrom")
+                .long("by-chrom")
+                .num_args(1)
+                .value_name("name")
+                .conflicts_with("chr")
+                .help("Split files by the chromosome of the reference species <name>"),
+        )
         .arg(
             Arg::new("simple")
                 .long("simple")
@@ -63,6 +79,7 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let opt_suffix = args.get_one::<String>("suffix").unwrap();
     let is_chr = args.get_flag("chr");
     let is_simple = args.get_flag("simple");
+    let opt_by_chrom = args.get_one::<String>("by_chrom");
 
     let mut file_of: BTreeMap<String, BufWriter<std::fs::File>> = BTreeMap::new();
     let stdout = std::io::stdout();
@@ -76,10 +93,14 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         {
             let block = block_result
                 .with_context(|| format!("read block {} from {}", block_idx, infile))?;
-            let filename = match pgr::libs::fmt::fas::split_block_key(&block, is_chr) {
-                Some(k) => pgr::libs::io::sanitize_filename(&k),
-                None => continue,
+            let key = match opt_by_chrom {
+                Some(ref_name) => pgr::libs::fmt::fas::split_block_chrom(&block, ref_name),
+                None => match pgr::libs::fmt::fas::split_block_key(&block, is_chr) {
+                    Some(k) => k,
+                    None => continue,
+                },
             };
+            let filename = pgr::libs::io::sanitize_filename(&key);
             let block_str = pgr::libs::fmt::fas::format_split_block(&block, is_simple)?;
 
             if outdir == "stdout" {
@@ -1,6 +1,6 @@
 use anyhow::Context;
-use clap::{Arg, ArgAction, ArgMatches, Command};
-use std::collections::HashMap;
+use clap::{Arg, ArgAction, ArgGroup, ArgMatches, Command};
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
 
 /// Build the clap subcommand for subset.
@@ -16,6 +16,9 @@ Notes:
 * Reads from stdin if input file is 'stdin'
 * The --required file lists species names to keep, one per line
 * The order of species in the output follows the order in the <name.lst> file
+* `--keep` and `--drop` filter species while preserving their order within each block
+* Exactly one of --required, --keep and --drop must be given
+* With `--keep`/`--drop`, blocks left without any species are skipped
 
 Examples:
 1. Extract a subset of species:
@@ -27,9 +30,30 @@ Examples:
 3. Output results to a file:
    pgr fas subset tests/fas/example.fas -R tests/fas/name.lst -o output.fas
 
+4. Drop the listed species from every block:
+   pgr fas subset tests/fas/example.fas --drop tests/fas/name.lst
+
 "###,
         )
-        .arg(crate::cmd_pgr::args::required_species_list_arg())
+        .arg(crate::cmd_pgr::args::required_species_list_arg().required(false))
+        .arg(
+            Arg::new("keep")
+                .long("keep")
+                .num_args(1)
+                .help("File with a list of species names to keep, in block order"),
+        )
+        .arg(
+            Arg::new("drop")
+                .long("drop")
+                .num_args(1)
+                .help("File with a list of species names to drop"),
+        )
+        .group(
+            ArgGroup::new("species")
+                .args(["required", "keep", "drop"])
+                .required(true)
+                .multiple(false),
+        )
         .arg(crate::cmd_pgr::args::infiles_arg("block FA"))
         .arg(
             Arg::new("strict")
@@ -47,6 +71,36 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         pgr::writer(outfile).with_context(|| format!("Failed to open writer for {}", outfile))?;
     let is_strict = args.get_flag("strict");
 
+    if args.contains_id("keep") || args.contains_id("drop") {
+        let (list, is_keep) = match args.get_one::<String>("keep") {
+            Some(keep) => (keep, true),
+            None => (args.get_one::<String>("drop").unwrap(), false),
+        };
+        let names = pgr::libs::io::read_names::<HashSet<String>>(list)?;
+
+        for infile in args.get_many::<String>("infiles").unwrap() {
+            let mut reader = pgr::reader(infile)
+                .with_context(|| format!("Failed to open reader for {}", infile))?;
+
+            for block_result in pgr::libs::fmt::fas::iter_fas_blocks(&mut reader) {
+                let mut block = block_result?;
+                if is_strict && is_keep && !names.iter().all(|n| block.names.contains(n)) {
+                    continue;
+                }
+
+                block.retain_names(|n| names.contains(n) == is_keep);
+                if block.entries.is_empty() {
+                    continue;
+                }
+
+                block.write(&mut writer)?;
+            }
+        }
+
+        writer.flush()?;
+        return Ok(());
+    }
+
     let needed =
         pgr::libs::io::read_names::<Vec<String>>(args.get_one::<String>("required").unwrap())?;
 
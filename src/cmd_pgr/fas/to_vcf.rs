@@ -1,5 +1,5 @@
 use anyhow::Context;
-use clap::{Arg, ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 use std::collections::BTreeMap;
 use std::io::Write;
 
@@ -9,16 +9,23 @@ use pgr::libs::fmt::vcf::write_vcf_header;
 /// Build the clap subcommand for to-vcf.
 pub fn make_subcommand() -> Command {
     Command::new("to-vcf")
-        .about("Outputs VCF file (substitutions only)")
+        .about("Outputs VCF file (substitutions and indels)")
         .after_help(
             r###"
-Outputs VCF file (substitutions only) from block FA files.
+Outputs VCF file (substitutions and indels) from block FA files.
 
 Notes:
 * Supports both plain text and gzipped (.gz) files
 * Reads from stdin if input file is 'stdin'
-* Outputs substitutions only; ID/QUAL/FILTER/INFO are '.'
+* ID/QUAL/FILTER/INFO are '.'
+* Each run of gap columns becomes one indel, anchored on the preceding base:
+    * Substitutions at the anchor base are part of the indel record
+    * Indels at the start of a block or on a minus-strand target can't be anchored;
+      they are skipped with a warning
+* `--no-indels` outputs substitutions only
 * CHROM/POS are derived from the target range; REF is the target base; ALT are non-REF bases
+* Every species is a sample column with a GT field: `0` for REF, `1..` for ALT alleles,
+  `.` for gaps and ambiguous bases
 * Use `--sizes` to emit `##contig=<ID=...,length=...>` headers
 
 Examples:
@@ -28,6 +35,9 @@ Examples:
 2. Output VCF with contig headers:
    pgr fas to-vcf --sizes tests/fas_vcf/S288c.chr.sizes tests/fas_vcf/YDL184C.fas
 
+3. Substitutions only:
+   pgr fas to-vcf --no-indels tests/fas/example.fas
+
 "###,
         )
         .arg(crate::cmd_pgr::args::infiles_arg("block FA"))
@@ -38,6 +48,12 @@ Examples:
                 .num_args(1)
                 .help("Chrom sizes file with lines: <chr> <length>"),
         )
+        .arg(
+            Arg::new("no_indels")
+                .long("no-indels")
+                .action(ArgAction::SetTrue)
+                .help("Only output substitutions"),
+        )
 }
 
 /// Execute the to-vcf command.
@@ -55,6 +71,8 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         BTreeMap::new()
     };
 
+    let with_indels = !args.get_flag("no_indels");
+
     let mut header_written = false;
     let mut header_names: Option<Vec<String>> = None;
 
@@ -82,7 +100,7 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
                 }
             }
 
-            pgr::libs::fmt::fas::write_vcf_block(&block, block_idx, &mut writer)?;
+            pgr::libs::fmt::fas::write_vcf_block(&block, block_idx, with_indels, &mut writer)?;
         }
     }
 
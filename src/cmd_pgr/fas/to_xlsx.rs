@@ -13,6 +13,9 @@ Notes:
 * Reads from stdin if input file is 'stdin'
 * `--min-freq` and `--max-freq` must be in [0, 1] and `--min-freq` <= `--max-freq`
 * `--outgroup` treats the last sequence of each block as the outgroup
+* Cells are colored by base and by the occurrence pattern of each variation; `--no-color` writes plain cells
+* A second worksheet, `summary`, lists each block with its mean conservation and the identity of
+  every other sequence to the first (reference) one
 
 Examples:
 1. Export variations to an Excel file:
@@ -27,6 +30,9 @@ Examples:
 4. Omit singleton and complex variations:
    pgr fas to-xlsx tests/fas/example.fas --no-single --no-complex
 
+5. Plain cells without colors:
+   pgr fas to-xlsx tests/fas/example.fas --no-color
+
 "###,
         )
         .arg(crate::cmd_pgr::args::infiles_arg("block FA"))
@@ -71,6 +77,12 @@ Examples:
                 .num_args(1)
                 .help("Maximal frequency"),
         )
+        .arg(
+            Arg::new("no_color")
+                .long("no-color")
+                .action(ArgAction::SetTrue)
+                .help("Do not color cells"),
+        )
         .arg(crate::cmd_pgr::args::outfile_arg_with_default(
             "variations.xlsx",
         ))
@@ -86,6 +98,7 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let is_nocomplex = args.get_flag("no_complex");
     let opt_min = args.get_one::<f64>("min_freq").cloned();
     let opt_max = args.get_one::<f64>("max_freq").cloned();
+    let is_nocolor = args.get_flag("no_color");
     if let Some(v) = opt_min {
         anyhow::ensure!(
             v.is_finite() && (0.0..=1.0).contains(&v),
@@ -119,5 +132,6 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         is_nocomplex,
         opt_min,
         opt_max,
+        is_nocolor,
     )
 }
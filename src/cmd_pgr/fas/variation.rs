@@ -1,5 +1,5 @@
 use anyhow::Context;
-use clap::{ArgMatches, Command};
+use clap::{value_parser, Arg, ArgMatches, Command};
 use std::io::Write;
 
 /// Build the clap subcommand for variation.
@@ -16,6 +16,9 @@ Notes:
 * `--outgroup` requires at least 2 sequences per block and polarizes substitutions against the last sequence
 * Filter out complex variations: `tsv-filter -H --ne freq:-1`
 * Filter out singletons: `tsv-filter -H --ne freq:1`
+* `--window` switches to a per-window summary along the reference (first) sequence;
+  columns: #target chr start end width variants density
+* `--step` defaults to the window size; the trailing window keeps its actual width
 
 Examples:
 1. List substitutions from block FA files:
@@ -27,10 +30,28 @@ Examples:
 3. Output results to a file:
    pgr fas variation tests/fas/example.fas -o output.tsv
 
+4. Variant counts in 100 bp windows sliding by 50 bp:
+   pgr fas variation tests/fas/example.fas --window 100 --step 50
+
 "###,
         )
         .arg(crate::cmd_pgr::args::infiles_arg("block FA"))
         .arg(crate::cmd_pgr::args::outgroup_arg())
+        .arg(
+            Arg::new("window")
+                .long("window")
+                .num_args(1)
+                .value_parser(value_parser!(usize))
+                .help("Summarize variants in windows of this size on the reference"),
+        )
+        .arg(
+            Arg::new("step")
+                .long("step")
+                .num_args(1)
+                .requires("window")
+                .value_parser(value_parser!(usize))
+                .help("Step between windows [default: window size]"),
+        )
         .arg(crate::cmd_pgr::args::outfile_arg())
 }
 
@@ -40,6 +61,34 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let mut writer =
         pgr::writer(outfile).with_context(|| format!("Failed to open writer for {}", outfile))?;
     let has_outgroup = args.get_flag("outgroup");
+    let window = args.get_one::<usize>("window").copied();
+    let step = args.get_one::<usize>("step").copied().or(window);
+
+    if let (Some(window), Some(step)) = (window, step) {
+        let field_names = [
+            "#target", "chr", "start", "end", "width", "variants", "density",
+        ];
+        writeln!(writer, "{}", field_names.join("\t"))?;
+
+        for infile in args.get_many::<String>("infiles").unwrap() {
+            let mut reader = pgr::reader(infile)
+                .with_context(|| format!("Failed to open reader for {}", infile))?;
+
+            for block_result in pgr::libs::fmt::fas::iter_fas_blocks(&mut reader) {
+                let block = block_result?;
+                pgr::libs::fmt::fas::write_variation_windows(
+                    &block,
+                    has_outgroup,
+                    window,
+                    step,
+                    &mut writer,
+                )?;
+            }
+        }
+
+        writer.flush()?;
+        return Ok(());
+    }
 
     let field_names = [
         "#target",
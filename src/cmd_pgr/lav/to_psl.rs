@@ -8,6 +8,11 @@ pub fn make_subcommand() -> Command {
             r###"
 Convert blastz lav to psl format.
 
+Notes:
+* One PSL record is written per alignment (`a`) stanza
+* Sequence names come from the `h` stanza following each `s` stanza
+* Out-of-range or overlapping gapped blocks are reported as errors
+
 Examples:
 1. Convert lav to psl:
    pgr lav to-psl in.lav -o out.psl
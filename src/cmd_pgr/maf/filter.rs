@@ -0,0 +1,87 @@
+use anyhow::Context;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use pgr::libs::fmt::maf::{next_maf_block, MafFilter, MafWriter};
+use std::io::Write;
+
+/// Build the clap subcommand for filter.
+pub fn make_subcommand() -> Command {
+    Command::new("filter")
+        .about("Filters MAF blocks by score and species presence")
+        .after_help(
+            r###"
+Keeps the MAF blocks that pass all given criteria and writes them as MAF.
+
+Notes:
+* The species is the part of the sequence name before the first '.'
+* Blocks without a `score=` field are treated as score 0
+* --require can be given multiple times; every named species must be present
+* Supports both plain text and gzipped (.gz) files
+* Reads from stdin if input file is 'stdin'
+
+Examples:
+1. Keep blocks scoring at least 10000:
+   pgr maf filter tests/maf/example.maf --min-score 10000
+
+2. Keep blocks with at least 3 species, one of them S288c:
+   pgr maf filter in.maf --min-species 3 --require S288c -o out.maf
+
+"###,
+        )
+        .arg(crate::cmd_pgr::args::infiles_arg("MAF"))
+        .arg(crate::cmd_pgr::args::min_score_arg_optional(
+            "Keep blocks scoring at least N",
+        ))
+        .arg(
+            Arg::new("min_species")
+                .long("min-species")
+                .num_args(1)
+                .default_value("0")
+                .value_parser(clap::value_parser!(usize))
+                .help("Keep blocks with at least N distinct species"),
+        )
+        .arg(
+            Arg::new("require")
+                .long("require")
+                .num_args(1)
+                .action(ArgAction::Append)
+                .help("Keep blocks containing this species, maybe repeated"),
+        )
+        .arg(crate::cmd_pgr::args::outfile_arg())
+}
+
+/// Execute the filter command.
+pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
+    let filter = MafFilter {
+        min_score: args.get_one::<f64>("min_score").copied(),
+        min_species: *args.get_one::<usize>("min_species").unwrap(),
+        require: args
+            .get_many::<String>("require")
+            .map(|vals| vals.cloned().collect())
+            .unwrap_or_default(),
+    };
+
+    let outfile = crate::cmd_pgr::args::get_outfile(args);
+    let writer =
+        pgr::writer(outfile).with_context(|| format!("Failed to open writer for {}", outfile))?;
+    let mut writer = MafWriter::new(writer);
+    writer.write_header("pgr")?;
+
+    for infile in args.get_many::<String>("infiles").unwrap() {
+        let mut reader =
+            pgr::reader(infile).with_context(|| format!("Failed to open reader for {}", infile))?;
+
+        loop {
+            let block = match next_maf_block(&mut reader) {
+                Ok(b) => b,
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(e.into()),
+            };
+            if filter.passes(&block) {
+                writer.write_ali(&block)?;
+            }
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
@@ -1,3 +1,5 @@
+pub mod filter;
+pub mod stat;
 pub mod to_fas;
 pub mod to_paf;
 
@@ -8,12 +10,16 @@ pub fn make_subcommand() -> Command {
         .about("Manipulates MAF alignment files")
         .subcommand_required(true)
         .arg_required_else_help(true)
+        .subcommand(filter::make_subcommand())
+        .subcommand(stat::make_subcommand())
         .subcommand(to_fas::make_subcommand())
         .subcommand(to_paf::make_subcommand())
 }
 /// Execute the maf command.
 pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     match args.subcommand() {
+        Some(("filter", sub_matches)) => filter::execute(sub_matches),
+        Some(("stat", sub_matches)) => stat::execute(sub_matches),
         Some(("to-fas", sub_matches)) => to_fas::execute(sub_matches),
         Some(("to-paf", sub_matches)) => to_paf::execute(sub_matches),
         _ => Ok(()),
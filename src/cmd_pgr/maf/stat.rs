@@ -0,0 +1,64 @@
+use anyhow::Context;
+use clap::{ArgMatches, Command};
+use pgr::libs::fmt::maf::{next_maf_block, MafStats};
+use std::io::Write;
+
+/// Build the clap subcommand for stat.
+pub fn make_subcommand() -> Command {
+    Command::new("stat")
+        .about("Reports summary statistics of MAF files")
+        .after_help(
+            r###"
+Reports a summary (TSV: key<TAB>value) over all blocks of the input MAF files.
+
+Keys:
+* blocks: number of alignment blocks
+* species: number of distinct species
+* columns: total alignment columns
+* rows:<species>: number of `s` rows of each species
+
+Notes:
+* The species is the part of the sequence name before the first '.'
+* A malformed block stops processing and is reported with its 1-based block index
+* Supports both plain text and gzipped (.gz) files
+* Reads from stdin if input file is 'stdin'
+
+Examples:
+1. Summarize a MAF file:
+   pgr maf stat tests/maf/example.maf
+
+"###,
+        )
+        .arg(crate::cmd_pgr::args::infiles_arg("MAF"))
+        .arg(crate::cmd_pgr::args::outfile_arg())
+}
+
+/// Execute the stat command.
+pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
+    let outfile = crate::cmd_pgr::args::get_outfile(args);
+    let mut writer =
+        pgr::writer(outfile).with_context(|| format!("Failed to open writer for {}", outfile))?;
+
+    let mut stats = MafStats::default();
+    for infile in args.get_many::<String>("infiles").unwrap() {
+        let mut reader =
+            pgr::reader(infile).with_context(|| format!("Failed to open reader for {}", infile))?;
+
+        let mut index = 0;
+        loop {
+            index += 1;
+            let block = match next_maf_block(&mut reader) {
+                Ok(b) => b,
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+                Err(e) => anyhow::bail!("{}: malformed block {}: {}", infile, index, e),
+            };
+            stats
+                .add_block(&block)
+                .map_err(|e| anyhow::anyhow!("{}: malformed block {}: {}", infile, index, e))?;
+        }
+    }
+
+    stats.write_tsv(&mut writer)?;
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use clap::{ArgMatches, Command};
+use std::io::Write;
+
+/// Build the clap subcommand for format.
+pub fn make_subcommand() -> Command {
+    Command::new("format")
+        .about("Reformats a PHYLIP distance matrix")
+        .after_help(
+            r###"
+This command cleans up a PHYLIP distance matrix, e.g. one emitted by an external tool,
+and writes it back as a full tab-separated matrix.
+
+* Input: a PHYLIP distance matrix, full or lower-triangular
+* Steps are applied in this order: --symmetrize, --normalize, --precision
+
+Notes:
+* --symmetrize:
+    * avg: average each pair of entries
+    * lower: mirror the lower triangle onto the upper
+    * upper: mirror the upper triangle onto the lower
+* --normalize:
+    * none: leave entries unchanged (default)
+    * max: divide every entry by the largest one
+    * minmax: map the smallest entry to 0 and the largest to 1
+* Without --precision, values are written in their shortest exact form
+
+Examples:
+1. Convert a lower-triangular matrix to a full one:
+   pgr mat format input.phy
+
+2. Average an asymmetric matrix and round to 3 decimals:
+   pgr mat format input.phy --symmetrize avg --precision 3
+
+3. Scale distances into [0, 1]:
+   pgr mat format input.phy --normalize minmax
+
+"###,
+        )
+        .arg(crate::cmd_pgr::args::infile_arg().help("Input PHYLIP distance matrix"))
+        .arg(
+            clap::Arg::new("precision")
+                .long("precision")
+                .num_args(1)
+                .value_parser(clap::value_parser!(usize))
+                .help("Number of decimal places"),
+        )
+        .arg(
+            clap::Arg::new("normalize")
+                .long("normalize")
+                .action(clap::ArgAction::Set)
+                .value_parser([
+                    clap::builder::PossibleValue::new("none"),
+                    clap::builder::PossibleValue::new("max"),
+                    clap::builder::PossibleValue::new("minmax"),
+                ])
+                .default_value("none")
+                .help("Scale entries"),
+        )
+        .arg(
+            clap::Arg::new("symmetrize")
+                .long("symmetrize")
+                .action(clap::ArgAction::Set)
+                .value_parser([
+                    clap::builder::PossibleValue::new("avg"),
+                    clap::builder::PossibleValue::new("lower"),
+                    clap::builder::PossibleValue::new("upper"),
+                ])
+                .help("Enforce symmetry"),
+        )
+        .arg(crate::cmd_pgr::args::outfile_arg())
+}
+
+/// Execute the format command.
+pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
+    let infile = crate::cmd_pgr::args::get_infile(args);
+    let opt_precision = args.get_one::<usize>("precision").copied();
+    let opt_normalize = args.get_one::<String>("normalize").unwrap();
+
+    let (names, mut matrix) = pgr::libs::fmt::phylip::read_matrix(infile)?;
+
+    if let Some(mode) = args.get_one::<String>("symmetrize") {
+        pgr::libs::fmt::phylip::symmetrize(&mut matrix, mode)?;
+    }
+    pgr::libs::fmt::phylip::normalize(&mut matrix, opt_normalize)?;
+
+    let mut writer = pgr::writer(crate::cmd_pgr::args::get_outfile(args))?;
+    pgr::libs::fmt::phylip::write_matrix(&mut writer, &names, &matrix, opt_precision)?;
+
+    writer.flush()?;
+    Ok(())
+}
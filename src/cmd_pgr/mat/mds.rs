@@ -0,0 +1,73 @@
+use clap::{ArgMatches, Command};
+use std::io::Write;
+
+/// Build the clap subcommand for mds.
+pub fn make_subcommand() -> Command {
+    Command::new("mds")
+        .about("Classical multidimensional scaling (PCoA) of a distance matrix")
+        .after_help(
+            r###"
+This command embeds the taxa of a PHYLIP distance matrix into k dimensions.
+
+* Input: a PHYLIP distance matrix, full or lower-triangular
+* Output: TSV with a `name dim1 .. dimk` header, then one row per taxon
+
+Notes:
+* The squared distance matrix is double-centered and eigen-decomposed
+* Negative eigenvalues mean the distances are not Euclidean; they are reported
+  on stderr and clamped to 0, so the matching coordinates are 0
+* Axes are oriented so the largest-magnitude coordinate is positive
+
+Examples:
+1. Two-dimensional embedding:
+   pgr mat mds tests/mat/line.phy
+
+2. Three dimensions, written to a file:
+   pgr mat mds input.phy -k 3 -o coords.tsv
+
+"###,
+        )
+        .arg(crate::cmd_pgr::args::infile_arg().help("Input PHYLIP distance matrix"))
+        .arg(
+            clap::Arg::new("k")
+                .long("k")
+                .short('k')
+                .num_args(1)
+                .default_value("2")
+                .value_parser(clap::value_parser!(usize))
+                .help("Number of dimensions to output"),
+        )
+        .arg(crate::cmd_pgr::args::outfile_arg())
+}
+
+/// Execute the mds command.
+pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
+    let infile = crate::cmd_pgr::args::get_infile(args);
+    let opt_k = *args.get_one::<usize>("k").unwrap();
+    anyhow::ensure!(opt_k > 0, "--k must be positive: {}", opt_k);
+
+    let (names, matrix) = pgr::libs::fmt::phylip::read_matrix(infile)?;
+    let (coords, eigvals) = pgr::libs::linalg::mds(&matrix, opt_k);
+
+    let negatives: Vec<f64> = eigvals.iter().copied().filter(|v| *v < -1e-9).collect();
+    if !negatives.is_empty() {
+        log::warn!(
+            "{} negative eigenvalue(s) clamped to 0, most negative {:.4}",
+            negatives.len(),
+            negatives.iter().copied().fold(0.0f64, f64::min)
+        );
+    }
+
+    let mut writer = pgr::writer(crate::cmd_pgr::args::get_outfile(args))?;
+    let header: Vec<String> = (1..=coords.first().map_or(0, |r| r.len()))
+        .map(|i| format!("dim{}", i))
+        .collect();
+    writer.write_fmt(format_args!("name\t{}\n", header.join("\t")))?;
+    for (name, row) in names.iter().zip(&coords) {
+        let values: Vec<String> = row.iter().map(|v| format!("{:.4}", v)).collect();
+        writer.write_fmt(format_args!("{}\t{}\n", name, values.join("\t")))?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
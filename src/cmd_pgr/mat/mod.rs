@@ -0,0 +1,28 @@
+// Subcommand modules for the `mat` command.
+pub mod format;
+pub mod mds;
+pub mod subset;
+pub mod to_pair;
+
+use clap::{ArgMatches, Command};
+/// Build the clap subcommand for mat.
+pub fn make_subcommand() -> Command {
+    Command::new("mat")
+        .about("Operates on distance matrices")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .subcommand(format::make_subcommand())
+        .subcommand(mds::make_subcommand())
+        .subcommand(subset::make_subcommand())
+        .subcommand(to_pair::make_subcommand())
+}
+/// Execute the mat command.
+pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
+    match args.subcommand() {
+        Some(("format", sub_matches)) => format::execute(sub_matches),
+        Some(("mds", sub_matches)) => mds::execute(sub_matches),
+        Some(("subset", sub_matches)) => subset::execute(sub_matches),
+        Some(("to-pair", sub_matches)) => to_pair::execute(sub_matches),
+        _ => Ok(()),
+    }
+}
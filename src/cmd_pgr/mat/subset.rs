@@ -0,0 +1,72 @@
+use clap::{ArgMatches, Command};
+use std::io::Write;
+
+/// Build the clap subcommand for subset.
+pub fn make_subcommand() -> Command {
+    Command::new("subset")
+        .about("Extracts a submatrix of listed taxa")
+        .after_help(
+            r###"
+This command extracts the rows and columns of the taxa named in a list file.
+
+* Input: a PHYLIP distance matrix, full or lower-triangular
+* --list: one taxon name per line
+* Output: a full tab-separated PHYLIP matrix
+
+Notes:
+* By default taxa are emitted in the order of the list
+* --keep-order emits them in the order of the original matrix instead
+* A listed name absent from the matrix is an error, unless --lenient
+* Duplicated names in the list are emitted once
+
+Examples:
+1. Extract and reorder taxa:
+   pgr mat subset input.phy --list taxa.lst
+
+2. Keep the original order and ignore unknown names:
+   pgr mat subset input.phy --list taxa.lst --keep-order --lenient
+
+"###,
+        )
+        .arg(crate::cmd_pgr::args::infile_arg().help("Input PHYLIP distance matrix"))
+        .arg(
+            clap::Arg::new("list")
+                .long("list")
+                .num_args(1)
+                .required(true)
+                .help("File of taxon names, one per line"),
+        )
+        .arg(
+            clap::Arg::new("keep_order")
+                .long("keep-order")
+                .action(clap::ArgAction::SetTrue)
+                .help("Keep the original matrix order instead of the list order"),
+        )
+        .arg(
+            clap::Arg::new("lenient")
+                .long("lenient")
+                .action(clap::ArgAction::SetTrue)
+                .help("Skip listed names that are absent from the matrix"),
+        )
+        .arg(crate::cmd_pgr::args::outfile_arg())
+}
+
+/// Execute the subset command.
+pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
+    let infile = crate::cmd_pgr::args::get_infile(args);
+    let opt_list = args.get_one::<String>("list").unwrap();
+    let is_keep_order = args.get_flag("keep_order");
+    let is_lenient = args.get_flag("lenient");
+
+    let (names, matrix) = pgr::libs::fmt::phylip::read_matrix(infile)?;
+    let wanted: Vec<String> = pgr::libs::io::read_names(opt_list)?;
+
+    let (sub_names, sub_matrix) =
+        pgr::libs::fmt::phylip::subset(&names, &matrix, &wanted, is_keep_order, is_lenient)?;
+
+    let mut writer = pgr::writer(crate::cmd_pgr::args::get_outfile(args))?;
+    pgr::libs::fmt::phylip::write_matrix(&mut writer, &sub_names, &sub_matrix, None)?;
+
+    writer.flush()?;
+    Ok(())
+}
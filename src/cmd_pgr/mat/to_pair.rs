@@ -0,0 +1,79 @@
+use clap::{ArgMatches, Command};
+use std::io::Write;
+
+/// Build the clap subcommand for to-pair.
+pub fn make_subcommand() -> Command {
+    Command::new("to-pair")
+        .about("Flattens a distance matrix to pairs")
+        .after_help(
+            r###"
+This command writes each pair of taxa in a PHYLIP distance matrix as one line.
+
+* Input: a PHYLIP distance matrix, full or lower-triangular
+* Output: `name1<TAB>name2<TAB>value`, one line per unordered pair
+
+Notes:
+* Pairs follow the matrix order, and each pair is written once, from the upper triangle
+* The diagonal is skipped unless --include-self
+* --max-dist keeps pairs with value <= the threshold
+* --min-sim keeps pairs with value >= the threshold, for similarity matrices
+* The output is a weighted edge list for building similarity graphs
+
+Examples:
+1. All off-diagonal pairs:
+   pgr mat to-pair input.phy
+
+2. Pairs within a distance of 0.05:
+   pgr mat to-pair input.phy --max-dist 0.05
+
+3. Pairs with a similarity of at least 0.95, self pairs included:
+   pgr mat to-pair sim.phy --min-sim 0.95 --include-self
+
+"###,
+        )
+        .arg(crate::cmd_pgr::args::infile_arg().help("Input PHYLIP distance matrix"))
+        .arg(
+            clap::Arg::new("max_dist")
+                .long("max-dist")
+                .num_args(1)
+                .value_parser(clap::value_parser!(f64))
+                .conflicts_with("min_sim")
+                .help("Only output pairs with a value <= this"),
+        )
+        .arg(
+            clap::Arg::new("min_sim")
+                .long("min-sim")
+                .num_args(1)
+                .value_parser(clap::value_parser!(f64))
+                .help("Only output pairs with a value >= this"),
+        )
+        .arg(
+            clap::Arg::new("include_self")
+                .long("include-self")
+                .action(clap::ArgAction::SetTrue)
+                .help("Also output the diagonal"),
+        )
+        .arg(crate::cmd_pgr::args::outfile_arg())
+}
+
+/// Execute the to-pair command.
+pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
+    let infile = crate::cmd_pgr::args::get_infile(args);
+    let opt_max_dist = args.get_one::<f64>("max_dist").copied();
+    let opt_min_sim = args.get_one::<f64>("min_sim").copied();
+    let is_include_self = args.get_flag("include_self");
+
+    let (names, matrix) = pgr::libs::fmt::phylip::read_matrix(infile)?;
+
+    let keep =
+        |v: f64| opt_max_dist.is_none_or(|max| v <= max) && opt_min_sim.is_none_or(|min| v >= min);
+    let pairs = pgr::libs::fmt::phylip::to_pairs(&matrix, keep, is_include_self);
+
+    let mut writer = pgr::writer(crate::cmd_pgr::args::get_outfile(args))?;
+    for (i, j, v) in pairs {
+        writer.write_fmt(format_args!("{}\t{}\t{}\n", names[i], names[j], v))?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
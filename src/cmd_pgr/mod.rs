@@ -3,6 +3,7 @@
 pub mod args;
 pub mod axt;
 pub mod chain;
+pub mod clust;
 pub mod dist;
 pub mod fa;
 pub mod fas;
@@ -10,6 +11,7 @@ pub mod fq;
 pub mod gff;
 pub mod lav;
 pub mod maf;
+pub mod mat;
 pub mod ms;
 pub mod net;
 pub mod paf;
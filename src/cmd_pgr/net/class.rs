@@ -1,5 +1,5 @@
 use anyhow::Context;
-use clap::{ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 use std::collections::HashMap;
 use std::io::Write;
 
@@ -8,9 +8,34 @@ use pgr::libs::chain::net::{collect_stats_gap, read_nets, Stats};
 pub fn make_subcommand() -> Command {
     Command::new("class")
         .about("Shows stats of net")
+        .after_help(
+            r###"
+This command shows the count and bases of each fill class, and of the gaps.
+
+Notes:
+* --summary also writes a tab-separated tally of the fill classes to stderr:
+    class  count  bases  ali
+    * top, syn, inv and nonSyn are always listed, followed by other classes
+    * ali is the sum of the fills' aligned bases
+
+Examples:
+1. Show net statistics:
+   pgr net class in.net
+
+2. Keep the tally as TSV:
+   pgr net class in.net --summary 2> tally.tsv
+
+"###,
+        )
         .arg(crate::cmd_pgr::args::infile_arg_required_with_help(
             "Input net file (or stdin if 'stdin')",
         ))
+        .arg(
+            Arg::new("summary")
+                .long("summary")
+                .action(ArgAction::SetTrue)
+                .help("Write a TSV tally of fill classes to stderr"),
+        )
         .arg(crate::cmd_pgr::args::outfile_arg())
 }
 /// Execute the class command.
@@ -35,6 +60,10 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     // UCSC usually sorts by bases or hierarchy.
     // Let's sort by bases desc.
 
+    if args.get_flag("summary") {
+        write_summary(&stats_map);
+    }
+
     let mut results: Vec<(String, u64, u64)> = stats_map
         .into_iter()
         .map(|(k, v)| (k, v.count, v.bases))
@@ -78,3 +107,24 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     writer.flush()?;
     Ok(())
 }
+
+// Write the fill-class tally to stderr, the main synteny classes first.
+fn write_summary(stats_map: &HashMap<String, Stats>) {
+    let main = ["top", "syn", "inv", "nonSyn"];
+    let mut others: Vec<&String> = stats_map
+        .keys()
+        .filter(|k| k.as_str() != "gap" && !main.contains(&k.as_str()))
+        .collect();
+    others.sort();
+
+    eprintln!("class\tcount\tbases\tali");
+    for class in main
+        .into_iter()
+        .chain(others.into_iter().map(|k| k.as_str()))
+    {
+        let (count, bases, ali) = stats_map
+            .get(class)
+            .map_or((0, 0, 0), |s| (s.count, s.bases, s.ali));
+        eprintln!("{}\t{}\t{}\t{}", class, count, bases, ali);
+    }
+}
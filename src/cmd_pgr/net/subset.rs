@@ -1,6 +1,6 @@
 use anyhow::Context;
 use clap::{Arg, ArgAction, ArgMatches, Command};
-use pgr::libs::chain::net::{read_nets, subset_nets, SubsetOptions};
+use pgr::libs::chain::net::{clip_chrom, read_nets, subset_nets, SubsetOptions};
 use pgr::libs::chain::{read_chains, Chain};
 use std::collections::HashMap;
 use std::io::Write;
@@ -8,6 +8,25 @@ use std::io::Write;
 pub fn make_subcommand() -> Command {
     Command::new("subset")
         .about("Creates chain file with subset of chains that appear in the net")
+        .after_help(
+            r###"
+This command writes the parts of chains that appear as fills in the net.
+
+Notes:
+* --chrom restricts the output to one target chromosome
+    * --start/--end (1-based, inclusive) further restrict it to a window
+    * Fills crossing the window edges are clipped, and the chains are cut
+      at the clipped target coordinates
+
+Examples:
+1. Chains behind the net:
+   pgr net subset in.net in.chain out.chain
+
+2. Chains of chr1:10001-20000:
+   pgr net subset in.net in.chain out.chain --chrom chr1 --start 10001 --end 20000
+
+"###,
+        )
         .arg(crate::cmd_pgr::args::in_net_arg().index(1))
         .arg(crate::cmd_pgr::args::in_chain_arg().index(2))
         .arg(
@@ -32,6 +51,28 @@ pub fn make_subcommand() -> Command {
             ArgAction::Set,
             "Restrict output to particular type in net file",
         ))
+        .arg(
+            Arg::new("chrom")
+                .long("chrom")
+                .num_args(1)
+                .help("Restrict output to this target chromosome"),
+        )
+        .arg(
+            Arg::new("start")
+                .long("start")
+                .num_args(1)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .requires("chrom")
+                .help("Window start on --chrom (1-based)"),
+        )
+        .arg(
+            Arg::new("end")
+                .long("end")
+                .num_args(1)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .requires("chrom")
+                .help("Window end on --chrom (inclusive)"),
+        )
 }
 /// Execute the subset command.
 pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
@@ -54,7 +95,20 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     // Read nets
     let net_reader =
         pgr::reader(net_in).with_context(|| format!("Failed to open reader for {}", net_in))?;
-    let chroms = read_nets(net_reader)?;
+    let mut chroms = read_nets(net_reader)?;
+
+    if let Some(name) = args.get_one::<String>("chrom") {
+        chroms.retain(|c| &c.name == name);
+        if chroms.is_empty() {
+            log::warn!("{} not found in {}", name, net_in);
+        }
+        let start = args.get_one::<u64>("start").map_or(0, |s| s - 1);
+        for chrom in &chroms {
+            let end = args.get_one::<u64>("end").copied().unwrap_or(chrom.size);
+            anyhow::ensure!(start < end, "--start must not be greater than --end");
+            clip_chrom(chrom, start, end);
+        }
+    }
 
     let mut writer = pgr::writer(chain_out)
         .with_context(|| format!("Failed to open writer for {}", chain_out))?;
@@ -1,6 +1,6 @@
 use anyhow::Context;
 use clap::{Arg, ArgMatches, Command};
-use pgr::libs::chain::net::{net_to_axt, read_nets};
+use pgr::libs::chain::net::{net_to_axt, read_nets, referenced_sequences};
 use pgr::libs::chain::sub_matrix::SubMatrix;
 use pgr::libs::chain::{Chain, ChainReader};
 use pgr::libs::fmt::twobit::TwoBitFile;
@@ -10,18 +10,65 @@ use std::io::Write;
 pub fn make_subcommand() -> Command {
     Command::new("to-axt")
         .about("Converts net (and chain) to axt")
+        .after_help(
+            r###"
+Converts a net file and its source chains to AXT. The alignment rows are filled
+with sequences fetched from the target and query 2bit files; query fills on the
+minus strand are reverse-complemented.
+
+Notes:
+* The 2bit files can be given positionally or with `--t-2bit` / `--q-2bit`
+* Every target and query sequence referenced by the net must be present in the 2bit files
+
+Examples:
+1. Positional 2bit files:
+   pgr net to-axt in.net in.chain target.2bit query.2bit -o out.axt
+
+2. Named 2bit files:
+   pgr net to-axt in.net in.chain --t-2bit target.2bit --q-2bit query.2bit -o out.axt
+
+"###,
+        )
         .arg(crate::cmd_pgr::args::in_net_arg())
         .arg(crate::cmd_pgr::args::in_chain_arg())
-        .arg(Arg::new("target").required(true).help("Target 2bit file"))
-        .arg(Arg::new("query").required(true).help("Query 2bit file"))
+        .arg(
+            Arg::new("target")
+                .required_unless_present("t_2bit")
+                .conflicts_with("t_2bit")
+                .help("Target 2bit file"),
+        )
+        .arg(
+            Arg::new("query")
+                .required_unless_present("q_2bit")
+                .conflicts_with("q_2bit")
+                .help("Query 2bit file"),
+        )
+        .arg(
+            Arg::new("t_2bit")
+                .long("t-2bit")
+                .num_args(1)
+                .help("Target 2bit file (alternative to the positional argument)"),
+        )
+        .arg(
+            Arg::new("q_2bit")
+                .long("q-2bit")
+                .num_args(1)
+                .help("Query 2bit file (alternative to the positional argument)"),
+        )
         .arg(crate::cmd_pgr::args::outfile_arg_required())
 }
 /// Execute the to-axt command.
 pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let in_net = args.get_one::<String>("in_net").unwrap();
     let in_chain = args.get_one::<String>("in_chain").unwrap();
-    let target = args.get_one::<String>("target").unwrap();
-    let query = args.get_one::<String>("query").unwrap();
+    let target = args
+        .get_one::<String>("t_2bit")
+        .or_else(|| args.get_one::<String>("target"))
+        .unwrap();
+    let query = args
+        .get_one::<String>("q_2bit")
+        .or_else(|| args.get_one::<String>("query"))
+        .unwrap();
     let out_axt = crate::cmd_pgr::args::get_outfile(args);
 
     let mut t_2bit =
@@ -42,6 +89,25 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         pgr::reader(in_net).with_context(|| format!("Failed to open reader for {}", in_net))?;
     let nets = read_nets(reader)?;
 
+    // Fail early, before any output, if a referenced sequence is missing
+    let (t_names, q_names) = referenced_sequences(&nets, &chains);
+    for name in &t_names {
+        anyhow::ensure!(
+            t_2bit.has_sequence(name),
+            "Target sequence {} not found in {}",
+            name,
+            target
+        );
+    }
+    for name in &q_names {
+        anyhow::ensure!(
+            q_2bit.has_sequence(name),
+            "Query sequence {} not found in {}",
+            name,
+            query
+        );
+    }
+
     let matrix = SubMatrix::hoxd55();
 
     let mut writer =
@@ -33,6 +33,12 @@ Notes:
 * <infiles> can be plain or gzipped (.fas.gz) block fasta files.
 * Input cannot be stdin.
 * Output is written to a directory (default: `PL-p2m`), not stdout.
+* --dry-run prints the steps and the files they would create without running
+  anything. The target name is only known after the first step and is shown
+  as <target_name>.
+* --resume skips each step whose output files exist and are newer than its
+  inputs, so an interrupted run continues where it stopped. --force reruns
+  every step.
 
 "###,
         )
@@ -41,121 +47,71 @@ Notes:
             2..,
         ))
         .arg(crate::cmd_pgr::args::outdir_arg_with_default("PL-p2m"))
+        .arg(crate::cmd_pgr::args::dry_run_arg())
+        .arg(crate::cmd_pgr::args::resume_arg())
+        .arg(crate::cmd_pgr::args::force_arg())
 }
 
 /// Execute the p2m command.
 pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let outdir = args.get_one::<String>("outdir").unwrap();
-    fs::create_dir_all(outdir)?;
-
-    let curdir = env::current_dir()?;
     let pgr = pgr::libs::io::current_exe_string()?;
 
-    run_cmd!(echo "==> Paths")?;
-    run_cmd!(echo "    \"pgr\"      = ${pgr}")?;
-    run_cmd!(echo "    \"curdir\" = ${curdir:?}")?;
-    run_cmd!(echo "    \"outdir\" = ${outdir}")?;
-
-    // basename => [abs_path, .json, .slice.fas]
-    let mut info_of: BTreeMap<String, Vec<String>> = BTreeMap::new();
-
-    // Operating
-    run_cmd!(echo "==> Basenames and absolute paths")?;
+    // basename => abs_path
+    let mut info_of: BTreeMap<String, String> = BTreeMap::new();
     for infile in args.get_many::<String>("infiles").unwrap() {
         let basename = intspan::basename(infile)?;
         let absolute = intspan::absolute_path(infile)?.display().to_string();
 
-        info_of.insert(basename.to_string(), vec![absolute.to_string()]);
+        info_of.insert(basename.to_string(), absolute);
     }
 
-    run_cmd!(echo "==> Switch to outdir")?;
-    let _cwd_guard = CwdGuard::enter(outdir)?;
-
-    run_cmd!(echo "==> pgr fas name - first")?;
-    let target_name = first_target_name(&pgr, &info_of)?;
-    run_cmd!(echo "    \"target_name\" = ${target_name}")?;
-
-    run_cmd!(echo "==> pgr fas cover")?;
-    for (basename, info) in info_of.iter_mut() {
-        let infile = info
-            .first()
-            .ok_or_else(|| anyhow::anyhow!("no info for basename {}", basename))?;
-        let outfile = format!("{}.json", basename);
-        run_cmd!(${pgr} fas cover ${infile} --trim 10 --name ${target_name} -o ${outfile})?;
-
-        info.push(outfile.to_string());
+    if args.get_flag("dry_run") {
+        let mut writer = pgr::writer("stdout")?;
+        plan(&pgr, outdir, &info_of).write(&mut writer)?;
+        return Ok(());
     }
 
-    run_cmd!(echo "==> spanr compare")?;
-    {
-        let infiles: Vec<String> = info_of
-            .iter()
-            .map(|e| {
-                e.1.get(1)
-                    .ok_or_else(|| anyhow::anyhow!("missing cover output for {}", e.0))
-                    .map(|s| s.to_string())
-            })
-            .collect::<Result<_, _>>()?;
-        let files = infiles.clone();
-        run_cmd!(
-            spanr compare --op intersect $[files] |
-                spanr span stdin --op excise -n 10 -o intersect.json
-        )?;
-        let files = infiles.clone();
-        run_cmd!(
-            spanr merge $[files] intersect.json -o merge.json
-        )?;
-    }
+    fs::create_dir_all(outdir)?;
+    let curdir = env::current_dir()?;
 
-    run_cmd!(echo "==> pgr fas slice")?;
-    for (basename, info) in info_of.iter_mut() {
-        let infile = info
-            .first()
-            .ok_or_else(|| anyhow::anyhow!("no info for basename {}", basename))?;
-        let outfile = format!("{}.slice.fas", basename);
-        run_cmd!(${pgr} fas slice ${infile} --runlist intersect.json --name ${target_name} -o ${outfile})?;
+    // Paths of the steps are absolute, as the steps run inside outdir
+    let plan = plan(&pgr, &pgr::libs::pl::abs_path(outdir)?, &info_of);
+    let is_resume = crate::cmd_pgr::args::get_resume(args);
 
-        info.push(outfile.to_string());
-    }
+    run_cmd!(echo "==> Paths")?;
+    run_cmd!(echo "    \"pgr\"      = ${pgr}")?;
+    run_cmd!(echo "    \"curdir\" = ${curdir:?}")?;
+    run_cmd!(echo "    \"outdir\" = ${outdir}")?;
 
-    run_cmd!(echo "==> pgr fas join")?;
-    {
-        let infiles: Vec<String> = info_of
-            .iter()
-            .map(|e| {
-                e.1.get(2)
-                    .ok_or_else(|| anyhow::anyhow!("missing slice output for {}", e.0))
-                    .map(|s| s.to_string())
-            })
-            .collect::<Result<_, _>>()?;
-        run_cmd!(
-            ${pgr} fas join $[infiles] --name ${target_name} -o join.raw.fas
-        )?;
-    }
+    // Operating
+    run_cmd!(echo "==> Basenames and absolute paths")?;
 
-    run_cmd!(echo "==> pgr fas name && pgr fas subset")?;
-    {
-        run_cmd!(
-            ${pgr} fas name join.raw.fas -o name.lst
-        )?;
-        run_cmd!(
-            ${pgr} fas subset join.raw.fas --required name.lst -o join.subset.fas
-        )?;
+    run_cmd!(echo "==> Switch to outdir")?;
+    let _cwd_guard = CwdGuard::enter(outdir)?;
+
+    // Filled in by the first step
+    let mut target_name = String::new();
+    for (i, step) in plan.steps.iter().enumerate() {
+        let name = &step.name;
+        run_cmd!(echo "==> ${name}")?;
+        if is_resume && step.is_up_to_date() {
+            run_cmd!(echo "    Up to date, skipped")?;
+        } else {
+            step.run(&[("target_name", &target_name)])?;
+        }
+
+        if i == 0 {
+            target_name = read_target_name()?;
+            run_cmd!(echo "    \"target_name\" = ${target_name}")?;
+        }
     }
 
     Ok(())
 }
 
-// Run `pgr fas name` on the first input file and return the first name line.
-fn first_target_name(pgr: &str, info_of: &BTreeMap<String, Vec<String>>) -> anyhow::Result<String> {
-    let infile = info_of
-        .values()
-        .next()
-        .and_then(|v| v.first())
-        .ok_or_else(|| anyhow::anyhow!("no cover input found"))?;
-    run_cmd!(
-        ${pgr} fas name ${infile} -o name.first.lst
-    )?;
+// The first name line written by the first step.
+fn read_target_name() -> anyhow::Result<String> {
     let first_content = fs::read_to_string("name.first.lst")
         .map_err(|e| anyhow::anyhow!("failed to read name.first.lst: {}", e))?;
     first_content
@@ -164,3 +120,114 @@ fn first_target_name(pgr: &str, info_of: &BTreeMap<String, Vec<String>>) -> anyh
         .ok_or_else(|| anyhow::anyhow!("name.first.lst is empty"))
         .map(str::to_string)
 }
+
+// Steps of the p2m pipeline, run by `execute` and printed by --dry-run.
+// Files are created inside `outdir`; the commands run there and use the
+// placeholder <target_name>, which the first step determines.
+fn plan(pgr: &str, outdir: &str, info_of: &BTreeMap<String, String>) -> pgr::libs::pl::Plan {
+    use pgr::libs::pl::shell_quote;
+
+    let pgr = shell_quote(pgr);
+    let in_outdir = |file: &str| format!("{}/{}", outdir, file);
+    let abs_files: Vec<&str> = info_of.values().map(|f| f.as_str()).collect();
+    let basenames: Vec<&String> = info_of.keys().collect();
+    let json_files: Vec<String> = basenames.iter().map(|b| format!("{}.json", b)).collect();
+    let slice_files: Vec<String> = basenames
+        .iter()
+        .map(|b| format!("{}.slice.fas", b))
+        .collect();
+    let join_quoted = |files: &[String]| {
+        files
+            .iter()
+            .map(|f| shell_quote(f))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let mut plan = pgr::libs::pl::Plan::new();
+    let first_file = abs_files.first().copied().unwrap_or_default();
+    plan.step_with_inputs(
+        "pgr fas name - first",
+        vec![format!(
+            "{} fas name {} -o name.first.lst",
+            pgr,
+            shell_quote(first_file)
+        )],
+        vec![first_file.to_string()],
+        vec![in_outdir("name.first.lst")],
+    );
+    plan.step_with_inputs(
+        "pgr fas cover",
+        abs_files
+            .iter()
+            .zip(&json_files)
+            .map(|(infile, outfile)| {
+                format!(
+                    "{} fas cover {} --trim 10 --name <target_name> -o {}",
+                    pgr,
+                    shell_quote(infile),
+                    shell_quote(outfile)
+                )
+            })
+            .collect(),
+        abs_files.iter().map(|f| f.to_string()).collect(),
+        json_files.iter().map(|f| in_outdir(f)).collect(),
+    );
+    plan.step_with_inputs(
+        "spanr compare",
+        vec![
+            format!(
+                "spanr compare --op intersect {} | spanr span stdin --op excise -n 10 -o intersect.json",
+                join_quoted(&json_files)
+            ),
+            format!(
+                "spanr merge {} intersect.json -o merge.json",
+                join_quoted(&json_files)
+            ),
+        ],
+        json_files.iter().map(|f| in_outdir(f)).collect(),
+        vec![in_outdir("intersect.json"), in_outdir("merge.json")],
+    );
+    let mut slice_inputs: Vec<String> = abs_files.iter().map(|f| f.to_string()).collect();
+    slice_inputs.push(in_outdir("intersect.json"));
+    plan.step_with_inputs(
+        "pgr fas slice",
+        abs_files
+            .iter()
+            .zip(&slice_files)
+            .map(|(infile, outfile)| {
+                format!(
+                    "{} fas slice {} --runlist intersect.json --name <target_name> -o {}",
+                    pgr,
+                    shell_quote(infile),
+                    shell_quote(outfile)
+                )
+            })
+            .collect(),
+        slice_inputs,
+        slice_files.iter().map(|f| in_outdir(f)).collect(),
+    );
+    plan.step_with_inputs(
+        "pgr fas join",
+        vec![format!(
+            "{} fas join {} --name <target_name> -o join.raw.fas",
+            pgr,
+            join_quoted(&slice_files)
+        )],
+        slice_files.iter().map(|f| in_outdir(f)).collect(),
+        vec![in_outdir("join.raw.fas")],
+    );
+    plan.step_with_inputs(
+        "pgr fas name && pgr fas subset",
+        vec![
+            format!("{} fas name join.raw.fas -o name.lst", pgr),
+            format!(
+                "{} fas subset join.raw.fas --required name.lst -o join.subset.fas",
+                pgr
+            ),
+        ],
+        vec![in_outdir("join.raw.fas")],
+        vec![in_outdir("name.lst"), in_outdir("join.subset.fas")],
+    );
+    plan
+}
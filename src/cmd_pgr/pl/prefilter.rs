@@ -1,9 +1,9 @@
 use anyhow::Context;
-use clap::{Arg, ArgMatches, Command};
-use cmd_lib::run_cmd;
+use clap::{Arg, ArgAction, ArgMatches, Command};
 use rayon::prelude::*;
 use std::io::{Read, Write};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Build the clap subcommand for prefilter.
 pub fn make_subcommand() -> Command {
@@ -21,11 +21,15 @@ Process:
 4. Compares with reference protein sequences
 
 Parameters:
-* --chunk N: Process N bytes at a time (memory control)
+* --chunk-size N: Process N bytes at a time (memory control)
 * --min-len N: Minimum peptide length to consider (filters short ORFs)
 * --kmer/-k N: K-mer size for minimizers
 * --window/-w N: Window size for minimizers
 * --parallel/-p N: Number of threads
+* --progress: Print the number of finished chunks to stderr
+* --dry-run: Print the steps and the files they would create, then exit
+* --resume: Skip the run when the output file is newer than the input and
+  reference files; --force always runs
 
 Notes:
 * Input file must be FASTA or BGZF-compressed FASTA (.gz)
@@ -33,13 +37,15 @@ Notes:
 * Automatic index creation (.loc) if missing
 * Cannot read from stdin (requires random access)
 * Output format matches `pgr dist seq`
+* Chunks are processed in parallel, but their results are written in input
+  order, so the output does not depend on --parallel
 
 Examples:
 1. Basic usage:
    pgr pl prefilter assembly.fa refs.pep.fa
 
 2. Specify chunk size and minimum peptide length:
-   pgr pl prefilter assembly.fa refs.pep.fa --chunk 50000 --min-len 20
+   pgr pl prefilter assembly.fa refs.pep.fa --chunk-size 50000 --min-len 20
 
 3. Use custom k-mer and window sizes:
    pgr pl prefilter assembly.fa refs.pep.fa -k 7 -w 2 --parallel 8
@@ -66,7 +72,16 @@ Examples:
         .arg(crate::cmd_pgr::args::kmer_arg_with_default("7"))
         .arg(crate::cmd_pgr::args::window_arg())
         .arg(crate::cmd_pgr::args::parallel_arg())
+        .arg(
+            Arg::new("progress")
+                .long("progress")
+                .action(ArgAction::SetTrue)
+                .help("Print the number of finished chunks to stderr"),
+        )
         .arg(crate::cmd_pgr::args::outfile_arg())
+        .arg(crate::cmd_pgr::args::dry_run_arg())
+        .arg(crate::cmd_pgr::args::resume_arg())
+        .arg(crate::cmd_pgr::args::force_arg())
 }
 
 /// Execute the prefilter command.
@@ -78,6 +93,40 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let opt_len = *args.get_one::<usize>("min_len").unwrap();
     let opt_kmer = *args.get_one::<usize>("kmer").unwrap();
     let opt_window = *args.get_one::<usize>("window").unwrap();
+    let is_progress = args.get_flag("progress");
+    let outfile = crate::cmd_pgr::args::get_outfile(args);
+
+    let loc_file = format!("{}.loc", infile);
+    let has_loc = std::path::Path::new(&loc_file).is_file();
+
+    // Run on each chunk, and printed by --dry-run
+    let chunk_cmd = {
+        let pgr = pgr::libs::io::current_exe_string()?;
+        let pgr = pgr::libs::pl::shell_quote(&pgr);
+        format!(
+            "{} fa six-frame <chunk> --min-len {} | {} dist seq stdin {} -k {} -w {} > <chunk>.tsv",
+            pgr,
+            opt_len,
+            pgr,
+            pgr::libs::pl::shell_quote(match_file),
+            opt_kmer,
+            opt_window
+        )
+    };
+
+    if args.get_flag("dry_run") {
+        let mut writer = pgr::writer("stdout")?;
+        plan(infile, has_loc, &chunk_cmd, outfile).write(&mut writer)?;
+        return Ok(());
+    }
+
+    if crate::cmd_pgr::args::get_resume(args)
+        && outfile != "stdout"
+        && pgr::libs::pl::is_up_to_date(&[infile, match_file], &[outfile])
+    {
+        log::info!("==> {} is up to date, skipped", outfile);
+        return Ok(());
+    }
 
     // Set the number of threads for rayon
     let opt_parallel = *args.get_one::<usize>("parallel").unwrap();
@@ -88,15 +137,15 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let is_bgzf = pgr::is_bgzf(infile);
 
     // Open files
-    let loc_file = format!("{}.loc", infile);
-    if !std::path::Path::new(&loc_file).is_file() {
+    if !has_loc {
         pgr::libs::loc::create_loc(infile, &loc_file, is_bgzf)?;
     }
 
     // Split .loc file into chunks
     let chunks = pgr::libs::loc::split_loc_file(&loc_file, opt_chunk)?;
 
-    let pgr = pgr::libs::io::current_exe_string()?;
+    let n_chunks = chunks.len();
+    let finished = AtomicUsize::new(0);
 
     // Each parallel task writes its sub-process stdout to a private temp file to
     // avoid interleaved output across rayon workers. Temp files are kept alive
@@ -122,19 +171,10 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
             let temp_input_persist_path = temp_input.into_temp_path().keep()?;
             let temp_input_keep = temp_input_persist_path.clone();
 
-            let temp_output = tempfile::Builder::new().tempfile_in(std::env::temp_dir())?;
-            let temp_output_path = temp_output
-                .path()
-                .to_str()
-                .ok_or_else(|| anyhow::anyhow!("temp file path is not valid UTF-8"))?
-                .to_string();
-            // Persist output temp file too; we read & delete it in the serial phase.
-            let temp_output_persist_path = temp_output.into_temp_path().keep()?;
-
-            run_cmd!(
-                ${pgr} fa six-frame ${temp_input_path} --min-len ${opt_len} |
-                    ${pgr} dist seq stdin ${match_file} -k ${opt_kmer} -w ${opt_window} > ${temp_output_path}
-            )?;
+            // The command writes <chunk>.tsv next to the input; we read & delete
+            // it in the serial phase.
+            pgr::libs::pl::run_command(&chunk_cmd, &[("chunk", &temp_input_path)])?;
+            let temp_output_path = PathBuf::from(format!("{}.tsv", temp_input_path));
 
             // Best-effort cleanup of the input temp file; failure is non-fatal.
             if let Err(e) = std::fs::remove_file(&temp_input_keep) {
@@ -145,12 +185,16 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
                 );
             }
 
-            Ok(temp_output_persist_path)
+            if is_progress {
+                let n = finished.fetch_add(1, Ordering::Relaxed) + 1;
+                eprintln!("==> Finished {}/{} chunks", n, n_chunks);
+            }
+
+            Ok(temp_output_path)
         })
         .collect();
 
     // Serial phase: stream each chunk's output in order to the writer.
-    let outfile = crate::cmd_pgr::args::get_outfile(args);
     let mut writer =
         pgr::writer(outfile).with_context(|| format!("Failed to open writer for {}", outfile))?;
     for result in results {
@@ -169,3 +213,32 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+// Steps of the prefilter pipeline for --dry-run. `<chunk>` stands for each
+// chunk of the input; indexing and concatenation run in-process.
+fn plan(infile: &str, has_loc: bool, chunk_cmd: &str, outfile: &str) -> pgr::libs::pl::Plan {
+    let mut plan = pgr::libs::pl::Plan::new();
+    if !has_loc {
+        plan.step(
+            "Index",
+            vec![format!("(internal) index {}", infile)],
+            vec![format!("{}.loc", infile)],
+        );
+    }
+    plan.step(
+        "Process chunks",
+        vec![chunk_cmd.to_string()],
+        vec!["<tempdir>/<chunk>.tsv".to_string()],
+    );
+    let outputs = if outfile == "stdout" {
+        vec![]
+    } else {
+        vec![outfile.to_string()]
+    };
+    plan.step(
+        "Outputs",
+        vec![format!("cat <chunk>.tsv > {}", outfile)],
+        outputs,
+    );
+    plan
+}
@@ -1,5 +1,4 @@
 use clap::{ArgMatches, Command};
-use cmd_lib::run_cmd;
 
 /// Build the clap subcommand for rept.
 pub fn make_subcommand() -> Command {
@@ -13,6 +12,11 @@ This command identifies repetitive regions in a genome using k-mer analysis.
 
 * All operations are running in a tempdir and no intermediate files are retained.
 
+* --dry-run prints the steps and the files they would create without running anything.
+
+* --resume skips the whole run when the output file exists and is newer than the inputs.
+  --force always runs.
+
 * External dependencies
     * FastK / Profex / Fastrm
     * spanr
@@ -30,6 +34,9 @@ This command identifies repetitive regions in a genome using k-mer analysis.
         ))
         .arg(crate::cmd_pgr::args::fill_fragment_arg())
         .arg(crate::cmd_pgr::args::outfile_arg())
+        .arg(crate::cmd_pgr::args::dry_run_arg())
+        .arg(crate::cmd_pgr::args::resume_arg())
+        .arg(crate::cmd_pgr::args::force_arg())
 }
 
 /// Execute the rept command.
@@ -41,14 +48,9 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let opt_min = *args.get_one::<usize>("min_len").unwrap();
     let opt_ff = *args.get_one::<usize>("fill_fragment").unwrap();
 
-    let ctx = pgr::libs::pl::PipelineCtx::new("pgr_rept_")?;
-
-    run_cmd!(info "==> Absolute paths")?;
-    let abs_infile = ctx.abs_path(args.get_one::<String>("infile").unwrap())?;
+    let abs_infile = pgr::libs::pl::abs_path(args.get_one::<String>("infile").unwrap())?;
     let abs_outfile = pgr::libs::pl::abs_path_or_stdout(outfile)?;
 
-    let _cwd_guard = ctx.enter()?;
-
     let re_prof: regex::Regex = regex::Regex::new(
         r"(?xi)
             (?<start>\d+)       # start
@@ -60,7 +62,7 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     )?;
 
     let opts = pgr::libs::pl::RepeatOpts {
-        pgr: ctx.pgr.clone(),
+        pgr: pgr::libs::io::current_exe_string()?,
         abs_infile,
         abs_outfile,
         opt_kmer,
@@ -72,6 +74,23 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         min_depth: Some(2),
     };
 
+    if args.get_flag("dry_run") {
+        let mut writer = pgr::writer("stdout")?;
+        pgr::libs::pl::repeat_plan(&opts).write(&mut writer)?;
+        return Ok(());
+    }
+
+    if crate::cmd_pgr::args::get_resume(args)
+        && opts.abs_outfile != "stdout"
+        && pgr::libs::pl::is_up_to_date(&[&opts.abs_infile], &[&opts.abs_outfile])
+    {
+        log::info!("==> {} is up to date, skipped", opts.abs_outfile);
+        return Ok(());
+    }
+
+    let ctx = pgr::libs::pl::PipelineCtx::new("pgr_rept_")?;
+    let _cwd_guard = ctx.enter()?;
+
     pgr::libs::pl::run_repeat_pipeline(&opts)?;
 
     // Done
@@ -15,6 +15,11 @@ This command identifies tandem repeats in a genome via `trf`.
 
 * All operations are running in a tempdir and no intermediate files are retained.
 
+* --dry-run prints the steps and the files they would create without running anything.
+
+* --resume skips the whole run when the output file exists and is newer than the input.
+  --force always runs.
+
 * External dependencies
     * trf
     * spanr
@@ -74,6 +79,9 @@ This command identifies tandem repeats in a genome via `trf`.
                 .help("Maximum period size to report"),
         )
         .arg(crate::cmd_pgr::args::outfile_arg())
+        .arg(crate::cmd_pgr::args::dry_run_arg())
+        .arg(crate::cmd_pgr::args::resume_arg())
+        .arg(crate::cmd_pgr::args::force_arg())
 }
 
 /// Execute the trf command.
@@ -98,31 +106,62 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let opt_minscore_u = opt_minscore as usize;
     let opt_max_period = *args.get_one::<usize>("max_period").unwrap();
 
-    let ctx = pgr::libs::pl::PipelineCtx::new("pgr_trf_")?;
-    let pgr = ctx.pgr.clone();
-
-    run_cmd!(info "==> Absolute paths")?;
-    let abs_infile = ctx.abs_path(args.get_one::<String>("infile").unwrap())?;
+    let abs_infile = pgr::libs::pl::abs_path(args.get_one::<String>("infile").unwrap())?;
     let abs_outfile = pgr::libs::pl::abs_path_or_stdout(outfile)?;
 
+    let pgr = pgr::libs::io::current_exe_string()?;
+    let trf_args = format!(
+        "{} {} {} {} {} {} {}",
+        opt_trf_match, opt_trf_mismatch, opt_delta, opt_pm, opt_pi, opt_minscore_u, opt_max_period
+    );
+    let plan = plan(&pgr, &abs_infile, &abs_outfile, &trf_args);
+
+    if args.get_flag("dry_run") {
+        let mut writer = pgr::writer("stdout")?;
+        plan.write(&mut writer)?;
+        return Ok(());
+    }
+
+    if crate::cmd_pgr::args::get_resume(args)
+        && abs_outfile != "stdout"
+        && pgr::libs::pl::is_up_to_date(&[&abs_infile], &[&abs_outfile])
+    {
+        log::info!("==> {} is up to date, skipped", abs_outfile);
+        return Ok(());
+    }
+
+    let ctx = pgr::libs::pl::PipelineCtx::new("pgr_trf_")?;
     let _cwd_guard = ctx.enter()?;
 
-    run_cmd!(info "==> Split by names")?;
-    run_cmd!(
-        ${pgr} fa split name ${abs_infile} -o .
-    )?;
+    for step in &plan.steps {
+        let name = &step.name;
+        run_cmd!(info "==> ${name}")?;
+        if step.name == PER_CHR_STEP {
+            process_chrs(step)?;
+        } else {
+            step.run(&[])?;
+        }
+    }
 
-    run_cmd!(info "==> Process each chromosome")?;
-    run_cmd!(
-        ${pgr} fa size ${abs_infile} -o chr.sizes
-    )?;
+    // Done
+
+    Ok(())
+}
+
+// Name of the step that runs trf on each chromosome.
+const PER_CHR_STEP: &str = "Process each chromosome";
+
+// Write chr.sizes, then run trf on each chromosome and turn its .dat into a
+// .rg file.
+fn process_chrs(step: &pgr::libs::pl::PlanStep) -> anyhow::Result<()> {
+    let [sizes_cmd, trf_cmd] = step.commands.as_slice() else {
+        anyhow::bail!("unexpected commands of step {}", step.name);
+    };
+    pgr::libs::pl::run_command(sizes_cmd, &[])?;
     let chrs = pgr::libs::io::read_names::<Vec<String>>("chr.sizes")?;
 
-    let mut rg_files = vec![];
     for (i, chr) in chrs.iter().enumerate() {
-        run_cmd!(
-            trf ${chr}.fa ${opt_trf_match} ${opt_trf_mismatch} ${opt_delta} ${opt_pm} ${opt_pi} ${opt_minscore_u} ${opt_max_period} -d -h -ngs > trf.${i}.dat
-        )?;
+        pgr::libs::pl::run_command(trf_cmd, &[("chr", chr), ("i", &i.to_string())])?;
 
         // 198 229 12 2.7 12 90 0 50 34 46 3 15 1.62 CATTACCACCAC CATTAGCACCACCATTACCACCACCATCACCA ATAGCGCACAGACAGATAAAAATTACAGAGTACACAACATCCATGAAACG TTACCACAGGTAACGGTGCGGGCTGACGCGTACAGGAAACACAGAAAAAA
         // start end
@@ -146,15 +185,49 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
             .with_context(|| format!("Failed to open writer for {}", rg_file))?;
         pgr::libs::pl::parse_trf_output(reader, chr, &mut writer)?;
         writer.flush()?;
-        rg_files.push(rg_file);
     }
 
-    run_cmd!(info "==> Outputs")?;
-    run_cmd!(
-        spanr cover $[rg_files] -o ${abs_outfile}
-    )?;
-
-    // Done
-
     Ok(())
 }
+
+// Steps of the trf pipeline, run by `execute` and printed by --dry-run.
+// Per-chromosome commands and files use the placeholders <chr> and <i>
+// (0-based index).
+fn plan(pgr: &str, abs_infile: &str, abs_outfile: &str, trf_args: &str) -> pgr::libs::pl::Plan {
+    use pgr::libs::pl::shell_quote;
+
+    let pgr = shell_quote(pgr);
+    let infile = shell_quote(abs_infile);
+    let mut plan = pgr::libs::pl::Plan::new();
+    plan.step(
+        "Split by names",
+        vec![format!("{} fa split name {} -o .", pgr, infile)],
+        vec!["<tempdir>/<chr>.fa".to_string()],
+    );
+    plan.step(
+        PER_CHR_STEP,
+        vec![
+            format!("{} fa size {} -o chr.sizes", pgr, infile),
+            format!("trf <chr>.fa {} -d -h -ngs > trf.<i>.dat", trf_args),
+        ],
+        vec![
+            "<tempdir>/chr.sizes".to_string(),
+            "<tempdir>/trf.<i>.dat".to_string(),
+            "<tempdir>/trf.<i>.rg".to_string(),
+        ],
+    );
+    let outputs = if abs_outfile == "stdout" {
+        vec![]
+    } else {
+        vec![abs_outfile.to_string()]
+    };
+    plan.step(
+        "Outputs",
+        vec![format!(
+            "spanr cover trf.*.rg -o {}",
+            shell_quote(abs_outfile)
+        )],
+        outputs,
+    );
+    plan
+}
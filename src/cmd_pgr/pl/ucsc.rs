@@ -12,6 +12,10 @@ This command implements the UCSC pipeline for pairwise genome alignments, psl-ch
 * <target> and <query> are fasta files
 * <psl> can be a .psl file or a directory containing multiple .psl files
 * Default names of target and query in the output .maf are derived from the basename of <target> and <query>
+* --dry-run prints the steps and the files they would create without running anything.
+  Per-chromosome files use the placeholder <chr>
+* --resume skips the whole run when <outdir>/.ucsc.done, written once all .maf files are
+  complete, is newer than <target>, <query> and the .psl files. --force always runs.
 
 * `--gap-model` and `--min-score`:
     * Human18vsChimp2 use `loose` and 1000
@@ -70,26 +74,21 @@ References:
         .arg(crate::cmd_pgr::args::q_name_arg(None))
         .arg(crate::cmd_pgr::args::syn_arg("Generate syntenic alignments"))
         .arg(crate::cmd_pgr::args::outdir_arg())
+        .arg(crate::cmd_pgr::args::dry_run_arg())
+        .arg(crate::cmd_pgr::args::resume_arg())
+        .arg(crate::cmd_pgr::args::force_arg())
 }
 
 /// Execute the ucsc command.
 pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let outdir = args.get_one::<String>("outdir").unwrap();
-    if outdir != "stdout" {
-        std::fs::create_dir_all(outdir)?;
-    }
-
     let opt_gap_model = args.get_one::<String>("gap_model").unwrap();
     let opt_minscore = *args.get_one::<f64>("min_score").unwrap();
 
     let is_syn = args.get_flag("syn");
 
-    let ctx = pgr::libs::pl::PipelineCtx::new("pgr_pipeline_")?;
-    let pgr = ctx.pgr.clone();
-
-    run_cmd!(info "==> Absolute paths")?;
-    let abs_target = ctx.abs_path(args.get_one::<String>("target").unwrap())?;
-    let abs_query = ctx.abs_path(args.get_one::<String>("query").unwrap())?;
+    let abs_target = pgr::libs::pl::abs_path(args.get_one::<String>("target").unwrap())?;
+    let abs_query = pgr::libs::pl::abs_path(args.get_one::<String>("query").unwrap())?;
 
     let opt_tname = if let Some(tname) = args.get_one::<String>("t_name") {
         if tname.is_empty() {
@@ -110,29 +109,183 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         format!("{}.", pgr::libs::io::basename_or_err(&abs_query)?)
     };
 
-    let abs_psl = ctx.abs_path(args.get_one::<String>("psl").unwrap())?;
+    let abs_psl = pgr::libs::pl::abs_path(args.get_one::<String>("psl").unwrap())?;
     let infiles = if std::path::Path::new(&abs_psl).is_dir() {
         pgr::libs::io::list_files_ext(&abs_psl, "psl")
     } else {
         vec![abs_psl]
     };
+    if infiles.is_empty() {
+        anyhow::bail!(
+            "no .psl files found in {}",
+            args.get_one::<String>("psl").unwrap()
+        );
+    }
 
-    let abs_outdir = pgr::libs::pl::abs_path_or_stdout(outdir)?;
+    let prefix_args = if opt_tname.is_empty() {
+        "".to_string()
+    } else {
+        format!(
+            "{} {}",
+            pgr::libs::pl::shell_quote(&format!("-tPrefix={}", opt_tname)),
+            pgr::libs::pl::shell_quote(&format!("-qPrefix={}", opt_qname))
+        )
+    };
 
+    let mut opts = PlanOpts {
+        pgr: pgr::libs::io::current_exe_string()?,
+        abs_target: abs_target.clone(),
+        abs_query: abs_query.clone(),
+        infiles: infiles.clone(),
+        chain_args: format!("-minScore={} -linearGap={}", opt_minscore, opt_gap_model),
+        prefix_args,
+        outdir: outdir.to_string(),
+        is_syn,
+    };
+
+    if args.get_flag("dry_run") {
+        let mut writer = pgr::writer("stdout")?;
+        plan(&opts)?.write(&mut writer)?;
+        return Ok(());
+    }
+
+    // The completion marker of the last step tells a finished run from one
+    // that stopped halfway through writing the .maf files
+    if crate::cmd_pgr::args::get_resume(args) && outdir != "stdout" {
+        let is_done = plan(&opts)?
+            .steps
+            .last()
+            .is_some_and(|step| step.name == DONE_STEP && step.is_up_to_date());
+        if is_done {
+            log::info!("==> {} is up to date, skipped", outdir);
+            return Ok(());
+        }
+    }
+
+    if outdir != "stdout" {
+        std::fs::create_dir_all(outdir)?;
+    }
+    // The steps run inside the tempdir
+    opts.outdir = pgr::libs::pl::abs_path_or_stdout(outdir)?;
+    let plan = plan(&opts)?;
+    let marker = format!("{}/{}", opts.outdir, DONE_MARKER);
+    if std::path::Path::new(&marker).exists() {
+        std::fs::remove_file(&marker)?;
+    }
+
+    let ctx = pgr::libs::pl::PipelineCtx::new("pgr_pipeline_")?;
     let _cwd_guard = ctx.enter()?;
 
-    run_cmd!(info "==> Target .sizes and .2bit")?;
-    run_cmd!(
-        ${pgr} fa size ${abs_target} -o target.chr.sizes;
-        faToTwoBit ${abs_target} target.chr.2bit;
-    )?;
-    run_cmd!(info "==> Query .sizes and .2bit")?;
-    run_cmd!(
-        ${pgr} fa size ${abs_query} -o query.chr.sizes;
-        faToTwoBit ${abs_query} query.chr.2bit;
-    )?;
-
-    run_cmd!(info "==> axtChain")?;
+    for step in &plan.steps {
+        let name = &step.name;
+        run_cmd!(info "==> ${name}")?;
+        for cmd in &step.commands {
+            if !cmd.contains("<chr>") {
+                pgr::libs::pl::run_command(cmd, &[])?;
+                continue;
+            }
+
+            // Listed when reached, as the files are written by earlier commands
+            let (dir, ext) = chr_files_of(&step.name)?;
+            let mut files = pgr::libs::io::list_files_ext(dir, ext);
+            files.sort();
+            for file in files {
+                let chr = chr_of(&file)?;
+                pgr::libs::pl::run_command(cmd, &[("chr", &chr)])?;
+            }
+        }
+    }
+
+    // Done
+
+    Ok(())
+}
+
+// Written into outdir by the last step, once every .maf file is complete.
+const DONE_MARKER: &str = ".ucsc.done";
+const DONE_STEP: &str = "Mark as done";
+
+// chainMergeSort opens all its input files at once and could reach the
+// system's maxfile limit, so the chains are merged this many at a time.
+const CHAIN_BATCH_SIZE: usize = 100;
+
+// Steps with per-chromosome commands, and the directory and extension of the
+// files whose names fill their <chr> placeholder.
+const PER_CHR_FILES: [(&str, &str, &str); 3] = [
+    ("netToAxt", "net", "net"),
+    ("axtToMaf", "axtNet", "axt"),
+    ("synNet.maf", "synNet", "net"),
+];
+
+fn chr_files_of(step: &str) -> anyhow::Result<(&'static str, &'static str)> {
+    PER_CHR_FILES
+        .iter()
+        .find(|(name, _, _)| *name == step)
+        .map(|&(_, dir, ext)| (dir, ext))
+        .ok_or_else(|| anyhow::anyhow!("no per-chromosome files for step {}", step))
+}
+
+// The chromosome of a per-chromosome file: its name without the extension.
+// Unlike `basename_or_err`, it keeps dots inside names such as NC_000913.3.
+fn chr_of(file: &str) -> anyhow::Result<String> {
+    std::path::Path::new(file)
+        .file_stem()
+        .and_then(std::ffi::OsStr::to_str)
+        .map(str::to_string)
+        .ok_or_else(|| anyhow::anyhow!("failed to get the chromosome of: {}", file))
+}
+
+// Inputs of the ucsc pipeline plan.
+struct PlanOpts {
+    pgr: String,
+    abs_target: String,
+    abs_query: String,
+    infiles: Vec<String>,
+    chain_args: String,
+    prefix_args: String,
+    outdir: String,
+    is_syn: bool,
+}
+
+// Steps of the ucsc pipeline, run by `execute` and printed by --dry-run.
+// Commands run inside the tempdir; per-chromosome ones use the placeholder
+// <chr>, see PER_CHR_FILES.
+fn plan(opts: &PlanOpts) -> anyhow::Result<pgr::libs::pl::Plan> {
+    use pgr::libs::pl::shell_quote;
+
+    let pgr = shell_quote(&opts.pgr);
+    let target = shell_quote(&opts.abs_target);
+    let query = shell_quote(&opts.abs_query);
+    let tmp = |file: &str| format!("<tempdir>/{}", file);
+    let maf_output = if opts.outdir == "stdout" {
+        "stdout".to_string()
+    } else {
+        format!("{}/<chr>.maf", shell_quote(&opts.outdir))
+    };
+    let maf_outputs = if opts.outdir == "stdout" {
+        vec![]
+    } else {
+        vec![format!("{}/<chr>.maf", opts.outdir)]
+    };
+
+    let mut plan = pgr::libs::pl::Plan::new();
+    plan.step(
+        "Target .sizes and .2bit",
+        vec![
+            format!("{} fa size {} -o target.chr.sizes", pgr, target),
+            format!("faToTwoBit {} target.chr.2bit", target),
+        ],
+        vec![tmp("target.chr.sizes"), tmp("target.chr.2bit")],
+    );
+    plan.step(
+        "Query .sizes and .2bit",
+        vec![
+            format!("{} fa size {} -o query.chr.sizes", pgr, query),
+            format!("faToTwoBit {} query.chr.2bit", query),
+        ],
+        vec![tmp("query.chr.sizes"), tmp("query.chr.2bit")],
+    );
+
     // axtChain - Chain together axt alignments.
     // usage:
     //   axtChain -linearGap=loose in.axt tNibDir qNibDir out.chain
@@ -146,224 +299,159 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     // options:
     //    -minScore=N - minimum score (after repeat stuff) to pass
     //    -noCheckScore=N - score that will pass without checks (speed tweak)
-    std::fs::create_dir_all("pslChain")?;
-    for infile in infiles {
-        let stem = pgr::libs::io::basename_or_err(&infile)?;
-        run_cmd!(
-            axtChain -minScore=${opt_minscore} -linearGap=${opt_gap_model} -psl ${infile} target.chr.2bit query.chr.2bit pslChain/${stem}.tmp
-        )?;
-        run_cmd!(
-            chainAntiRepeat target.chr.2bit query.chr.2bit pslChain/${stem}.tmp pslChain/${stem}.chain
-        )?;
-    }
-
-    run_cmd!(info "==> chainMergeSort and chainPreNet")?;
-    {
-        // This step would open all .chain files and reach system's maxfile limit.
-        // So merge CHAIN_BATCH_SIZE files a time.
-        //
-        // chainMergeSort - Combine sorted files into larger sorted file
-        // usage:
-        //    chainMergeSort file(s)
-        // Output goes to standard output
-        // options:
-        //    -saveId - keep the existing chain ids.
-        //    -inputList=somefile - somefile contains list of input chain files.
-        //    -tempDir=somedir/ - somedir has space for temporary sorting data, default ./
-        const CHAIN_BATCH_SIZE: usize = 100;
-        let mut files = pgr::libs::io::list_files_ext("pslChain", "chain");
-        let mut sn = 1;
-        let mut merge_files = vec![];
-        while !files.is_empty() {
-            let batching: Vec<_> = files.drain(0..CHAIN_BATCH_SIZE.min(files.len())).collect();
-
-            let mut tmp = tempfile::NamedTempFile::new()?;
-            {
-                use std::io::Write;
-                for s in &batching {
-                    writeln!(tmp, "{}", s)?;
-                }
-                tmp.flush()?;
-            }
-            let tmp_path = tmp
-                .path()
-                .to_str()
-                .ok_or_else(|| anyhow::anyhow!("temp file path is not valid UTF-8"))?
-                .to_string();
-            run_cmd!(
-                chainMergeSort -inputList=${tmp_path} > all.${sn}.chain.tmp
-            )?;
-            merge_files.push(format!("all.{}.chain.tmp", sn));
-
-            sn += 1;
-        }
-
-        let cleanup_files: Vec<String> = merge_files.clone();
-        run_cmd!(
-            chainMergeSort $[merge_files] > all.chain
-        )?;
-
-        // Clean up intermediate batch tmp files (best-effort)
-        for f in &cleanup_files {
-            if let Err(e) = std::fs::remove_file(f) {
-                log::warn!("failed to remove temp file {}: {}", f, e);
-            }
-        }
-
-        // chainPreNet - Remove chains that don't have a chance of being netted
-        // usage:
-        //   chainPreNet in.chain target.sizes query.sizes out.chain
-        run_cmd!(
-            chainPreNet all.chain target.chr.sizes query.chr.sizes all.pre.chain
-        )?;
+    let mut commands = vec!["mkdir -p pslChain".to_string()];
+    let mut outputs = vec![];
+    let mut chain_files = vec![];
+    for infile in &opts.infiles {
+        let stem = shell_quote(&pgr::libs::io::basename_or_err(infile)?).into_owned();
+        commands.push(format!(
+            "axtChain {} -psl {} target.chr.2bit query.chr.2bit pslChain/{}.tmp",
+            opts.chain_args,
+            shell_quote(infile),
+            stem
+        ));
+        commands.push(format!(
+            "chainAntiRepeat target.chr.2bit query.chr.2bit pslChain/{}.tmp pslChain/{}.chain",
+            stem, stem
+        ));
+        outputs.push(tmp(&format!("pslChain/{}.tmp", stem)));
+        outputs.push(tmp(&format!("pslChain/{}.chain", stem)));
+        chain_files.push(format!("pslChain/{}.chain", stem));
     }
+    plan.step("axtChain", commands, outputs);
 
-    run_cmd!(info "==> chain-net")?;
-    {
-        // chainNet - Make alignment nets out of chains
-        // usage:
-        //   chainNet in.chain target.sizes query.sizes target.net query.net
-        //
-        // netSyntenic - Add synteny info to net.
-        // usage:
-        //   netSyntenic in.net out.net
-        run_cmd!(
-            chainNet -minSpace=1 all.pre.chain target.chr.sizes query.chr.sizes stdout query.chainnet |
-                netSyntenic stdin noClass.net
-        )?;
-
-        // netChainSubset - Create chain file with subset of chains that appear in
-        // the net
-        // usage:
-        //    netChainSubset in.net in.chain out.chain
-        // options:
-        //    -gapOut=gap.tab - Output gap sizes to file
-        //    -type=XXX - Restrict output to particular type in net file
-        //    -splitOnInsert - Split chain when get an insertion of another chain
-        //    -wholeChains - Write entire chain references by net, don't split
-        //     when a high-level net is encoundered.  This is useful when nets
-        //     have been filtered.
-        //    -skipMissing - skip chains that are not found instead of generating
-        //     an error.  Useful if chains have been filtered.
-        //
-        // chainStitchId - Join chain fragments with the same chain ID into a single
-        //    chain per ID.  Chain fragments must be from same original chain but
-        //    must not overlap.  Chain fragment scores are summed.
-        // usage:
-        //    chainStitchId in.chain out.chain
-        run_cmd!(
-            netChainSubset -verbose=0 noClass.net all.chain stdout |
-                chainStitchId stdin over.chain
-        )?;
-
-        // netSplit - Split a genome net file into chromosome net files
-        // usage:
-        //   netSplit in.net outDir
-        std::fs::create_dir_all("net")?;
-        run_cmd!(
-            netSplit noClass.net net > /dev/null
-        )?;
-    }
-
-    run_cmd!(info "==> netToAxt")?;
-    {
-        std::fs::create_dir_all("axtNet")?;
-
-        let files = pgr::libs::io::list_files_ext("net", "net");
-
-        // netToAxt - Convert net (and chain) to axt.
-        // usage:
-        //   netToAxt in.net in.chain target.2bit query.2bit out.axt
-        // note:
-        // directories full of .nib files (an older format)
-        // may also be used in place of target.2bit and query.2bit.
-        //
-        // axtSort - Sort axt files
-        // usage:
-        //   axtSort in.axt out.axt
-        for file in files {
-            let stem = pgr::libs::io::basename_or_err(&file)?;
-            run_cmd!(
-                netToAxt ${file} all.pre.chain target.chr.2bit query.chr.2bit stdout |
-                    axtSort stdin axtNet/${stem}.axt
-            )?;
-        }
+    // chainMergeSort - Combine sorted files into larger sorted file
+    // usage:
+    //    chainMergeSort file(s)
+    // Output goes to standard output
+    //
+    // chainPreNet - Remove chains that don't have a chance of being netted
+    // usage:
+    //   chainPreNet in.chain target.sizes query.sizes out.chain
+    let mut commands = vec![];
+    let mut merge_files = vec![];
+    for (i, batch) in chain_files.chunks(CHAIN_BATCH_SIZE).enumerate() {
+        let merge_file = format!("all.{}.chain.tmp", i + 1);
+        commands.push(format!(
+            "chainMergeSort {} > {}",
+            batch.join(" "),
+            merge_file
+        ));
+        merge_files.push(merge_file);
     }
-
-    run_cmd!(info "==> axt-maf")?;
-    if !is_syn {
-        run_cmd!(info "==> axtToMaf")?;
-
-        let files = pgr::libs::io::list_files_ext("axtNet", "axt");
-        for file in files {
-            let stem = pgr::libs::io::basename_or_err(&file)?;
-            let maf_output = if abs_outdir == "stdout" {
-                "stdout".to_string()
-            } else {
-                format!("{}/{}.maf", abs_outdir, stem)
-            };
-            let prefix_args = if opt_tname.is_empty() {
-                "".to_string()
-            } else {
-                format!("-tPrefix={} -qPrefix={}", opt_tname, opt_qname)
-            };
-            run_cmd!(
-                axtToMaf ${prefix_args} ${file} target.chr.sizes query.chr.sizes ${maf_output}
-            )?;
-        }
+    commands.push(format!(
+        "chainMergeSort {} > all.chain",
+        merge_files.join(" ")
+    ));
+    commands.push(format!("rm {}", merge_files.join(" ")));
+    commands
+        .push("chainPreNet all.chain target.chr.sizes query.chr.sizes all.pre.chain".to_string());
+    plan.step(
+        "chainMergeSort and chainPreNet",
+        commands,
+        vec![tmp("all.chain"), tmp("all.pre.chain")],
+    );
+
+    // chainNet - Make alignment nets out of chains
+    // usage:
+    //   chainNet in.chain target.sizes query.sizes target.net query.net
+    //
+    // netSyntenic - Add synteny info to net.
+    // usage:
+    //   netSyntenic in.net out.net
+    //
+    // netChainSubset - Create chain file with subset of chains that appear in
+    // the net
+    // usage:
+    //    netChainSubset in.net in.chain out.chain
+    //
+    // chainStitchId - Join chain fragments with the same chain ID into a single
+    //    chain per ID.
+    // usage:
+    //    chainStitchId in.chain out.chain
+    //
+    // netSplit - Split a genome net file into chromosome net files
+    // usage:
+    //   netSplit in.net outDir
+    plan.step(
+        "chain-net",
+        vec![
+            "chainNet -minSpace=1 all.pre.chain target.chr.sizes query.chr.sizes stdout query.chainnet | netSyntenic stdin noClass.net".to_string(),
+            "netChainSubset -verbose=0 noClass.net all.chain stdout | chainStitchId stdin over.chain".to_string(),
+            "mkdir -p net".to_string(),
+            "netSplit noClass.net net > /dev/null".to_string(),
+        ],
+        vec![
+            tmp("query.chainnet"),
+            tmp("noClass.net"),
+            tmp("over.chain"),
+            tmp("net/<chr>.net"),
+        ],
+    );
+
+    // netToAxt - Convert net (and chain) to axt.
+    // usage:
+    //   netToAxt in.net in.chain target.2bit query.2bit out.axt
+    //
+    // axtSort - Sort axt files
+    // usage:
+    //   axtSort in.axt out.axt
+    plan.step(
+        "netToAxt",
+        vec![
+            "mkdir -p axtNet".to_string(),
+            "netToAxt net/<chr>.net all.pre.chain target.chr.2bit query.chr.2bit stdout | axtSort stdin axtNet/<chr>.axt".to_string(),
+        ],
+        vec![tmp("axtNet/<chr>.axt")],
+    );
+
+    if !opts.is_syn {
+        plan.step(
+            "axtToMaf",
+            vec![format!(
+                "axtToMaf {} axtNet/<chr>.axt target.chr.sizes query.chr.sizes {}",
+                opts.prefix_args, maf_output
+            )],
+            maf_outputs,
+        );
     } else {
-        std::fs::create_dir_all("synNet")?;
-        std::fs::create_dir_all("chain")?;
-
-        run_cmd!(info "==> synNet.maf")?;
-
         // netFilter - Filter out parts of net.  What passes
-        // filter goes to standard output.  Note a net is a
-        // recursive data structure.  If a parent fails to pass
-        // the filter, the children are not even considered.
+        // filter goes to standard output.
         // usage:
         //    netFilter in.net(s)
-        run_cmd!(
-            netFilter -syn noClass.net |
-                netSplit stdin synNet > /dev/null
-        )?;
-
+        //
         // chainSplit - Split chains up by target or query sequence
         // usage:
         //    chainSplit outDir inChain(s)
-        // options:
-        //    -q  - Split on query (default is on target)
-        //    -lump=N  Lump together so have only N split files.
-        run_cmd!(
-            chainSplit synNet all.chain
-        )?;
-
-        let files = pgr::libs::io::list_files_ext("synNet", "net");
-        for file in files {
-            let stem = pgr::libs::io::basename_or_err(&file)?;
-            let net_stem = file
-                .strip_suffix(".net")
-                .ok_or_else(|| anyhow::anyhow!("expected .net suffix: {}", file))?;
-            let chain_file = format!("{}.chain", net_stem);
-            let maf_output = if abs_outdir == "stdout" {
-                "stdout".to_string()
-            } else {
-                format!("{}/{}.maf", abs_outdir, stem)
-            };
-            let prefix_args = if opt_tname.is_empty() {
-                "".to_string()
-            } else {
-                format!("-tPrefix={} -qPrefix={}", opt_tname, opt_qname)
-            };
-            run_cmd!(
-                netToAxt ${file} ${chain_file} target.chr.2bit query.chr.2bit stdout |
-                    axtSort stdin stdout |
-                    axtToMaf ${prefix_args} stdin target.chr.sizes query.chr.sizes ${maf_output}
-            )?;
-        }
+        let mut outputs = vec![tmp("synNet/<chr>.net"), tmp("synNet/<chr>.chain")];
+        outputs.extend(maf_outputs);
+        plan.step(
+            "synNet.maf",
+            vec![
+                "mkdir -p synNet".to_string(),
+                "netFilter -syn noClass.net | netSplit stdin synNet > /dev/null".to_string(),
+                "chainSplit synNet all.chain".to_string(),
+                format!(
+                    "netToAxt synNet/<chr>.net synNet/<chr>.chain target.chr.2bit query.chr.2bit stdout | axtSort stdin stdout | axtToMaf {} stdin target.chr.sizes query.chr.sizes {}",
+                    opts.prefix_args, maf_output
+                ),
+            ],
+            outputs,
+        );
     }
 
-    // Done
-
-    Ok(())
+    if opts.outdir != "stdout" {
+        let mut inputs = vec![opts.abs_target.clone(), opts.abs_query.clone()];
+        inputs.extend(opts.infiles.iter().cloned());
+        plan.step_with_inputs(
+            DONE_STEP,
+            vec![format!(
+                "touch {}",
+                shell_quote(&format!("{}/{}", opts.outdir, DONE_MARKER))
+            )],
+            inputs,
+            vec![format!("{}/{}", opts.outdir, DONE_MARKER)],
+        );
+    }
+    Ok(plan)
 }
@@ -18,16 +18,44 @@ The 'field' argument determines what data to collect, the following are currentl
 
 * idSpread - difference between the highest and lowest fraction identity for alignments of a query. Output line per query, with the difference.
 
+The following fields are binned per alignment, with output as `bin_start<TAB>count`:
+
+* identity - fraction identity (matches / aligned bases), binned over [0, 1].
+
+* coverage - fraction of the query covered by aligned bases, binned over [0, 1].
+
+* score - PSL score, binned over the observed range.
+
+* qsize, tsize - query or target sequence size, binned over the observed range.
+
+Use --bins N (default: 10) for N equal bins, or --width W for fixed-width bins.
+The last bin includes the upper bound. --non-zero omits empty bins.
+
 Examples:
 1. Collect alignment counts per query:
    pgr psl histo --field alignsPerQuery in.psl -o out.histo
+
+2. Histogram of identities in 20 bins:
+   pgr psl histo --field identity --bins 20 in.psl
+
+3. Histogram of query sizes in 1 kb bins:
+   pgr psl histo --field qsize --width 1000 in.psl
 "###,
         )
         .arg(
             Arg::new("field")
                 .long("field")
                 .required(true)
-                .value_parser(["alignsPerQuery", "coverSpread", "idSpread"])
+                .value_parser([
+                    "alignsPerQuery",
+                    "coverSpread",
+                    "idSpread",
+                    "identity",
+                    "coverage",
+                    "score",
+                    "qsize",
+                    "tsize",
+                ])
                 .help("What data to collect"),
         )
         .arg(crate::cmd_pgr::args::infile_arg().help("Input PSL file. [stdin] for standard input"))
@@ -45,6 +73,22 @@ Examples:
                 .action(ArgAction::SetTrue)
                 .help("Omit queries with zero values"),
         )
+        .arg(
+            Arg::new("bins")
+                .long("bins")
+                .num_args(1)
+                .default_value("10")
+                .value_parser(clap::value_parser!(usize))
+                .help("Number of bins for binned fields"),
+        )
+        .arg(
+            Arg::new("width")
+                .long("width")
+                .num_args(1)
+                .value_parser(clap::value_parser!(f64))
+                .conflicts_with("bins")
+                .help("Bin width for binned fields"),
+        )
 }
 /// Execute the histo command.
 pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
@@ -59,7 +103,16 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let mut writer =
         pgr::writer(output).with_context(|| format!("Failed to open writer for {}", output))?;
 
-    pgr::libs::fmt::psl::histogram(reader, &mut writer, what, multi_only, non_zero)?;
+    match what.as_str() {
+        "alignsPerQuery" | "coverSpread" | "idSpread" => {
+            pgr::libs::fmt::psl::histogram(reader, &mut writer, what, multi_only, non_zero)?
+        }
+        _ => {
+            let bins = *args.get_one::<usize>("bins").unwrap();
+            let width = args.get_one::<f64>("width").copied();
+            pgr::libs::fmt::psl::histogram_binned(reader, &mut writer, what, bins, width, non_zero)?
+        }
+    }
 
     writer.flush()?;
     Ok(())
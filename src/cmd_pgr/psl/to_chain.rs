@@ -1,5 +1,7 @@
 use anyhow::Context;
-use clap::{Arg, ArgMatches, Command};
+use clap::{builder, Arg, ArgMatches, Command};
+use pgr::libs::chain::{psl_matrix_score, GapCalc, ScoreContext, SubMatrix};
+use pgr::libs::fmt::twobit::TwoBitFile;
 use std::io::Write;
 /// Build the clap subcommand for to-chain.
 pub fn make_subcommand() -> Command {
@@ -13,6 +15,13 @@ Notes:
 * Chain format requires an explicit target strand. PSL records with a '-' target strand must be reverse-complemented first.
 * By default, records with '-' target strand cause an error; use --fix-strand to reverse-complement them automatically.
 * Malformed PSL lines are skipped with a warning unless --strict is used.
+* `--score` selects how chain scores are computed:
+    * `matches` (default): the PSL score (matches + repMatches/2 - mismatches - inserts).
+    * `blastz`: +100 per match and -100 per mismatch or N from the PSL counts,
+      minus `--gap-model` costs between blocks. No sequences needed.
+    * `matrix`: axtChain-style score; blocks are scored from the sequences in
+      `--target-2bit`/`--query-2bit` with `--score-scheme` (default: +100/-100),
+      minus `--gap-model` costs between blocks.
 
 Examples:
 1. Convert PSL to Chain:
@@ -23,6 +32,9 @@ Examples:
 
 3. Fail on parse errors:
    pgr psl to-chain in.psl -o out.chain --strict
+
+4. Score chains from the sequences:
+   pgr psl to-chain in.psl -o out.chain --score matrix -t t.2bit -q q.2bit
 "###,
         )
         .arg(crate::cmd_pgr::args::infile_arg().help("Input PSL file. [stdin] for standard input"))
@@ -39,6 +51,26 @@ Examples:
                 .action(clap::ArgAction::SetTrue)
                 .help("Fail on parse errors instead of skipping malformed lines"),
         )
+        .arg(
+            Arg::new("score")
+                .long("score")
+                .num_args(1)
+                .default_value("matches")
+                .value_parser([
+                    builder::PossibleValue::new("matches"),
+                    builder::PossibleValue::new("blastz"),
+                    builder::PossibleValue::new("matrix"),
+                ])
+                .help("How to compute chain scores"),
+        )
+        .arg(crate::cmd_pgr::args::gap_model_arg(
+            "loose",
+            &["loose", "medium"],
+            "Gap model for blastz/matrix scores: loose or medium",
+        ))
+        .arg(crate::cmd_pgr::args::score_scheme_arg())
+        .arg(crate::cmd_pgr::args::target_2bit_arg().required(false))
+        .arg(crate::cmd_pgr::args::query_2bit_arg().required(false))
 }
 /// Execute the to-chain command.
 pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
@@ -46,13 +78,49 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let output = crate::cmd_pgr::args::get_outfile(args);
     let fix_strand = args.get_flag("fix_strand");
     let strict = args.get_flag("strict");
+    let score_mode = args.get_one::<String>("score").unwrap();
+    let gap_calc = match args.get_one::<String>("gap_model").unwrap().as_str() {
+        "medium" => GapCalc::medium(),
+        _ => GapCalc::loose(),
+    };
 
     let reader =
         pgr::reader(input).with_context(|| format!("Failed to open reader for {}", input))?;
     let mut writer =
         pgr::writer(output).with_context(|| format!("Failed to open writer for {}", output))?;
 
-    pgr::libs::fmt::psl::to_chain(reader, &mut writer, fix_strand, strict)?;
+    match score_mode.as_str() {
+        "blastz" => pgr::libs::fmt::psl::to_chain(reader, &mut writer, fix_strand, strict, |p| {
+            Ok(p.blastz_score(&gap_calc))
+        })?,
+        "matrix" => {
+            let (Some(t_path), Some(q_path)) = (
+                args.get_one::<String>("target_2bit"),
+                args.get_one::<String>("query_2bit"),
+            ) else {
+                anyhow::bail!("--score matrix requires --target-2bit and --query-2bit");
+            };
+            let mut t_2bit = TwoBitFile::open(t_path)
+                .with_context(|| format!("Failed to open 2bit file {}", t_path))?;
+            let mut q_2bit = TwoBitFile::open(q_path)
+                .with_context(|| format!("Failed to open 2bit file {}", q_path))?;
+            let matrix = match args.get_one::<String>("score_scheme") {
+                Some(name) => SubMatrix::from_name(name)?,
+                None => SubMatrix::default(),
+            };
+            let mut ctx = ScoreContext {
+                t_2bit: &mut t_2bit,
+                q_2bit: &mut q_2bit,
+                matrix: &matrix,
+            };
+            pgr::libs::fmt::psl::to_chain(reader, &mut writer, fix_strand, strict, |p| {
+                psl_matrix_score(p, &mut ctx, &gap_calc)
+            })?
+        }
+        _ => pgr::libs::fmt::psl::to_chain(reader, &mut writer, fix_strand, strict, |p| {
+            Ok(p.score() as f64)
+        })?,
+    }
 
     writer.flush()?;
     Ok(())
@@ -1,5 +1,5 @@
 use anyhow::Context;
-use clap::{ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 use pgr::libs::fmt::twobit::TwoBitFile;
 use pgr::libs::loc::merge_intervals;
 use std::io::Write;
@@ -18,9 +18,17 @@ The output is a list of regions in the format:
     seq_name:start-end        # For regions spanning multiple positions
     seq_name:position         # For single positions
 
+With --bed, the raw blocks are written as BED instead:
+    seq_name<TAB>start<TAB>end<TAB>kind
+
 Notes:
 * Coordinates are 1-based, inclusive
 * Adjacent masked positions are merged into a single region
+* --bed reads the 2bit block tables directly; coordinates are 0-based, half-open
+    * kind is `mask` for soft-mask blocks and `N` for N blocks
+    * Soft-mask blocks are reported by default; add --n-blocks for the N runs,
+      or use --gap for the N runs only
+    * Blocks are not merged, so a soft-masked N run appears in both sets
 * 2bit files are binary and require random access (seeking)
 * Does not support stdin or gzipped inputs
 
@@ -31,16 +39,35 @@ Examples:
 2. Identify only N/n gap regions (hard-masked):
    pgr 2bit masked input.2bit --gap -o gap_regions.txt
 
+3. Soft-mask and N blocks as BED:
+   pgr 2bit masked input.2bit --bed --n-blocks -o blocks.bed
+
 "###,
         )
         .arg(crate::cmd_pgr::args::infiles_arg("2bit"))
         .arg(crate::cmd_pgr::args::gap_arg())
+        .arg(
+            Arg::new("bed")
+                .long("bed")
+                .action(ArgAction::SetTrue)
+                .help("Write the raw blocks as BED"),
+        )
+        .arg(
+            Arg::new("n_blocks")
+                .long("n-blocks")
+                .action(ArgAction::SetTrue)
+                .requires("bed")
+                .conflicts_with("gap")
+                .help("With --bed, also write the N blocks"),
+        )
         .arg(crate::cmd_pgr::args::outfile_arg())
 }
 
 /// Execute the masked command.
 pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let is_gap = args.get_flag("gap");
+    let is_bed = args.get_flag("bed");
+    let is_n_blocks = args.get_flag("n_blocks");
     let outfile = crate::cmd_pgr::args::get_outfile(args);
     let mut writer =
         pgr::writer(outfile).with_context(|| format!("Failed to open writer for {}", outfile))?;
@@ -53,6 +80,22 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         for name in names {
             let (n_blocks, mask_blocks) = tb.get_sequence_blocks(&name)?;
 
+            if is_bed {
+                let mut blocks = Vec::new();
+                if !is_gap {
+                    blocks.extend(mask_blocks.0.into_iter().map(|b| (b, "mask")));
+                }
+                if is_gap || is_n_blocks {
+                    blocks.extend(n_blocks.0.into_iter().map(|b| (b, "N")));
+                }
+                blocks.sort_by_key(|(b, _)| (b.start, b.end));
+
+                for (block, kind) in blocks {
+                    writeln!(writer, "{}\t{}\t{}\t{}", name, block.start, block.end, kind)?;
+                }
+                continue;
+            }
+
             let mut blocks = Vec::new();
             blocks.extend(n_blocks.0);
             if !is_gap {
@@ -1,5 +1,5 @@
 use anyhow::Context;
-use clap::{ArgMatches, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 use pgr::libs::fmt::twobit::TwoBitFile;
 use std::io::Write;
 
@@ -13,6 +13,9 @@ This command retrieves the sequence sizes from one or more 2bit files.
 
 Notes:
 * Use --no-ns to report the size excluding hard-masked N positions
+* --n-count appends a third column with the number of N bases, read from the
+  N-block table without decoding the sequence
+* --total appends a final `total<TAB>sum` line (with the N sum under --n-count)
 * 2bit files are binary and require random access (seeking)
 * Does not support stdin or gzipped inputs
 
@@ -23,11 +26,26 @@ Examples:
 2. Save the output to a file:
    pgr 2bit size input.2bit -o output.tsv
 
+3. N counts and the genome total:
+   pgr 2bit size input.2bit --n-count --total
+
 "###,
         )
         .arg(crate::cmd_pgr::args::infiles_arg("2bit"))
         .arg(crate::cmd_pgr::args::outfile_arg())
         .arg(crate::cmd_pgr::args::no_ns_arg())
+        .arg(
+            Arg::new("n_count")
+                .long("n-count")
+                .action(ArgAction::SetTrue)
+                .help("Append a column with the number of N bases"),
+        )
+        .arg(
+            Arg::new("total")
+                .long("total")
+                .action(ArgAction::SetTrue)
+                .help("Append a final line with the sum of sizes"),
+        )
 }
 
 /// Execute the size command.
@@ -36,6 +54,11 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let mut writer =
         pgr::writer(outfile).with_context(|| format!("Failed to open writer for {}", outfile))?;
     let no_ns = args.get_flag("no_ns");
+    let is_n_count = args.get_flag("n_count");
+    let is_total = args.get_flag("total");
+
+    let mut sum = 0;
+    let mut n_sum = 0;
 
     for infile in args.get_many::<String>("infiles").unwrap() {
         let mut tb = TwoBitFile::open(infile)
@@ -50,7 +73,24 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
             } else {
                 tb.get_sequence_len(&name)?
             };
-            writeln!(writer, "{}\t{}", name, len)?;
+            sum += len;
+
+            if is_n_count {
+                let (n_blocks, _) = tb.get_sequence_blocks(&name)?;
+                let n_count: usize = n_blocks.iter().map(|b| b.end - b.start).sum();
+                n_sum += n_count;
+                writeln!(writer, "{}\t{}\t{}", name, len, n_count)?;
+            } else {
+                writeln!(writer, "{}\t{}", name, len)?;
+            }
+        }
+    }
+
+    if is_total {
+        if is_n_count {
+            writeln!(writer, "total\t{}\t{}", sum, n_sum)?;
+        } else {
+            writeln!(writer, "total\t{}", sum)?;
         }
     }
 
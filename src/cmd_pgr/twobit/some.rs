@@ -1,5 +1,5 @@
 use anyhow::Context;
-use clap::{ArgMatches, Command};
+use clap::{Arg, ArgMatches, Command};
 use pgr::libs::fmt::twobit::TwoBitFile;
 use std::collections::HashSet;
 use std::io::Write;
@@ -16,6 +16,8 @@ and writes them to a FASTA file.
 Notes:
 * Case-sensitive name matching
 * One sequence name per line in the list file
+* The list can be given positionally or with --list
+* Listed names absent from the 2bit file are reported as warnings
 * Empty lines and lines starting with '#' are ignored
 * Only the first whitespace-delimited field on each line is used; trailing text is ignored
 * Output format is FASTA
@@ -29,12 +31,22 @@ Examples:
 2. Extract sequences NOT in list.txt:
    pgr 2bit some input.2bit list.txt -i -o output.fa
 
+3. The same as 1, with the list as an option:
+   pgr 2bit some input.2bit --list list.txt -o output.fa
+
 "###,
         )
         .arg(crate::cmd_pgr::args::infile_arg_required_with_help(
             "Input 2bit file to process",
         ))
-        .arg(crate::cmd_pgr::args::fa_name_list_arg(true))
+        .arg(crate::cmd_pgr::args::fa_name_list_arg(false).required_unless_present("list"))
+        .arg(
+            Arg::new("list")
+                .long("list")
+                .num_args(1)
+                .conflicts_with("name_list")
+                .help("File containing one sequence name per line"),
+        )
         .arg(crate::cmd_pgr::args::invert_arg())
         .arg(crate::cmd_pgr::args::outfile_arg())
 }
@@ -43,7 +55,10 @@ Examples:
 pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
     let is_invert = args.get_flag("invert");
     let infile = args.get_one::<String>("infile").unwrap();
-    let list_file = args.get_one::<String>("name_list").unwrap();
+    let list_file = args
+        .get_one::<String>("list")
+        .or_else(|| args.get_one::<String>("name_list"))
+        .unwrap();
     let outfile = crate::cmd_pgr::args::get_outfile(args);
 
     // Load list, ignoring empty lines and comment lines starting with '#'.
@@ -64,6 +79,12 @@ pub fn execute(args: &ArgMatches) -> anyhow::Result<()> {
         TwoBitFile::open(infile).with_context(|| format!("Failed to open 2bit file {}", infile))?;
     let names = tb.get_sequence_names();
 
+    let mut missing: Vec<&String> = set_list.iter().filter(|n| !tb.has_sequence(n)).collect();
+    missing.sort();
+    for name in missing {
+        log::warn!("{} not found in the 2bit file", name);
+    }
+
     let mut writer =
         pgr::writer(outfile).with_context(|| format!("Failed to open writer for {}", outfile))?;
 
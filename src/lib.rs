@@ -3,4 +3,4 @@
 
 pub mod libs;
 pub use libs::alignment::coords::reverse_range;
-pub use libs::io::{is_bgzf, read_lines, read_sizes, reader, writer};
+pub use libs::io::{gz_writer, is_bgzf, read_lines, read_sizes, reader, writer};
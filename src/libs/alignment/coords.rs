@@ -43,48 +43,6 @@ pub fn seq_intspan(seq: &[u8]) -> IntSpan {
     IntSpan::from_pair(1, seq.len() as i32).diff(&indel_intspan(seq))
 }
 
-/// Coordinate transforming - from chr to align
-///
-/// ```ignore
-/// use pgr::libs::alignment::{indel_intspan, seq_intspan};
-/// let tests : Vec<(&str, i32, i32, &str, i32)> = vec![
-///     // seq, pos, chr_start, strand, expected
-///     ("AAAATTTTTG", 4, 1, "+", 4),
-///     ("AAAATTTTTG", 4, 1, "-", 7),
-///     ("-AA--TTTGG", 5, 1, "+", 8),
-///     ("-AA--TTTGG", 5, 1, "-", 6),
-///     ("-AA--TTTGG", 105, 101, "+", 8),
-///     ("-AA--TTTGG", 105, 101, "-", 6),
-/// ];
-/// for (seq, pos, chr_start, strand, expected) in tests {
-///     let ints = seq_intspan(seq.as_ref());
-///     let result = pgr::libs::alignment::chr_to_align(&ints, pos, chr_start, strand).unwrap();
-///     assert_eq!(result, expected);
-/// }
-/// ```
-pub fn chr_to_align(ints: &IntSpan, pos: i32, chr_start: i32, strand: &str) -> anyhow::Result<i32> {
-    let chr_end = chr_start + ints.size() - 1;
-
-    if pos < chr_start || pos > chr_end {
-        return Err(anyhow!(
-            "[{}] out of ranges [{}, {}]",
-            pos,
-            chr_start,
-            chr_end
-        ));
-    }
-
-    let aln_pos = match strand {
-        "+" => ints.at(pos - chr_start + 1),
-        "-" => ints.at(-(pos - chr_start + 1)),
-        _ => {
-            return Err(anyhow!("Unrecognized strand: {}", strand));
-        }
-    };
-
-    Ok(aln_pos)
-}
-
 /// Coordinate transforming - from align to chr
 ///
 /// ```ignore
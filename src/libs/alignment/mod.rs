@@ -6,16 +6,20 @@ pub mod trim;
 pub mod variation;
 
 pub use coords::{
-    align_to_chr, chr_to_align, indel_intspan, reverse_range, reverse_range_1based,
-    reverse_range_1based_pair, reverse_range_pair, seq_intspan,
+    align_to_chr, indel_intspan, reverse_range, reverse_range_1based, reverse_range_1based_pair,
+    reverse_range_pair, seq_intspan,
 };
 pub use msa::{
-    align_seqs, align_seqs_quick, get_consensus_poa_builtin, get_consensus_poa_external,
+    align_seqs, align_seqs_builtin, align_seqs_codon, align_seqs_quick, get_consensus_poa_builtin,
+    get_consensus_poa_external, get_poa_dot_builtin,
 };
 pub use slice::slice_block;
-pub use stat::{alignment_stat, pair_d};
+pub use stat::{
+    alignment_stat, column_counts, conservation, information_content, pair_aa_d, pair_d,
+    pair_overlap, poisson_distance, smooth_scores,
+};
 pub use trim::{trim_complex_indel, trim_head_tail, trim_outgroup, trim_pure_dash};
 pub use variation::{
-    collect_indels, collect_subs, get_indels, get_subs, polarize_indels, polarize_subs,
-    vcf_alt_bases, Indel, Substitution,
+    collect_indels, collect_subs, get_indels, get_subs, polarize_indels, polarize_subs, Indel,
+    Substitution,
 };
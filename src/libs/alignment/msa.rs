@@ -166,14 +166,7 @@ pub fn get_consensus_poa_builtin(
         gap_open,
         gap_extend,
     };
-    let align_type = match algo_code {
-        0 => AlignmentType::Local,
-        1 => AlignmentType::Global,
-        2 => AlignmentType::SemiGlobal,
-        _ => AlignmentType::Global,
-    };
-
-    let mut poa = Poa::new(params, align_type);
+    let mut poa = Poa::new(params, align_type_from_code(algo_code));
 
     for seq in seqs {
         poa.add_sequence(seq);
@@ -184,6 +177,118 @@ pub fn get_consensus_poa_builtin(
     Ok(consensus_str)
 }
 
+/// Builds the built-in POA graph for `seqs` and renders it in DOT format.
+///
+/// Used for debugging consensus calls; see [`crate::libs::poa::graph::PoaGraph::to_dot`].
+pub fn get_poa_dot_builtin(seqs: &[&[u8]], params: &AlignmentParams, algo_code: i32) -> String {
+    let mut poa = Poa::new(params.clone(), align_type_from_code(algo_code));
+
+    for seq in seqs {
+        poa.add_sequence(seq);
+    }
+
+    poa.graph().to_dot()
+}
+
+/// Maps an alignment mode code (0=local, 1=global, 2=semi_global) to [`AlignmentType`].
+fn align_type_from_code(algo_code: i32) -> AlignmentType {
+    match algo_code {
+        0 => AlignmentType::Local,
+        1 => AlignmentType::Global,
+        2 => AlignmentType::SemiGlobal,
+        _ => AlignmentType::Global,
+    }
+}
+
+/// Realigns sequences with the built-in POA engine.
+///
+/// As with the external aligners, existing gaps are removed before realigning.
+///
+/// ```ignore
+/// use pgr::libs::poa::AlignmentParams;
+/// let seqs = vec!["ACGTAC--GTAC".to_string(), "ACGT--ACGTAC".to_string()];
+/// let alns = pgr::libs::alignment::align_seqs_builtin(&seqs, &AlignmentParams::default());
+/// assert_eq!(alns, vec!["ACGTACGTAC".to_string(), "ACGTACGTAC".to_string()]);
+/// ```
+pub fn align_seqs_builtin(seqs: &[String], params: &AlignmentParams) -> Vec<String> {
+    let mut poa = Poa::new(params.clone(), AlignmentType::Global);
+    for seq in seqs {
+        let ungapped: Vec<u8> = seq.bytes().filter(|b| *b != b'-').collect();
+        poa.add_sequence(&ungapped);
+    }
+    poa.msa()
+}
+
+/// Codon-aware realignment.
+///
+/// Each sequence is translated in frame 1, the proteins are aligned with
+/// `aligner` (`"builtin"` or an external program), and every residue is then
+/// replaced by its own codon, so gaps only ever come in multiples of three.
+///
+/// Returns `None` when the block can't be aligned codon by codon: the first
+/// (reference) sequence is out of frame, i.e. contains an internal stop
+/// codon, or any ungapped sequence has a length not divisible by three.
+///
+/// ```ignore
+/// use pgr::libs::poa::AlignmentParams;
+/// let seqs = vec!["ATGAAACCCTTT".to_string(), "ATGCCCTTT".to_string()];
+/// let alns = pgr::libs::alignment::align_seqs_codon(&seqs, "builtin", &AlignmentParams::default())
+///     .unwrap()
+///     .unwrap();
+/// assert_eq!(alns[1], "ATG---CCCTTT".to_string());
+/// ```
+pub fn align_seqs_codon(
+    seqs: &[String],
+    aligner: &str,
+    params: &AlignmentParams,
+) -> anyhow::Result<Option<Vec<String>>> {
+    let ungapped: Vec<Vec<u8>> = seqs
+        .iter()
+        .map(|seq| seq.bytes().filter(|b| *b != b'-').collect())
+        .collect();
+    if ungapped.iter().any(|seq| seq.len() % 3 != 0) {
+        return Ok(None);
+    }
+
+    let mut proteins: Vec<String> = Vec::with_capacity(ungapped.len());
+    for (i, seq) in ungapped.iter().enumerate() {
+        let mut protein = crate::libs::nt::translate(seq, 1, 1)?;
+        if i == 0 && protein.iter().rev().skip(1).any(|b| *b == b'*') {
+            return Ok(None);
+        }
+        // Residues are only placeholders for their codons; external aligners may reject `*`
+        for b in protein.iter_mut().filter(|b| **b == b'*') {
+            *b = b'X';
+        }
+        proteins.push(String::from_utf8(protein)?);
+    }
+
+    let aligned = if aligner == "builtin" {
+        align_seqs_builtin(&proteins, params)
+    } else {
+        align_seqs(&proteins, aligner)?
+    };
+
+    let mut out_seqs = Vec::with_capacity(aligned.len());
+    for (protein, seq) in aligned.iter().zip(&ungapped) {
+        let mut codons = seq.chunks_exact(3);
+        let mut out_seq = String::with_capacity(protein.len() * 3);
+        for residue in protein.bytes() {
+            if residue == b'-' {
+                out_seq.push_str("---");
+            } else {
+                let codon = codons
+                    .next()
+                    .ok_or_else(|| anyhow!("aligned protein is longer than its codons"))?;
+                out_seq.push_str(str::from_utf8(codon)?);
+            }
+        }
+        out_seqs.push(out_seq);
+    }
+
+    Ok(Some(out_seqs))
+}
+
 /// Returns Strings to avoid lifetime issues
 ///
 /// ```ignore
@@ -240,12 +345,7 @@ pub fn align_seqs(seqs: &[String], aligner: &str) -> anyhow::Result<Vec<String>>
             }
         }
         "builtin" => {
-            let params = AlignmentParams::default();
-            let mut poa = Poa::new(params, AlignmentType::Global);
-            for seq in seqs {
-                poa.add_sequence(seq.as_bytes());
-            }
-            return Ok(poa.msa());
+            return Ok(align_seqs_builtin(seqs, &AlignmentParams::default()));
         }
         _ => {
             return Err(anyhow!("Unrecognized aligner: {}", aligner));
@@ -362,6 +462,7 @@ pub fn align_seqs(seqs: &[String], aligner: &str) -> anyhow::Result<Vec<String>>
 pub fn align_seqs_quick(
     seqs: &[String],
     aligner: &str,
+    params: &AlignmentParams,
     pad: i32,
     fill: i32,
 ) -> anyhow::Result<Vec<String>> {
@@ -398,7 +499,11 @@ pub fn align_seqs_quick(
             let subseq = &a[start..end];
             subseqs.push(subseq.to_string());
         }
-        let subseqs = align_seqs(&subseqs, aligner)?;
+        let subseqs = if aligner == "builtin" {
+            align_seqs_builtin(&subseqs, params)
+        } else {
+            align_seqs(&subseqs, aligner)?
+        };
 
         // put aligned subseqs back
         for (a, s) in aligned.iter_mut().take(count).zip(subseqs.iter()) {
@@ -4,7 +4,7 @@ use std::io::Write;
 use anyhow::anyhow;
 use intspan::IntSpan;
 
-use crate::libs::alignment::{align_to_chr, chr_to_align, indel_intspan, seq_intspan};
+use crate::libs::alignment::{align_to_chr, indel_intspan, seq_intspan};
 use crate::libs::fmt::fas::FasBlock;
 
 /// Slice a FasBlock by a set of chromosome runlists, writing each subslice
@@ -34,9 +34,6 @@ pub fn slice_block<W: Write>(
         return Ok(());
     }
 
-    // target sequence intspan
-    let t_ints_seq = seq_intspan(block.entries[idx].seq());
-
     // per-species align intspans + shared indel regions
     let mut ints_seq_of: BTreeMap<&str, IntSpan> = BTreeMap::new();
     let mut indel_ints = IntSpan::new();
@@ -46,11 +43,19 @@ pub fn slice_block<W: Write>(
         indel_ints.merge(&indel_intspan(seq));
     }
 
+    // chr position -> alignment column of the reference
+    let to_col = |pos: i32| {
+        block.entries[idx]
+            .pos_to_col(pos as u64)
+            .map(|col| col as i32)
+            .ok_or_else(|| anyhow!("[{}] out of range {}", pos, trange))
+    };
+
     // collect subslices (chr-position intersections)
     let mut sub_slices: Vec<IntSpan> = vec![];
     for (lower, upper) in i_ints_chr.spans() {
-        let ss_start = chr_to_align(&t_ints_seq, lower, trange.start, trange.strand())?;
-        let ss_end = chr_to_align(&t_ints_seq, upper, trange.start, trange.strand())?;
+        let ss_start = to_col(lower)?;
+        let ss_end = to_col(upper)?;
         if ss_start >= ss_end {
             continue;
         }
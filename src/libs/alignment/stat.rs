@@ -1,3 +1,4 @@
+use crate::libs::fmt::fas::FasBlock;
 use crate::libs::nt::NT_VAL;
 use anyhow::bail;
 use itertools::Itertools;
@@ -40,6 +41,80 @@ pub fn pair_d(seq1: &[u8], seq2: &[u8]) -> anyhow::Result<f32> {
     Ok(difference as f32 / comparable as f32)
 }
 
+/// Number of columns where both sequences have a base (aligned overlap)
+///
+/// ```ignore
+/// let seq1 = b"ACGT-A-";
+/// let seq2 = b"AC-TTA-";
+/// assert_eq!(pgr::libs::alignment::pair_overlap(seq1, seq2).unwrap(), 4);
+/// ```
+pub fn pair_overlap(seq1: &[u8], seq2: &[u8]) -> anyhow::Result<usize> {
+    if seq1.len() != seq2.len() {
+        bail!(
+            "Two sequences of different length ({}!={})",
+            seq1.len(),
+            seq2.len()
+        );
+    }
+
+    Ok(seq1
+        .iter()
+        .zip(seq2)
+        .filter(|(b1, b2)| **b1 != b'-' && **b2 != b'-')
+        .count())
+}
+
+/// Protein p-distance between two aligned sequences
+///
+/// Only columns where both residues are one of the 20 standard amino acids
+/// are compared; gaps, stops and ambiguity codes (X, B, Z, J, ...) are
+/// treated as missing. Returns the proportion of differing residues and the
+/// number of compared sites.
+///
+/// ```ignore
+/// //             *
+/// let seq1 = b"MKVLAX-";
+/// let seq2 = b"MKILAWW";
+/// assert_eq!(pgr::libs::alignment::pair_aa_d(seq1, seq2).unwrap(), (0.2, 5));
+/// ```
+pub fn pair_aa_d(seq1: &[u8], seq2: &[u8]) -> anyhow::Result<(f64, usize)> {
+    if seq1.len() != seq2.len() {
+        bail!(
+            "Two sequences of different length ({}!={})",
+            seq1.len(),
+            seq2.len()
+        );
+    }
+
+    let is_aa = |b: &u8| b"ACDEFGHIKLMNPQRSTVWY".contains(&b.to_ascii_uppercase());
+
+    let mut comparable = 0;
+    let mut difference = 0;
+    for (res1, res2) in seq1.iter().zip(seq2) {
+        if is_aa(res1) && is_aa(res2) {
+            comparable += 1;
+            if !res1.eq_ignore_ascii_case(res2) {
+                difference += 1;
+            }
+        }
+    }
+
+    if comparable == 0 {
+        bail!("Comparable residues shouldn't be zero");
+    }
+
+    Ok((difference as f64 / comparable as f64, comparable))
+}
+
+/// Poisson-corrected distance, `-ln(1 - p)`; infinite when `p >= 1`
+pub fn poisson_distance(p: f64) -> f64 {
+    if p >= 1.0 {
+        f64::INFINITY
+    } else {
+        -(1.0 - p).ln()
+    }
+}
+
 /// Basic stats on alignments
 ///
 /// ```ignore
@@ -126,3 +201,149 @@ pub fn alignment_stat(seqs: &[&[u8]]) -> anyhow::Result<(i32, i32, i32, i32, i32
         mean_d,
     ))
 }
+
+/// Per-column counts of A/C/G/T/`-` in a block, case-insensitive.
+///
+/// Ambiguous bases are not counted, and sequences shorter than the block
+/// contribute nothing to the missing columns.
+pub fn column_counts(block: &FasBlock) -> Vec<[usize; 5]> {
+    let seqs: Vec<&[u8]> = block.entries.iter().map(|e| e.seq()).collect();
+    let length = seqs.iter().map(|s| s.len()).max().unwrap_or(0);
+
+    let mut columns = Vec::with_capacity(length);
+    for pos in 0..length {
+        let mut counts = [0usize; 5];
+        for seq in &seqs {
+            match seq.get(pos) {
+                Some(b'-') => counts[4] += 1,
+                Some(&base) => {
+                    let val = NT_VAL[base as usize];
+                    if val <= 3 {
+                        counts[val] += 1;
+                    }
+                }
+                None => {}
+            }
+        }
+        columns.push(counts);
+    }
+
+    columns
+}
+
+/// Information content (bits) of a column, `2 - H`, where `H` is the Shannon
+/// entropy of its A/C/G/T frequencies. Gaps are ignored; a column without
+/// any A/C/G/T has no information.
+pub fn information_content(counts: &[usize; 5]) -> f64 {
+    let total: usize = counts[..4].iter().sum();
+    if total == 0 {
+        return 0.0;
+    }
+
+    let entropy: f64 = counts[..4]
+        .iter()
+        .filter(|&&c| c > 0)
+        .map(|&c| {
+            let p = c as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum();
+    2.0 - entropy
+}
+
+/// Per-column conservation scores of a block.
+///
+/// The score is `1 - H / 2`, where `H` is the Shannon entropy (bits) of the
+/// A/C/G/T frequencies in the column; gaps and ambiguous bases are ignored.
+/// A fully conserved column scores 1, a column with all four bases in equal
+/// proportion scores 0, and columns without any A/C/G/T also score 0.
+pub fn conservation(block: &FasBlock) -> Vec<f64> {
+    column_counts(block)
+        .iter()
+        .map(|counts| information_content(counts) / 2.0)
+        .collect()
+}
+
+/// Centered moving average of `scores` over `window` columns.
+///
+/// Windows are truncated at both ends. A window of 0 or 1 returns the input.
+pub fn smooth_scores(scores: &[f64], window: usize) -> Vec<f64> {
+    if window <= 1 {
+        return scores.to_vec();
+    }
+
+    let half = window / 2;
+    (0..scores.len())
+        .map(|i| {
+            let lo = i.saturating_sub(half);
+            let hi = (i + window - half).min(scores.len());
+            scores[lo..hi].iter().sum::<f64>() / (hi - lo) as f64
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::fmt::fas::FasEntry;
+    use intspan::Range;
+
+    #[test]
+    fn test_pair_aa_d() {
+        // X and the gap column are skipped; one difference in five sites.
+        let (p, sites) = pair_aa_d(b"MKVLAX-", b"MKILAWW").unwrap();
+        assert_eq!(sites, 5);
+        assert!((p - 0.2).abs() < 1e-12);
+        assert!((poisson_distance(p) - 0.2231).abs() < 1e-4);
+        assert!(pair_aa_d(b"XBZ", b"MKV").is_err());
+    }
+
+    fn block(seqs: &[&str]) -> FasBlock {
+        let entries: Vec<FasEntry> = seqs
+            .iter()
+            .enumerate()
+            .map(|(i, s)| FasEntry::from(&Range::from(&format!("S{}", i), 1, 1), s.as_bytes()))
+            .collect();
+        FasBlock {
+            names: (0..seqs.len()).map(|i| format!("S{}", i)).collect(),
+            headers: entries.iter().map(|e| e.range().to_string()).collect(),
+            entries,
+        }
+    }
+
+    #[test]
+    fn test_conservation() {
+        // col 0: conserved; col 1: A/C/G/T; col 2: all gaps; col 3: A/a/-/N
+        let b = block(&["AA-A", "AC-a", "AG--", "AT-N"]);
+        let scores = conservation(&b);
+
+        assert_eq!(scores.len(), 4);
+        assert!((scores[0] - 1.0).abs() < 1e-9);
+        assert!(scores[1].abs() < 1e-9);
+        assert_eq!(scores[2], 0.0);
+        assert!((scores[3] - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_information_content() {
+        // col 0: conserved; col 1: A/C/G/T; col 2: all gaps
+        let b = block(&["AA-", "AC-", "AG-", "AT-"]);
+        let columns = column_counts(&b);
+
+        assert_eq!(columns[0], [4, 0, 0, 0, 0]);
+        assert_eq!(columns[1], [1, 1, 1, 1, 0]);
+        assert_eq!(columns[2], [0, 0, 0, 0, 4]);
+        assert!((information_content(&columns[0]) - 2.0).abs() < 1e-9);
+        assert!(information_content(&columns[1]).abs() < 1e-9);
+        assert_eq!(information_content(&columns[2]), 0.0);
+    }
+
+    #[test]
+    fn test_smooth_scores() {
+        let scores = [1.0, 0.0, 1.0, 0.0];
+        assert_eq!(smooth_scores(&scores, 1), scores.to_vec());
+
+        let smoothed = smooth_scores(&scores, 3);
+        assert_eq!(smoothed, vec![0.5, 2.0 / 3.0, 1.0 / 3.0, 0.5]);
+    }
+}
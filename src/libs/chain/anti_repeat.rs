@@ -13,11 +13,15 @@ use crate::libs::fmt::twobit::TwoBitFile;
 use crate::libs::nt::is_lower;
 
 /// Check a chain against both the degeneracy and repeat filters.
+///
+/// `repeat_penalty` scales how strongly soft-masked bases down-weight the score;
+/// see [`check_repeat`].
 pub fn check_chain<R: std::io::Read + std::io::Seek>(
     chain: &Chain,
     t_2bit: &mut TwoBitFile<R>,
     q_2bit: &mut TwoBitFile<R>,
     min_score: f64,
+    repeat_penalty: f64,
 ) -> bool {
     // Check if sequences exist
     if !t_2bit.sequence_offsets.contains_key(&chain.header.t_name)
@@ -34,7 +38,7 @@ pub fn check_chain<R: std::io::Read + std::io::Seek>(
     }
 
     // 2. Repeat Filter (Lowercase check)
-    check_repeat(chain, &blocks, t_2bit, q_2bit, min_score)
+    check_repeat(chain, &blocks, t_2bit, q_2bit, min_score, repeat_penalty)
 }
 
 /// Read target and query slices for a block, accounting for query strand.
@@ -146,13 +150,22 @@ pub fn check_degeneracy<R: std::io::Read + std::io::Seek>(
 }
 
 /// Repeat filter: penalize chains with many soft-masked (lowercase) bases.
+///
+/// The adjusted score is `score * 2 * (1 - min(1, repeat_penalty * rep / total))`;
+/// a `repeat_penalty` of 1.0 matches UCSC chainAntiRepeat. A `repeat_penalty`
+/// of 0 disables the adjustment and compares the raw score with `min_score`.
 pub fn check_repeat<R: std::io::Read + std::io::Seek>(
     chain: &Chain,
     blocks: &[Block],
     t_2bit: &mut TwoBitFile<R>,
     q_2bit: &mut TwoBitFile<R>,
     min_score: f64,
+    repeat_penalty: f64,
 ) -> bool {
+    if repeat_penalty == 0.0 {
+        return chain.header.score >= min_score;
+    }
+
     let mut rep_count = 0;
     let mut total = 0;
 
@@ -186,7 +199,8 @@ pub fn check_repeat<R: std::io::Read + std::io::Seek>(
         return false;
     }
 
-    let adjusted_score = chain.header.score * 2.0 * ((total - rep_count) as f64) / (total as f64);
+    let rep_frac = rep_count as f64 / total as f64;
+    let adjusted_score = chain.header.score * 2.0 * (1.0 - (repeat_penalty * rep_frac).min(1.0));
     if adjusted_score < min_score {
         log::info!(
             "Chain {} filtered by repeat: score {} -> {} (rep {}/{})",
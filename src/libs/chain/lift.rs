@@ -0,0 +1,159 @@
+//! Lift target-side intervals onto the query side through chains.
+//!
+//! Chains are indexed by target sequence in interval trees. An interval is
+//! lifted through the overlapping chain that aligns the most of its bases;
+//! the result is split at block boundaries and bases falling in gaps are
+//! dropped, as in UCSC `liftOver`.
+
+use coitrees::{BasicCOITree, Interval, IntervalNode, IntervalTree};
+use std::collections::HashMap;
+
+use super::record::Chain;
+
+/// A lifted piece on the query sequence (0-based, half-open, forward strand).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiftPiece {
+    pub q_name: String,
+    pub q_strand: char,
+    pub q_start: u64,
+    pub q_end: u64,
+}
+
+/// Result of lifting one interval through its best chain.
+#[derive(Debug, Clone)]
+pub struct LiftResult {
+    /// Aligned pieces, in target order.
+    pub pieces: Vec<LiftPiece>,
+    /// Number of target bases that fell in aligned blocks.
+    pub matched: u64,
+}
+
+/// Chains indexed by target name for interval lifting.
+pub struct ChainLift {
+    chains: Vec<Chain>,
+    trees: HashMap<String, BasicCOITree<usize, u32>>,
+}
+
+impl ChainLift {
+    /// Build the target-side index.
+    pub fn new(chains: Vec<Chain>) -> Self {
+        let mut by_target: HashMap<String, Vec<Interval<usize>>> = HashMap::new();
+        for (i, chain) in chains.iter().enumerate() {
+            if chain.header.t_end <= chain.header.t_start {
+                continue;
+            }
+            by_target
+                .entry(chain.header.t_name.clone())
+                .or_default()
+                .push(Interval::new(
+                    chain.header.t_start as i32,
+                    chain.header.t_end as i32 - 1,
+                    i,
+                ));
+        }
+
+        let trees = by_target
+            .into_iter()
+            .map(|(name, intervals)| (name, BasicCOITree::new(&intervals)))
+            .collect();
+
+        Self { chains, trees }
+    }
+
+    /// Lift `[start, end)` on `t_name` through the chain covering the most bases.
+    ///
+    /// Ties are broken by the higher chain score. Returns `None` when no chain
+    /// aligns any base of the interval.
+    pub fn lift(&self, t_name: &str, start: u64, end: u64) -> Option<LiftResult> {
+        if end <= start {
+            return None;
+        }
+        let tree = self.trees.get(t_name)?;
+
+        let mut hits: Vec<usize> = vec![];
+        tree.query(
+            start as i32,
+            end as i32 - 1,
+            |iv: &IntervalNode<usize, u32>| hits.push(iv.metadata),
+        );
+        hits.sort_unstable();
+
+        let mut best: Option<(LiftResult, f64)> = None;
+        for idx in hits {
+            let chain = &self.chains[idx];
+            let Some(sub) = chain.subset(start, end) else {
+                continue;
+            };
+
+            let header = &sub.header;
+            let mut pieces = vec![];
+            let mut matched = 0;
+            for b in sub.to_blocks() {
+                matched += b.t_end - b.t_start;
+                let (q_start, q_end) = if header.q_strand == '-' {
+                    (header.q_size - b.q_end, header.q_size - b.q_start)
+                } else {
+                    (b.q_start, b.q_end)
+                };
+                pieces.push(LiftPiece {
+                    q_name: header.q_name.clone(),
+                    q_strand: header.q_strand,
+                    q_start,
+                    q_end,
+                });
+            }
+
+            let is_better = match &best {
+                None => true,
+                Some((r, score)) => {
+                    matched > r.matched || (matched == r.matched && header.score > *score)
+                }
+            };
+            if is_better {
+                best = Some((LiftResult { pieces, matched }, header.score));
+            }
+        }
+
+        best.map(|(r, _)| r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::chain::read_chains;
+
+    fn lifter(input: &str) -> ChainLift {
+        ChainLift::new(read_chains(input.as_bytes()).unwrap())
+    }
+
+    #[test]
+    fn test_lift_block_and_gap() {
+        let lift =
+            lifter("chain 1000 chrT 1000 + 100 350 chrQ 1000 + 500 750 1\n100\t50\t50\n100\n");
+
+        // 150-200 is aligned, 200-230 falls in the target gap
+        let r = lift.lift("chrT", 150, 230).unwrap();
+        assert_eq!(r.matched, 50);
+        assert_eq!(r.pieces.len(), 1);
+        assert_eq!((r.pieces[0].q_start, r.pieces[0].q_end), (550, 600));
+
+        // Spans both blocks
+        let r = lift.lift("chrT", 190, 260).unwrap();
+        assert_eq!(r.matched, 20);
+        assert_eq!(r.pieces.len(), 2);
+        assert_eq!((r.pieces[1].q_start, r.pieces[1].q_end), (650, 660));
+
+        assert!(lift.lift("chrT", 210, 240).is_none());
+        assert!(lift.lift("chrX", 150, 230).is_none());
+    }
+
+    #[test]
+    fn test_lift_minus_query() {
+        let lift = lifter("chain 1000 chrT 1000 + 100 200 chrQ 1000 - 0 100 1\n100\n");
+
+        let r = lift.lift("chrT", 110, 120).unwrap();
+        assert_eq!(r.pieces[0].q_strand, '-');
+        assert_eq!((r.pieces[0].q_start, r.pieces[0].q_end), (980, 990));
+    }
+}
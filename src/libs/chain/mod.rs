@@ -26,6 +26,7 @@
 
 pub mod anti_repeat;
 pub mod connect;
+pub mod lift;
 pub mod net;
 pub mod pre_net;
 pub mod psl_chain;
@@ -36,8 +37,9 @@ pub mod sub_matrix;
 
 pub use crate::libs::ds::GapCalc;
 pub use connect::{calc_block_score, chain_blocks, ChainableBlock, ScoreContext};
+pub use lift::{ChainLift, LiftPiece, LiftResult};
 pub use pre_net::{is_haplotype, pre_net, PreNetOptions};
-pub use psl_chain::{chain_psl, group_psl_blocks, GroupData, GroupKey};
+pub use psl_chain::{chain_psl, group_psl_blocks, psl_matrix_score, GroupData, GroupKey};
 pub use record::{read_chains, Block, Chain, ChainData, ChainHeader, ChainReader};
 pub use sort::sort_chains;
 pub use stitch::stitch_chains;
@@ -8,11 +8,13 @@ use std::cell::{Ref, RefCell};
 use std::collections::HashMap;
 use std::rc::Rc;
 
-/// Per-class aggregate counters (count and bases covered).
+/// Per-class aggregate counters (count, bases covered and aligned bases).
 #[derive(Default)]
 pub struct Stats {
     pub count: u64,
     pub bases: u64,
+    /// Sum of the fills' `ali` fields; always 0 for the `gap` class.
+    pub ali: u64,
 }
 
 /// Recursively collect stats for a gap and its nested fills.
@@ -46,6 +48,7 @@ pub fn collect_stats_gap(gap: &Rc<RefCell<Gap>>, stats: &mut HashMap<String, Sta
         let entry = stats.entry(class).or_default();
         entry.count += 1;
         entry.bases += fill_ref.end - fill_ref.start;
+        entry.ali += fill_ref.ali;
 
         // Recurse
         collect_stats_fill(fill, stats);
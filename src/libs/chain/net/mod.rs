@@ -28,9 +28,9 @@ pub use class::{collect_stats_fill, collect_stats_gap, Stats};
 pub use filter::{filter_chrom, prune_gap, FilterCriteria};
 pub use finalize::finalize_net;
 pub use reader::read_nets;
-pub use subset::{subset_nets, SubsetOptions};
+pub use subset::{clip_chrom, subset_nets, SubsetOptions};
 pub use syntenic::classify_syntenic;
-pub use to_axt::net_to_axt;
+pub use to_axt::{net_to_axt, referenced_sequences};
 pub use types::{Chrom, Fill, Gap, NetNode, Space};
 pub use writer::{range_intersection, write_net, write_net_file, write_sorted_net};
 
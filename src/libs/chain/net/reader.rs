@@ -8,7 +8,8 @@ use std::rc::Rc;
 
 /// Reads UCSC Net text format and returns a vector of chromosomes.
 ///
-/// Unrecognized non-empty lines are silently skipped.
+/// Unrecognized non-empty lines are silently skipped. Malformed lines are
+/// reported with their 1-based line number and the offending token.
 pub fn read_nets<R: BufRead>(mut reader: R) -> Result<Vec<Chrom>> {
     let mut chroms = Vec::new();
     let mut current_chrom: Option<Chrom> = None;
@@ -16,7 +17,9 @@ pub fn read_nets<R: BufRead>(mut reader: R) -> Result<Vec<Chrom>> {
     let mut pending_comments = Vec::new();
 
     let mut line = String::new();
+    let mut line_no = 0;
     while reader.read_line(&mut line)? > 0 {
+        line_no += 1;
         if line.trim().is_empty() {
             line.clear();
             continue;
@@ -42,17 +45,22 @@ pub fn read_nets<R: BufRead>(mut reader: R) -> Result<Vec<Chrom>> {
             line.clear();
             continue;
         }
+        let at_line = |e: anyhow::Error| anyhow!("line {}: {}", line_no, e);
 
         match parts[0] {
             "net" => {
                 if parts.len() < 3 {
-                    bail!("net line needs at least 3 fields: {}", line.trim_end());
+                    bail!(
+                        "line {}: net line needs at least 3 fields: {}",
+                        line_no,
+                        line.trim_end()
+                    );
                 }
                 if let Some(c) = current_chrom {
                     chroms.push(c);
                 }
                 let name = parts[1];
-                let size = parse_u64(&parts, 2, "net size")?;
+                let size = parse_u64(&parts, 2, "net size").map_err(at_line)?;
                 let mut chrom = Chrom::new(name, size);
                 if !pending_comments.is_empty() {
                     chrom.comments = std::mem::take(&mut pending_comments);
@@ -62,122 +70,7 @@ pub fn read_nets<R: BufRead>(mut reader: R) -> Result<Vec<Chrom>> {
                 current_chrom = Some(chrom);
             }
             "fill" => {
-                // fill tStart tLength qName qStrand qStart qLength id chainId score ali [type class]
-                if parts.len() < 11 {
-                    bail!("fill line needs at least 11 fields: {}", line.trim_end());
-                }
-                let start = parse_u64(&parts, 1, "fill tStart")?;
-                let len = parse_u64(&parts, 2, "fill tLength")?;
-                let q_name = parts[3].to_string();
-                let q_strand = parts[4]
-                    .chars()
-                    .next()
-                    .ok_or_else(|| anyhow!("empty fill qStrand field"))?;
-                let q_start = parse_u64(&parts, 5, "fill qStart")?;
-                let q_len = parse_u64(&parts, 6, "fill qLength")?;
-                // parts[7] is "id"
-                let chain_id = parse_u64(&parts, 8, "fill chainId")?;
-                // parts[9] is "score"
-                let score = parse_f64(&parts, 10, "fill score")?;
-                // parts[11] is "ali"
-                let ali = parse_u64(&parts, 12, "fill ali")?;
-
-                let mut class = String::new();
-                let mut q_dup = None;
-                let mut q_over = None;
-                let mut q_far = None;
-                let mut t_n = None;
-                let mut q_n = None;
-                let mut t_r = None;
-                let mut q_r = None;
-                let mut t_trf = None;
-                let mut q_trf = None;
-
-                let mut i = 13;
-                while i < parts.len() {
-                    match parts[i] {
-                        "type" => {
-                            if i + 1 < parts.len() {
-                                class = parts[i + 1].to_string();
-                                i += 2;
-                            } else {
-                                i += 1;
-                            }
-                        }
-                        "qDup" => {
-                            let (v, ni) = parse_opt_u64(&parts, i)?;
-                            q_dup = v;
-                            i = ni;
-                        }
-                        "qOver" => {
-                            let (v, ni) = parse_opt_u64(&parts, i)?;
-                            q_over = v;
-                            i = ni;
-                        }
-                        "qFar" => {
-                            let (v, ni) = parse_opt_i64(&parts, i)?;
-                            q_far = v;
-                            i = ni;
-                        }
-                        "tN" => {
-                            let (v, ni) = parse_opt_u64(&parts, i)?;
-                            t_n = v;
-                            i = ni;
-                        }
-                        "qN" => {
-                            let (v, ni) = parse_opt_u64(&parts, i)?;
-                            q_n = v;
-                            i = ni;
-                        }
-                        "tR" => {
-                            let (v, ni) = parse_opt_u64(&parts, i)?;
-                            t_r = v;
-                            i = ni;
-                        }
-                        "qR" => {
-                            let (v, ni) = parse_opt_u64(&parts, i)?;
-                            q_r = v;
-                            i = ni;
-                        }
-                        "tTrf" => {
-                            let (v, ni) = parse_opt_u64(&parts, i)?;
-                            t_trf = v;
-                            i = ni;
-                        }
-                        "qTrf" => {
-                            let (v, ni) = parse_opt_u64(&parts, i)?;
-                            q_trf = v;
-                            i = ni;
-                        }
-                        _ => {
-                            i += 1;
-                        }
-                    }
-                }
-
-                let fill = Rc::new(RefCell::new(Fill {
-                    start,
-                    end: start + len,
-                    o_start: q_start,
-                    o_end: q_start + q_len,
-                    o_chrom: q_name,
-                    o_strand: q_strand,
-                    chain_id,
-                    score,
-                    ali,
-                    class,
-                    q_dup,
-                    q_over,
-                    q_far,
-                    chain: None,
-                    gaps: Vec::new(),
-                    t_n,
-                    q_n,
-                    t_r,
-                    q_r,
-                    t_trf,
-                    q_trf,
-                }));
+                let fill = Rc::new(RefCell::new(parse_fill(&parts, &line).map_err(at_line)?));
 
                 // Find parent gap
                 let mut parent_found = false;
@@ -196,83 +89,11 @@ pub fn read_nets<R: BufRead>(mut reader: R) -> Result<Vec<Chrom>> {
                     }
                 }
                 if !parent_found {
-                    bail!("orphaned fill line: {}", line.trim_end());
+                    bail!("line {}: orphaned fill line: {}", line_no, line.trim_end());
                 }
             }
             "gap" => {
-                // gap tStart tLength qName qStrand qStart qLength
-                if parts.len() < 7 {
-                    bail!("gap line needs at least 7 fields: {}", line.trim_end());
-                }
-                let start = parse_u64(&parts, 1, "gap tStart")?;
-                let len = parse_u64(&parts, 2, "gap tLength")?;
-                let _q_name = parts[3].to_string();
-                let _q_strand = parts[4]
-                    .chars()
-                    .next()
-                    .ok_or_else(|| anyhow!("empty gap qStrand field"))?;
-                let q_start = parse_u64(&parts, 5, "gap qStart")?;
-                let q_len = parse_u64(&parts, 6, "gap qLength")?;
-
-                let mut t_n = None;
-                let mut q_n = None;
-                let mut t_r = None;
-                let mut q_r = None;
-                let mut t_trf = None;
-                let mut q_trf = None;
-
-                let mut i = 7;
-                while i < parts.len() {
-                    match parts[i] {
-                        "tN" => {
-                            let (v, ni) = parse_opt_u64(&parts, i)?;
-                            t_n = v;
-                            i = ni;
-                        }
-                        "qN" => {
-                            let (v, ni) = parse_opt_u64(&parts, i)?;
-                            q_n = v;
-                            i = ni;
-                        }
-                        "tR" => {
-                            let (v, ni) = parse_opt_u64(&parts, i)?;
-                            t_r = v;
-                            i = ni;
-                        }
-                        "qR" => {
-                            let (v, ni) = parse_opt_u64(&parts, i)?;
-                            q_r = v;
-                            i = ni;
-                        }
-                        "tTrf" => {
-                            let (v, ni) = parse_opt_u64(&parts, i)?;
-                            t_trf = v;
-                            i = ni;
-                        }
-                        "qTrf" => {
-                            let (v, ni) = parse_opt_u64(&parts, i)?;
-                            q_trf = v;
-                            i = ni;
-                        }
-                        _ => {
-                            i += 1;
-                        }
-                    }
-                }
-
-                let gap = Rc::new(RefCell::new(Gap {
-                    start,
-                    end: start + len,
-                    o_start: q_start,
-                    o_end: q_start + q_len,
-                    fills: Vec::new(),
-                    t_n,
-                    q_n,
-                    t_r,
-                    q_r,
-                    t_trf,
-                    q_trf,
-                }));
+                let gap = Rc::new(RefCell::new(parse_gap(&parts, &line).map_err(at_line)?));
 
                 // Find parent fill
                 let mut parent_found = false;
@@ -291,7 +112,7 @@ pub fn read_nets<R: BufRead>(mut reader: R) -> Result<Vec<Chrom>> {
                     }
                 }
                 if !parent_found {
-                    bail!("orphaned gap line: {}", line.trim_end());
+                    bail!("line {}: orphaned gap line: {}", line_no, line.trim_end());
                 }
             }
             _ => {}
@@ -304,6 +125,203 @@ pub fn read_nets<R: BufRead>(mut reader: R) -> Result<Vec<Chrom>> {
     Ok(chroms)
 }
 
+// Parse a `fill` line into a detached fill.
+fn parse_fill(parts: &[&str], line: &str) -> Result<Fill> {
+    // fill tStart tLength qName qStrand qStart qLength id chainId score ali [type class]
+    if parts.len() < 11 {
+        bail!("fill line needs at least 11 fields: {}", line.trim_end());
+    }
+    let start = parse_u64(parts, 1, "fill tStart")?;
+    let len = parse_u64(parts, 2, "fill tLength")?;
+    let q_name = parts[3].to_string();
+    let q_strand = parts[4]
+        .chars()
+        .next()
+        .ok_or_else(|| anyhow!("empty fill qStrand field"))?;
+    let q_start = parse_u64(parts, 5, "fill qStart")?;
+    let q_len = parse_u64(parts, 6, "fill qLength")?;
+    // parts[7] is "id"
+    let chain_id = parse_u64(parts, 8, "fill chainId")?;
+    // parts[9] is "score"
+    let score = parse_f64(parts, 10, "fill score")?;
+    // parts[11] is "ali"
+    let ali = parse_u64(parts, 12, "fill ali")?;
+
+    let mut class = String::new();
+    let mut q_dup = None;
+    let mut q_over = None;
+    let mut q_far = None;
+    let mut t_n = None;
+    let mut q_n = None;
+    let mut t_r = None;
+    let mut q_r = None;
+    let mut t_trf = None;
+    let mut q_trf = None;
+
+    let mut i = 13;
+    while i < parts.len() {
+        match parts[i] {
+            "type" => {
+                if i + 1 < parts.len() {
+                    class = parts[i + 1].to_string();
+                    i += 2;
+                } else {
+                    i += 1;
+                }
+            }
+            "qDup" => {
+                let (v, ni) = parse_opt_u64(parts, i)?;
+                q_dup = v;
+                i = ni;
+            }
+            "qOver" => {
+                let (v, ni) = parse_opt_u64(parts, i)?;
+                q_over = v;
+                i = ni;
+            }
+            "qFar" => {
+                let (v, ni) = parse_opt_i64(parts, i)?;
+                q_far = v;
+                i = ni;
+            }
+            "tN" => {
+                let (v, ni) = parse_opt_u64(parts, i)?;
+                t_n = v;
+                i = ni;
+            }
+            "qN" => {
+                let (v, ni) = parse_opt_u64(parts, i)?;
+                q_n = v;
+                i = ni;
+            }
+            "tR" => {
+                let (v, ni) = parse_opt_u64(parts, i)?;
+                t_r = v;
+                i = ni;
+            }
+            "qR" => {
+                let (v, ni) = parse_opt_u64(parts, i)?;
+                q_r = v;
+                i = ni;
+            }
+            "tTrf" => {
+                let (v, ni) = parse_opt_u64(parts, i)?;
+                t_trf = v;
+                i = ni;
+            }
+            "qTrf" => {
+                let (v, ni) = parse_opt_u64(parts, i)?;
+                q_trf = v;
+                i = ni;
+            }
+            _ => {
+                i += 1;
+            }
+        }
+    }
+
+    Ok(Fill {
+        start,
+        end: start + len,
+        o_start: q_start,
+        o_end: q_start + q_len,
+        o_chrom: q_name,
+        o_strand: q_strand,
+        chain_id,
+        score,
+        ali,
+        class,
+        q_dup,
+        q_over,
+        q_far,
+        chain: None,
+        gaps: Vec::new(),
+        t_n,
+        q_n,
+        t_r,
+        q_r,
+        t_trf,
+        q_trf,
+    })
+}
+
+// Parse a `gap` line into a detached gap.
+fn parse_gap(parts: &[&str], line: &str) -> Result<Gap> {
+    // gap tStart tLength qName qStrand qStart qLength
+    if parts.len() < 7 {
+        bail!("gap line needs at least 7 fields: {}", line.trim_end());
+    }
+    let start = parse_u64(parts, 1, "gap tStart")?;
+    let len = parse_u64(parts, 2, "gap tLength")?;
+    let _q_name = parts[3].to_string();
+    let _q_strand = parts[4]
+        .chars()
+        .next()
+        .ok_or_else(|| anyhow!("empty gap qStrand field"))?;
+    let q_start = parse_u64(parts, 5, "gap qStart")?;
+    let q_len = parse_u64(parts, 6, "gap qLength")?;
+
+    let mut t_n = None;
+    let mut q_n = None;
+    let mut t_r = None;
+    let mut q_r = None;
+    let mut t_trf = None;
+    let mut q_trf = None;
+
+    let mut i = 7;
+    while i < parts.len() {
+        match parts[i] {
+            "tN" => {
+                let (v, ni) = parse_opt_u64(parts, i)?;
+                t_n = v;
+                i = ni;
+            }
+            "qN" => {
+                let (v, ni) = parse_opt_u64(parts, i)?;
+                q_n = v;
+                i = ni;
+            }
+            "tR" => {
+                let (v, ni) = parse_opt_u64(parts, i)?;
+                t_r = v;
+                i = ni;
+            }
+            "qR" => {
+                let (v, ni) = parse_opt_u64(parts, i)?;
+                q_r = v;
+                i = ni;
+            }
+            "tTrf" => {
+                let (v, ni) = parse_opt_u64(parts, i)?;
+                t_trf = v;
+                i = ni;
+            }
+            "qTrf" => {
+                let (v, ni) = parse_opt_u64(parts, i)?;
+                q_trf = v;
+                i = ni;
+            }
+            _ => {
+                i += 1;
+            }
+        }
+    }
+
+    Ok(Gap {
+        start,
+        end: start + len,
+        o_start: q_start,
+        o_end: q_start + q_len,
+        fills: Vec::new(),
+        t_n,
+        q_n,
+        t_r,
+        q_r,
+        t_trf,
+        q_trf,
+    })
+}
+
 // Parse a required u64 field at index `i`.
 fn parse_u64(parts: &[&str], i: usize, field: &str) -> Result<u64> {
     let s = parts
@@ -397,6 +415,15 @@ mod tests {
         assert!(r.is_err());
     }
 
+    #[test]
+    fn test_malformed_error_has_line_number() {
+        let data = "# comment\nnet chr1 100\n\nnet chr2 notanumber\n";
+        let e = read_nets(std::io::Cursor::new(data)).unwrap_err();
+        let msg = e.to_string();
+        assert!(msg.contains("line 4"), "{}", msg);
+        assert!(msg.contains("notanumber"), "{}", msg);
+    }
+
     #[test]
     fn test_empty_input() {
         let r = read_nets(std::io::Cursor::new(""));
@@ -1,6 +1,6 @@
 //! Subset chains based on net structure.
 
-use super::types::{Fill, Gap};
+use super::types::{Chrom, Fill, Gap};
 use crate::libs::chain::Chain;
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -32,6 +32,41 @@ pub fn subset_nets(
     Ok(())
 }
 
+/// Restrict a chromosome net to the target window `[start, end)`.
+///
+/// Fills and gaps outside the window are dropped; those crossing its edges are
+/// clipped to it. Only target coordinates are clipped, the query side is
+/// recomputed from the chains when [`subset_nets`] cuts them.
+pub fn clip_chrom(chrom: &Chrom, start: u64, end: u64) {
+    clip_gap(&chrom.root, start, end);
+}
+
+fn clip_gap(gap: &Rc<RefCell<Gap>>, start: u64, end: u64) {
+    let mut gap = gap.borrow_mut();
+    gap.start = gap.start.clamp(start, end);
+    gap.end = gap.end.clamp(start, end);
+    gap.fills.retain(|f| {
+        let f = f.borrow();
+        f.start < end && f.end > start
+    });
+    for fill in &gap.fills {
+        clip_fill(fill, start, end);
+    }
+}
+
+fn clip_fill(fill: &Rc<RefCell<Fill>>, start: u64, end: u64) {
+    let mut fill = fill.borrow_mut();
+    fill.start = fill.start.max(start);
+    fill.end = fill.end.min(end);
+    fill.gaps.retain(|g| {
+        let g = g.borrow();
+        g.start < end && g.end > start
+    });
+    for gap in &fill.gaps {
+        clip_gap(gap, start, end);
+    }
+}
+
 fn process_gap(
     gap: &Rc<RefCell<Gap>>,
     chains_map: &HashMap<u64, Chain>,
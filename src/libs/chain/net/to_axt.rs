@@ -11,7 +11,7 @@ use crate::libs::io::SequenceReader;
 use crate::libs::nt;
 use anyhow::anyhow;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::io::Write;
 use std::rc::Rc;
 
@@ -47,6 +47,38 @@ pub fn net_to_axt<S: SequenceReader, W: Write>(
     Ok(counter)
 }
 
+/// Collect the target and query names of the chains used by the fills of
+/// `nets`, so callers can check their sequence sources before any output.
+pub fn referenced_sequences(
+    nets: &[Chrom],
+    chains: &HashMap<u64, Chain>,
+) -> (BTreeSet<String>, BTreeSet<String>) {
+    let mut t_names = BTreeSet::new();
+    let mut q_names = BTreeSet::new();
+    for net in nets {
+        r_referenced(&net.root, chains, &mut t_names, &mut q_names);
+    }
+    (t_names, q_names)
+}
+
+fn r_referenced(
+    gap: &Rc<RefCell<Gap>>,
+    chains: &HashMap<u64, Chain>,
+    t_names: &mut BTreeSet<String>,
+    q_names: &mut BTreeSet<String>,
+) {
+    for fill in &gap.borrow().fills {
+        let f = fill.borrow();
+        if let Some(chain) = chains.get(&f.chain_id) {
+            t_names.insert(chain.header.t_name.clone());
+            q_names.insert(chain.header.q_name.clone());
+        }
+        for child in &f.gaps {
+            r_referenced(child, chains, t_names, q_names);
+        }
+    }
+}
+
 fn r_convert<S: SequenceReader, W: Write>(
     gap: &Rc<RefCell<Gap>>,
     chains: &HashMap<u64, Chain>,
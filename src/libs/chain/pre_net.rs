@@ -1,6 +1,6 @@
 //! chainPreNet: filter chains that cannot be netted, marking used target/query ranges.
 
-use super::record::ChainReader;
+use super::record::{Block, ChainHeader, ChainReader};
 use crate::libs::ds::BitMap;
 use anyhow::{bail, Result};
 use std::collections::HashMap;
@@ -18,11 +18,27 @@ pub struct PreNetOptions {
     pub pad: u64,
     pub incl_hap: bool,
     pub dots: Option<usize>,
+    /// Chains scoring below this are dropped.
+    pub min_score: f64,
+    /// Chains whose aligned fraction of the larger span is below this are dropped.
+    pub min_coverage: f64,
+}
+
+/// Fraction of the larger of the target/query spans covered by aligned blocks.
+fn aligned_coverage(header: &ChainHeader, blocks: &[Block]) -> f64 {
+    let span = (header.t_end - header.t_start).max(header.q_end - header.q_start);
+    if span == 0 {
+        return 0.0;
+    }
+    let aligned: u64 = blocks.iter().map(|b| b.t_end - b.t_start).sum();
+    aligned as f64 / span as f64
 }
 
 /// Run chainPreNet: filter chains, mark used ranges in target/query bitmaps.
 ///
-/// Reads chains from `reader`, writes passing chains to `writer`. `t_hash` and
+/// Chains below `min_score` or `min_coverage` are dropped first; a chain whose
+/// blocks all fall in ranges already used by higher-scoring chains is contained
+/// and dropped too. Reads chains from `reader`, writes passing chains to `writer`. `t_hash` and
 /// `q_hash` are mutated in place to track used ranges.
 pub fn pre_net<R: BufRead, W: Write>(
     reader: R,
@@ -57,6 +73,10 @@ pub fn pre_net<R: BufRead, W: Write>(
             continue;
         }
 
+        if chain.header.score < opts.min_score {
+            continue;
+        }
+
         let t_chrom = t_hash.get_mut(&chain.header.t_name).ok_or_else(|| {
             anyhow::anyhow!("Target sequence {} not found in sizes", chain.header.t_name)
         })?;
@@ -65,6 +85,10 @@ pub fn pre_net<R: BufRead, W: Write>(
         })?;
 
         let blocks = chain.to_blocks();
+        if opts.min_coverage > 0.0 && aligned_coverage(&chain.header, &blocks) < opts.min_coverage {
+            continue;
+        }
+
         let mut any_open = false;
         for b in &blocks {
             if !q_chrom.is_fully_set(b.q_start, b.q_end - b.q_start) {
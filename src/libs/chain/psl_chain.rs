@@ -92,6 +92,50 @@ pub fn group_psl_blocks<R: BufRead, S: SequenceReader>(
     Ok(groups)
 }
 
+/// Score a single PSL record as a chain using sequence data.
+///
+/// Each block is scored with the substitution matrix in `ctx`, and the gaps
+/// between consecutive blocks are charged with `gap_calc`, as axtChain does.
+pub fn psl_matrix_score<S: SequenceReader>(
+    psl: &Psl,
+    ctx: &mut ScoreContext<S>,
+    gap_calc: &GapCalc,
+) -> anyhow::Result<f64> {
+    let q_strand = psl.strand.chars().next().unwrap_or('+');
+
+    let mut score = 0.0;
+    let mut prev: Option<ChainableBlock> = None;
+    for i in 0..psl.block_count as usize {
+        let size = psl.block_sizes[i] as u64;
+        let t_start = psl.t_starts[i] as u64;
+        let q_start = psl.q_starts[i] as u64;
+        let block = ChainableBlock {
+            t_start,
+            t_end: t_start + size,
+            q_start,
+            q_end: q_start + size,
+            score: 0.0,
+        };
+
+        score += calc_block_score(
+            &block,
+            ctx,
+            &psl.q_name,
+            &psl.t_name,
+            psl.q_size as u64,
+            q_strand,
+        )?;
+        if let Some(p) = &prev {
+            let dt = block.t_start.saturating_sub(p.t_end) as i32;
+            let dq = block.q_start.saturating_sub(p.q_end) as i32;
+            score -= gap_calc.calc(dq, dt) as f64;
+        }
+        prev = Some(block);
+    }
+
+    Ok(score)
+}
+
 /// Chain PSL alignments and write chains filtered by `min_score`.
 ///
 /// Reads PSL records, groups by (target, query, strand), chains each group
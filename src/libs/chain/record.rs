@@ -31,7 +31,7 @@ pub struct ChainHeader {
 }
 
 /// Data for a single block in a chain (size, and gap to next block).
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct ChainData {
     /// Size of the alignment block.
     pub size: u64,
@@ -130,6 +130,51 @@ impl Chain {
         data
     }
 
+    /// Build a chain from a header and a contiguous block list.
+    ///
+    /// Unlike [`Chain::from_blocks`], the blocks are validated: each must be
+    /// ungapped (equal target and query sizes) and start at or after the end of
+    /// the previous block in both coordinates. The header range is updated to
+    /// span the blocks.
+    pub fn try_from_blocks(mut header: ChainHeader, blocks: &[Block]) -> anyhow::Result<Chain> {
+        if blocks.is_empty() {
+            anyhow::bail!("cannot build chain {} from an empty block list", header.id);
+        }
+        for (i, b) in blocks.iter().enumerate() {
+            if b.t_end < b.t_start
+                || b.q_end < b.q_start
+                || b.t_end - b.t_start != b.q_end - b.q_start
+            {
+                anyhow::bail!(
+                    "chain {} block {}: target range {}-{} does not match query range {}-{}",
+                    header.id,
+                    i,
+                    b.t_start,
+                    b.t_end,
+                    b.q_start,
+                    b.q_end
+                );
+            }
+            if i > 0 {
+                let prev = &blocks[i - 1];
+                if b.t_start < prev.t_end || b.q_start < prev.q_end {
+                    anyhow::bail!(
+                        "chain {} block {}: blocks are not monotonic ({}/{} starts before {}/{})",
+                        header.id,
+                        i,
+                        b.t_start,
+                        b.q_start,
+                        prev.t_end,
+                        prev.q_end
+                    );
+                }
+            }
+        }
+
+        let data = Chain::from_blocks(&mut header, blocks);
+        Ok(Chain { header, data })
+    }
+
     /// Write the chain in UCSC Chain format.
     ///
     /// Header fields are space-separated; gap data lines are tab-separated
@@ -501,6 +546,30 @@ malformed
         assert_eq!(data[0].dq, 0);
     }
 
+    #[test]
+    fn test_try_from_blocks_round_trip() {
+        let input = "\
+chain 4900 chrT 1000 + 100 190 chrQ 1000 - 200 290 1
+16 0 4
+60 4 0
+10
+";
+        let chain = read_chains(input.as_bytes()).unwrap().remove(0);
+
+        let rebuilt = Chain::try_from_blocks(chain.header.clone(), &chain.to_blocks()).unwrap();
+        assert_eq!(rebuilt.data, chain.data);
+        assert_eq!(rebuilt.header.t_start, chain.header.t_start);
+        assert_eq!(rebuilt.header.t_end, chain.header.t_end);
+        assert_eq!(rebuilt.header.q_start, chain.header.q_start);
+        assert_eq!(rebuilt.header.q_end, chain.header.q_end);
+
+        // Out-of-order blocks are rejected
+        let mut blocks = chain.to_blocks();
+        blocks.swap(0, 1);
+        let err = Chain::try_from_blocks(chain.header.clone(), &blocks).unwrap_err();
+        assert!(err.to_string().contains("not monotonic"));
+    }
+
     #[test]
     fn test_subset_reverse_strand() {
         let chain = Chain {
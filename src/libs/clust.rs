@@ -0,0 +1,123 @@
+//! Cluster assignments and quality metrics used by `pgr clust`.
+
+/// Read a cluster file: one cluster per line, members separated by whitespace.
+///
+/// Empty lines are skipped.
+pub fn read_clusters(path: &str) -> anyhow::Result<Vec<Vec<String>>> {
+    Ok(crate::libs::io::read_lines(path)?
+        .iter()
+        .map(|line| {
+            line.split_whitespace()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+        })
+        .filter(|members| !members.is_empty())
+        .collect())
+}
+
+/// Map cluster members to their row indices in a distance matrix.
+///
+/// A member absent from `names`, or listed in more than one cluster, is an
+/// error. Taxa of the matrix that belong to no cluster are ignored.
+pub fn cluster_indices(
+    names: &[String],
+    clusters: &[Vec<String>],
+) -> anyhow::Result<Vec<Vec<usize>>> {
+    let index: std::collections::HashMap<&str, usize> = names
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.as_str(), i))
+        .collect();
+
+    let mut seen = vec![false; names.len()];
+    let mut result = Vec::with_capacity(clusters.len());
+    for members in clusters {
+        let mut idx = Vec::with_capacity(members.len());
+        for name in members {
+            let i = *index
+                .get(name.as_str())
+                .ok_or_else(|| anyhow::anyhow!("taxon not found in matrix: {}", name))?;
+            anyhow::ensure!(!seen[i], "taxon in more than one cluster: {}", name);
+            seen[i] = true;
+            idx.push(i);
+        }
+        result.push(idx);
+    }
+    Ok(result)
+}
+
+/// Silhouette coefficient of every member, grouped as in `clusters`.
+///
+/// For a member `i`, `a` is its mean distance to the rest of its cluster and
+/// `b` the smallest mean distance to another cluster; the score is
+/// `(b - a) / max(a, b)`. Members of singleton clusters score 0, as do all
+/// members when there is only one cluster.
+///
+/// ```
+/// let m = vec![
+///     vec![0.0, 1.0, 9.0],
+///     vec![1.0, 0.0, 9.0],
+///     vec![9.0, 9.0, 0.0],
+/// ];
+/// let s = pgr::libs::clust::silhouette(&m, &[vec![0, 1], vec![2]]);
+/// assert!((s[0][0] - 8.0 / 9.0).abs() < 1e-9);
+/// assert_eq!(s[1][0], 0.0);
+/// ```
+pub fn silhouette(matrix: &[Vec<f64>], clusters: &[Vec<usize>]) -> Vec<Vec<f64>> {
+    let mean_dist = |i: usize, members: &[usize]| -> f64 {
+        let others: Vec<f64> = members
+            .iter()
+            .filter(|&&j| j != i)
+            .map(|&j| matrix[i][j])
+            .collect();
+        others.iter().sum::<f64>() / others.len() as f64
+    };
+
+    clusters
+        .iter()
+        .enumerate()
+        .map(|(c, members)| {
+            members
+                .iter()
+                .map(|&i| {
+                    if members.len() < 2 {
+                        return 0.0;
+                    }
+                    let a = mean_dist(i, members);
+                    let b = clusters
+                        .iter()
+                        .enumerate()
+                        .filter(|&(o, _)| o != c)
+                        .map(|(_, other)| mean_dist(i, other))
+                        .fold(f64::INFINITY, f64::min);
+                    let scale = a.max(b);
+                    if !b.is_finite() || scale == 0.0 {
+                        0.0
+                    } else {
+                        (b - a) / scale
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_silhouette_singleton_and_single_cluster() {
+        let m = vec![
+            vec![0.0, 2.0, 4.0],
+            vec![2.0, 0.0, 6.0],
+            vec![4.0, 6.0, 0.0],
+        ];
+        let s = silhouette(&m, &[vec![0, 1], vec![2]]);
+        assert_eq!(s[0], vec![0.5, 2.0 / 3.0]);
+        assert_eq!(s[1], vec![0.0]);
+
+        let s = silhouette(&m, &[vec![0, 1, 2]]);
+        assert!(s[0].iter().all(|&v| v == 0.0));
+    }
+}
@@ -11,30 +11,77 @@ use crate::libs::chain::GapCalc;
 use crate::libs::fmt::fas::{FasBlock, FasEntry};
 use std::collections::BTreeMap;
 
-#[allow(clippy::type_complexity)]
 pub(super) fn banded_align_refs(
     blocks: [&FasBlock; 2],
     ref_name: &str,
     cfg: &FasMultizConfig,
-) -> anyhow::Result<Option<(Vec<Option<usize>>, Vec<Option<usize>>)>> {
+) -> anyhow::Result<Option<ColumnMaps>> {
     let submat = match &cfg.score_matrix {
         Some(name) => SubMatrix::from_name(name)?,
         None => SubMatrix::hoxd55(),
     };
-    Ok(banded_align_refs_inner(blocks, ref_name, cfg, &submat))
+
+    let (Some(ref_a), Some(ref_b)) = (
+        find_ref_entry(blocks[0], ref_name),
+        find_ref_entry(blocks[1], ref_name),
+    ) else {
+        return Ok(None);
+    };
+    let n = ref_a.seq().len();
+    let m = ref_b.seq().len();
+
+    // A band as wide as the longer row covers the whole matrix
+    let full = n.max(m);
+    let cap = cfg.max_band.max(cfg.radius).min(full);
+
+    let initial = cfg.radius.max(n.abs_diff(m));
+    let mut band = initial;
+    loop {
+        let (maps, hit_edge) = banded_align_refs_inner(blocks, ref_name, cfg, &submat, band);
+        if hit_edge && band < cap {
+            band = (band * 2).clamp(band + 1, cap);
+            continue;
+        }
+
+        if band > initial {
+            log::warn!(
+                "{}: banded alignment widened from {} to {}{}",
+                ref_a.range(),
+                initial,
+                band,
+                if hit_edge {
+                    " (band limit reached)"
+                } else {
+                    ""
+                }
+            );
+        }
+        return Ok(maps);
+    }
 }
 
-#[allow(clippy::type_complexity)]
+/// Column maps of the two reference rows; `None` marks a gap column.
+type ColumnMaps = (Vec<Option<usize>>, Vec<Option<usize>>);
+
+/// One banded DP pass with the given band radius.
+///
+/// The returned flag is set when the traceback failed or ran along a band
+/// edge, i.e. a wider band may find a better path.
 fn banded_align_refs_inner(
     blocks: [&FasBlock; 2],
     ref_name: &str,
     cfg: &FasMultizConfig,
     submat: &SubMatrix,
-) -> Option<(Vec<Option<usize>>, Vec<Option<usize>>)> {
+    band: usize,
+) -> (Option<ColumnMaps>, bool) {
     use std::cmp::min;
 
-    let ref_a = find_ref_entry(blocks[0], ref_name)?;
-    let ref_b = find_ref_entry(blocks[1], ref_name)?;
+    let (Some(ref_a), Some(ref_b)) = (
+        find_ref_entry(blocks[0], ref_name),
+        find_ref_entry(blocks[1], ref_name),
+    ) else {
+        return (None, false);
+    };
 
     let sa = ref_a.seq();
     let sb = ref_b.seq();
@@ -43,13 +90,9 @@ fn banded_align_refs_inner(
     let m = sb.len();
 
     if n == 0 || m == 0 {
-        return None;
+        return (None, false);
     }
 
-    let band = cfg
-        .radius
-        .max(((n as isize - m as isize).unsigned_abs()) as usize);
-
     let width = 2 * band + 1;
     let mut score = vec![i32::MIN; (n + 1) * width];
     let mut gap_i = vec![i32::MIN; (n + 1) * width];
@@ -73,7 +116,7 @@ fn banded_align_refs_inner(
         gap_j[k] = i32::MIN;
         trace[k] = 0;
     } else {
-        return None;
+        return (None, false);
     }
 
     let (gap_open_pen, gap_extend_pen) =
@@ -192,7 +235,13 @@ fn banded_align_refs_inner(
     let mut i = n;
     let mut j = m;
 
-    idx(i, j)?;
+    if idx(i, j).is_none() {
+        return (None, true);
+    }
+
+    // Band edges only constrain the path when the band is narrower than the matrix
+    let constrained = band < n.max(m);
+    let mut hit_edge = false;
 
     let mut map_a = Vec::new();
     let mut map_b = Vec::new();
@@ -202,6 +251,9 @@ fn banded_align_refs_inner(
             Some(v) => v,
             None => break,
         };
+        if constrained && (i, j) != (n, m) && (i == j + band || j == i + band) {
+            hit_edge = true;
+        }
         let bt = trace[k];
         if bt == 1 {
             if i == 0 || j == 0 {
@@ -234,9 +286,19 @@ fn banded_align_refs_inner(
         }
     }
 
+    // The traceback stopped before reaching the origin
+    if i > 0 || j > 0 {
+        hit_edge = true;
+    }
+
     map_a.reverse();
     map_b.reverse();
 
+    (trim_unpaired_ends(map_a, map_b), hit_edge)
+}
+
+/// Drop leading and trailing columns where either side is a gap.
+fn trim_unpaired_ends(map_a: Vec<Option<usize>>, map_b: Vec<Option<usize>>) -> Option<ColumnMaps> {
     if map_a.len() != map_b.len() || map_a.is_empty() {
         return None;
     }
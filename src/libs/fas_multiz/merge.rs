@@ -9,12 +9,20 @@ fn entry_seq_equal(a: &FasEntry, b: &FasEntry) -> bool {
     a.seq() == b.seq()
 }
 
-fn ungapped_equal(a: &FasEntry, b: &FasEntry) -> bool {
+/// Fraction of identical bases between the ungapped forms of two entries.
+///
+/// Returns 0.0 when the ungapped lengths differ, as only substitutions can be
+/// reconciled by the reference DP.
+fn ungapped_identity(a: &FasEntry, b: &FasEntry) -> f64 {
     let sa = a.seq();
     let sb = b.seq();
     let ua: Vec<u8> = sa.iter().copied().filter(|c| *c != b'-').collect();
     let ub: Vec<u8> = sb.iter().copied().filter(|c| *c != b'-').collect();
-    ua == ub
+    if ua.len() != ub.len() || ua.is_empty() {
+        return 0.0;
+    }
+    let same = ua.iter().zip(&ub).filter(|(x, y)| x == y).count();
+    same as f64 / ua.len() as f64
 }
 
 fn merge_two_blocks_with_dp(
@@ -31,7 +39,7 @@ fn merge_two_blocks_with_dp(
         None => return Ok(None),
     };
 
-    if !ungapped_equal(ref_a, ref_b) {
+    if ungapped_identity(ref_a, ref_b) < cfg.min_identity {
         return Ok(None);
     }
 
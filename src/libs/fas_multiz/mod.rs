@@ -9,7 +9,8 @@ mod windows;
 
 pub use merge::merge_window;
 
-use crate::libs::fmt::fas::{FasBlock, FasEntry};
+use crate::libs::fmt::fas::{FasBlock, FasEntry, FasReader};
+use rayon::prelude::*;
 use std::path::Path;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -29,7 +30,11 @@ pub enum FasMultizGapModel {
 pub struct FasMultizConfig {
     pub ref_name: String,
     pub radius: usize,
+    /// Upper limit when the band is widened after the traceback hits its edge.
+    pub max_band: usize,
     pub min_width: usize,
+    /// Minimum identity between the ungapped references of two blocks to merge them.
+    pub min_identity: f64,
     pub mode: FasMultizMode,
     pub match_score: i32,
     pub mismatch_score: i32,
@@ -38,6 +43,10 @@ pub struct FasMultizConfig {
     pub gap_open: Option<i32>,
     pub gap_extend: Option<i32>,
     pub score_matrix: Option<String>,
+    /// Number of threads used to merge windows; 1 merges sequentially.
+    pub parallel: usize,
+    /// Re-align the rows of each merged block with POA before returning it.
+    pub polish: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -77,21 +86,8 @@ pub fn merge_fas_files(
         let infile_str = infile.as_ref().to_str().ok_or_else(|| {
             anyhow::anyhow!("path is not valid UTF-8: {}", infile.as_ref().display())
         })?;
-        let mut reader = crate::reader(infile_str)?;
-        let mut blocks = Vec::new();
-
-        loop {
-            match crate::libs::fmt::fas::next_fas_block(&mut reader) {
-                Ok(block) => blocks.push(block),
-                Err(e) => {
-                    if e.kind() == std::io::ErrorKind::UnexpectedEof {
-                        break;
-                    } else {
-                        return Err(e.into());
-                    }
-                }
-            }
-        }
+        let reader = crate::reader(infile_str)?;
+        let blocks = FasReader::new(reader).collect::<anyhow::Result<Vec<_>>>()?;
 
         blocks_per_input.push(blocks);
     }
@@ -100,14 +96,7 @@ pub fn merge_fas_files(
         return Ok(Vec::new());
     }
 
-    let mut merged_blocks = Vec::new();
-    for window in windows {
-        if let Some(block) = merge_window(ref_name, window, &blocks_per_input, cfg)? {
-            merged_blocks.push(block);
-        }
-    }
-
-    Ok(merged_blocks)
+    merge_windows(ref_name, windows, &blocks_per_input, cfg)
 }
 
 pub fn merge_fas_files_auto_windows(
@@ -121,21 +110,8 @@ pub fn merge_fas_files_auto_windows(
         let infile_str = infile.as_ref().to_str().ok_or_else(|| {
             anyhow::anyhow!("path is not valid UTF-8: {}", infile.as_ref().display())
         })?;
-        let mut reader = crate::reader(infile_str)?;
-        let mut blocks = Vec::new();
-
-        loop {
-            match crate::libs::fmt::fas::next_fas_block(&mut reader) {
-                Ok(block) => blocks.push(block),
-                Err(e) => {
-                    if e.kind() == std::io::ErrorKind::UnexpectedEof {
-                        break;
-                    } else {
-                        return Err(e.into());
-                    }
-                }
-            }
-        }
+        let reader = crate::reader(infile_str)?;
+        let blocks = FasReader::new(reader).collect::<anyhow::Result<Vec<_>>>()?;
 
         blocks_per_input.push(blocks);
     }
@@ -145,12 +121,79 @@ pub fn merge_fas_files_auto_windows(
         return Ok(Vec::new());
     }
 
-    let mut merged_blocks = Vec::new();
-    for window in &windows {
-        if let Some(block) = merge_window(ref_name, window, &blocks_per_input, cfg)? {
-            merged_blocks.push(block);
-        }
+    merge_windows(ref_name, &windows, &blocks_per_input, cfg)
+}
+
+/// Merge every window, in parallel when `cfg.parallel > 1`.
+///
+/// Windows are independent; the merged blocks are returned in window order
+/// either way, so the output does not depend on the thread count.
+fn merge_windows(
+    ref_name: &str,
+    windows: &[Window],
+    blocks_per_input: &[Vec<FasBlock>],
+    cfg: &FasMultizConfig,
+) -> anyhow::Result<Vec<FasBlock>> {
+    let merged: Vec<Option<FasBlock>> = if cfg.parallel > 1 {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(cfg.parallel)
+            .build()?;
+        pool.install(|| {
+            windows
+                .par_iter()
+                .map(|window| merge_window(ref_name, window, blocks_per_input, cfg))
+                .collect::<anyhow::Result<_>>()
+        })?
+    } else {
+        windows
+            .iter()
+            .map(|window| merge_window(ref_name, window, blocks_per_input, cfg))
+            .collect::<anyhow::Result<_>>()?
+    };
+
+    let mut blocks: Vec<FasBlock> = merged.into_iter().flatten().collect();
+    if cfg.polish {
+        blocks.iter_mut().for_each(polish_block);
+    }
+
+    Ok(blocks)
+}
+
+/// Re-derive the MSA of a merged block with the builtin POA aligner.
+///
+/// Progressive merging can leave columns that are gaps in every row, or
+/// gaps placed differently in rows that came from different inputs. The
+/// ungapped sequences are aligned from scratch; rows without any bases
+/// (species absent from the window in union mode) stay all-gap.
+fn polish_block(block: &mut FasBlock) {
+    let present: Vec<usize> = block
+        .entries
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.seq().iter().any(|&b| b != b'-'))
+        .map(|(i, _)| i)
+        .collect();
+    if present.is_empty() {
+        return;
     }
 
-    Ok(merged_blocks)
+    let seqs: Vec<String> = present
+        .iter()
+        .map(|&i| String::from_utf8_lossy(block.entries[i].seq()).into_owned())
+        .collect();
+    let aligned = crate::libs::alignment::align_seqs_builtin(
+        &seqs,
+        &crate::libs::poa::AlignmentParams::default(),
+    );
+    let width = aligned[0].len();
+
+    let mut rows = aligned.into_iter();
+    for (i, entry) in block.entries.iter_mut().enumerate() {
+        let seq = if present.contains(&i) {
+            rows.next().unwrap().into_bytes()
+        } else {
+            vec![b'-'; width]
+        };
+        *entry = FasEntry::from(entry.range(), &seq);
+    }
 }
@@ -28,7 +28,9 @@ fn default_config(mode: FasMultizMode) -> FasMultizConfig {
     FasMultizConfig {
         ref_name: "ref".to_string(),
         radius: 5,
+        max_band: 5,
         min_width: 1,
+        min_identity: 1.0,
         mode,
         match_score: 2,
         mismatch_score: -1,
@@ -37,6 +39,8 @@ fn default_config(mode: FasMultizMode) -> FasMultizConfig {
         gap_open: None,
         gap_extend: None,
         score_matrix: None,
+        parallel: 1,
+        polish: false,
     }
 }
 
@@ -408,3 +412,103 @@ fn merge_window_multi_input_dp_progressive() {
         ]
     );
 }
+
+#[test]
+fn banded_align_refs_widens_band() {
+    // The same reference bases, shifted by 12 columns between the two blocks
+    let (a_entry, a_name, a_header) = make_entry("ref", 1, 12, "------------GATCCTAGGCAT");
+    let (b_entry, b_name, b_header) = make_entry("ref", 1, 12, "GATCCTAGGCAT------------");
+    let block_a = make_block(vec![(a_entry, a_name, a_header)]);
+    let block_b = make_block(vec![(b_entry, b_name, b_header)]);
+
+    let expected = (
+        (12..24).map(Some).collect::<Vec<_>>(),
+        (0..12).map(Some).collect::<Vec<_>>(),
+    );
+
+    // Radius 5 without widening can't reach the shifted diagonal
+    let mut cfg = default_config(FasMultizMode::Core);
+    let narrow = super::banded_align::banded_align_refs([&block_a, &block_b], "ref", &cfg).unwrap();
+    assert_ne!(narrow, Some(expected.clone()));
+
+    cfg.max_band = 24;
+    let wide = super::banded_align::banded_align_refs([&block_a, &block_b], "ref", &cfg).unwrap();
+    assert_eq!(wide, Some(expected));
+}
+
+#[test]
+fn merge_window_min_identity_allows_snp() {
+    let (ref_entry1, ref_name1, ref_header1) = make_entry("ref", 1, 10, "ACGTACGTAC");
+    let (a_entry, a_name, a_header) = make_entry("A", 1, 10, "ACGTACGTAC");
+    let block1 = make_block(vec![
+        (ref_entry1, ref_name1, ref_header1),
+        (a_entry, a_name, a_header),
+    ]);
+
+    // One substitution in the second reference
+    let (ref_entry2, ref_name2, ref_header2) = make_entry("ref", 1, 10, "ACGTACCTAC");
+    let (b_entry, b_name, b_header) = make_entry("B", 1, 10, "ACGTACCTAC");
+    let block2 = make_block(vec![
+        (ref_entry2, ref_name2, ref_header2),
+        (b_entry, b_name, b_header),
+    ]);
+
+    let blocks_per_input = vec![vec![block1], vec![block2]];
+    let window = Window {
+        chr: "ref".to_string(),
+        start: 1,
+        end: 10,
+    };
+
+    let mut cfg = default_config(FasMultizMode::Union);
+    let merged = merge_window("ref", &window, &blocks_per_input, &cfg).unwrap();
+    assert!(merged.is_none());
+
+    cfg.min_identity = 0.9;
+    let merged = merge_window("ref", &window, &blocks_per_input, &cfg)
+        .unwrap()
+        .unwrap();
+    assert_eq!(merged.names, vec!["ref", "A", "B"]);
+    assert_eq!(merged.entries[0].seq(), b"ACGTACGTAC");
+    assert_eq!(merged.entries[2].seq(), b"ACGTACCTAC");
+}
+
+#[test]
+fn polish_block_removes_gap_only_columns() {
+    // A noisy merge: columns 2 and 5 are gaps in every row, and the single
+    // deletion in A is placed off the other rows' gap.
+    let (ref_entry, ref_name, ref_header) = make_entry("ref", 1, 8, "AC-GT-ACGT");
+    let (a_entry, a_name, a_header) = make_entry("A", 1, 7, "A--GT-ACGT");
+    let (b_entry, b_name, b_header) = make_entry("B", 1, 8, "AC-GT-ACGT");
+    let (c_entry, c_name, c_header) = make_entry("C", 1, 1, "----------");
+    let mut block = make_block(vec![
+        (ref_entry, ref_name, ref_header),
+        (a_entry, a_name, a_header),
+        (b_entry, b_name, b_header),
+        (c_entry, c_name, c_header),
+    ]);
+
+    let gap_only_columns = |block: &FasBlock| {
+        let width = block.entries[0].seq().len();
+        (0..width)
+            .filter(|&col| block.entries.iter().all(|e| e.seq()[col] == b'-'))
+            .count()
+    };
+    assert_eq!(gap_only_columns(&block), 2);
+
+    polish_block(&mut block);
+    assert_eq!(gap_only_columns(&block), 0);
+
+    let width = block.entries[0].seq().len();
+    assert!(block.entries.iter().all(|e| e.seq().len() == width));
+    let ungapped: Vec<Vec<u8>> = block
+        .entries
+        .iter()
+        .map(|e| e.seq().iter().copied().filter(|&b| b != b'-').collect())
+        .collect();
+    assert_eq!(ungapped[0], b"ACGTACGT");
+    assert_eq!(ungapped[1], b"AGTACGT");
+    assert_eq!(ungapped[2], b"ACGTACGT");
+    assert!(ungapped[3].is_empty());
+    assert_eq!(block.names, vec!["ref", "A", "B", "C"]);
+}
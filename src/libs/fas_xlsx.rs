@@ -1,11 +1,16 @@
 //! Export FAS block variations (substitutions/indels) to an Excel workbook.
+//!
+//! The first worksheet draws the variations of each block; the second,
+//! `summary`, lists per-block conservation and identity to the reference.
 
 use anyhow::anyhow;
 use rust_xlsxwriter::{Color, Format, FormatAlign, Workbook, Worksheet};
 use std::cmp::max;
 use std::collections::BTreeMap;
 
-use crate::libs::alignment::{collect_indels, collect_subs, Indel, Substitution};
+use crate::libs::alignment::{
+    collect_indels, collect_subs, conservation, pair_d, Indel, Substitution,
+};
 use crate::libs::fmt::fas::{iter_fas_blocks, FasBlock};
 
 /// Export variations from FAS blocks to an Excel xlsx file.
@@ -20,9 +25,11 @@ pub fn export_to_xlsx(
     no_complex: bool,
     min_freq: Option<f64>,
     max_freq: Option<f64>,
+    no_color: bool,
 ) -> anyhow::Result<()> {
     let mut workbook = Workbook::new();
-    let worksheet = workbook.add_worksheet();
+    let mut worksheet = Worksheet::new();
+    let mut summary = Summary::default();
 
     let format_of: BTreeMap<String, Format> = create_formats()?;
 
@@ -35,6 +42,7 @@ pub fn export_to_xlsx(
         color_loop: 15,
         seq_count: 0,
         is_outgroup,
+        no_color,
     };
 
     for infile in infiles {
@@ -46,6 +54,7 @@ pub fn export_to_xlsx(
             for entry in &block.entries {
                 seqs.push(entry.seq());
             }
+            summary.add_block(&block);
 
             let vars = get_vars(
                 &seqs,
@@ -61,7 +70,7 @@ pub fn export_to_xlsx(
             opt.sec_height = opt.seq_count + 2;
             opt.col_cursor = 1;
 
-            paint_name(worksheet, &format_of, &mut opt, &block)?;
+            paint_name(&mut worksheet, &format_of, &mut opt, &block)?;
 
             if opt.is_outgroup {
                 opt.seq_count -= 1;
@@ -70,10 +79,10 @@ pub fn export_to_xlsx(
             for (_, var) in vars {
                 match var {
                     Variation::Substitution(sub) => {
-                        paint_sub(worksheet, &format_of, &mut opt, &sub)?
+                        paint_sub(&mut worksheet, &format_of, &mut opt, &sub)?
                     }
                     Variation::Indel(indel) => {
-                        paint_indel(worksheet, &format_of, &mut opt, &indel)?
+                        paint_indel(&mut worksheet, &format_of, &mut opt, &indel)?
                     }
                 }
 
@@ -92,11 +101,105 @@ pub fn export_to_xlsx(
     for i in 1..=(opt.wrap + 3) {
         worksheet.set_column_width(i, 1.6)?;
     }
+    workbook.push_worksheet(worksheet);
+
+    let summary_sheet = summary.to_worksheet(&format_of)?;
+    workbook.push_worksheet(summary_sheet);
 
     workbook.save(outfile)?;
     Ok(())
 }
 
+/// Per-block rows of the `summary` worksheet.
+#[derive(Debug, Default)]
+struct Summary {
+    /// Names of the non-reference entries of all blocks, in order of first
+    /// appearance; one identity column each.
+    names: Vec<String>,
+    rows: Vec<SummaryRow>,
+}
+
+#[derive(Debug)]
+struct SummaryRow {
+    target: String,
+    length: usize,
+    conservation: f64,
+    /// Identity to the reference by column of `Summary::names`.
+    identities: Vec<(usize, f64)>,
+}
+
+impl Summary {
+    fn add_block(&mut self, block: &FasBlock) {
+        if block.entries.is_empty() {
+            return;
+        }
+        let scores = conservation(block);
+        let mean = if scores.is_empty() {
+            0.0
+        } else {
+            scores.iter().sum::<f64>() / scores.len() as f64
+        };
+
+        // Identity over comparable columns, keyed by species name; left empty
+        // when nothing is comparable
+        let ref_seq = block.entries[0].seq();
+        let mut identities = vec![];
+        for (entry, name) in block.entries.iter().zip(&block.names).skip(1) {
+            let col = match self.names.iter().position(|n| n == name) {
+                Some(col) => col,
+                None => {
+                    self.names.push(name.clone());
+                    self.names.len() - 1
+                }
+            };
+            if let Ok(d) = pair_d(ref_seq, entry.seq()) {
+                identities.push((col, 1.0 - d as f64));
+            }
+        }
+
+        self.rows.push(SummaryRow {
+            target: block.entries[0].range().to_string(),
+            length: ref_seq.len(),
+            conservation: mean,
+            identities,
+        });
+    }
+
+    fn to_worksheet(&self, format_of: &BTreeMap<String, Format>) -> anyhow::Result<Worksheet> {
+        let name_format = format_of
+            .get("name")
+            .ok_or_else(|| anyhow!("missing 'name' format"))?;
+
+        let mut worksheet = Worksheet::new();
+        worksheet.set_name("summary")?;
+
+        let mut header = vec![
+            "target".to_string(),
+            "length".to_string(),
+            "conservation".to_string(),
+        ];
+        header.extend(self.names.iter().cloned());
+        for (col, field) in header.iter().enumerate() {
+            worksheet.write_with_format(0, col as u16, field, name_format)?;
+        }
+
+        for (i, row) in self.rows.iter().enumerate() {
+            let r = i as u32 + 1;
+            worksheet.write_with_format(r, 0, &row.target, name_format)?;
+            worksheet.write(r, 1, row.length as u32)?;
+            worksheet.write(r, 2, row.conservation)?;
+            for &(col, identity) in &row.identities {
+                worksheet.write(r, 3 + col as u16, identity)?;
+            }
+        }
+
+        let max_len = self.rows.iter().map(|r| r.target.len()).max().unwrap_or(6);
+        worksheet.set_column_width(0, max_len as f64)?;
+
+        Ok(worksheet)
+    }
+}
+
 #[derive(Debug)]
 enum Variation {
     Substitution(Substitution),
@@ -113,6 +216,7 @@ struct Opt {
     color_loop: u32,
     seq_count: u32,
     is_outgroup: bool,
+    no_color: bool,
 }
 
 fn paint_name(
@@ -156,7 +260,11 @@ fn paint_indel(
     let pos_format = format_of
         .get("pos")
         .ok_or_else(|| anyhow!("missing 'pos' format"))?;
-    let format = {
+    let format = if opt.no_color {
+        format_of
+            .get("plain")
+            .ok_or_else(|| anyhow!("missing 'plain' format"))?
+    } else {
         let bg_idx = if indel.occurred == "unknown" {
             "unknown".to_string()
         } else {
@@ -254,7 +362,9 @@ fn paint_sub(
             })?
         };
 
-        let base_color = if occurred == '1' {
+        let base_color = if opt.no_color {
+            "plain".to_string()
+        } else if occurred == '1' {
             let bg_idx = u32::from_str_radix(&sub.pattern, 2)? % opt.color_loop;
             format!("sub_{}_{}", base, bg_idx)
         } else {
@@ -267,7 +377,11 @@ fn paint_sub(
     }
 
     if opt.is_outgroup {
-        let base_color = format!("sub_{}_unknown", sub.obase);
+        let base_color = if opt.no_color {
+            "plain".to_string()
+        } else {
+            format!("sub_{}_unknown", sub.obase)
+        };
         let format = format_of
             .get(&base_color)
             .ok_or_else(|| anyhow!("missing format for outgroup substitution: {}", base_color))?;
@@ -369,6 +483,15 @@ fn create_formats() -> anyhow::Result<BTreeMap<String, Format>> {
             .set_rotation(90),
     );
 
+    format_of.insert(
+        "plain".to_string(),
+        Format::new()
+            .set_font_name("Courier New")
+            .set_font_size(10)
+            .set_align(FormatAlign::VerticalCenter)
+            .set_align(FormatAlign::Center),
+    );
+
     let bg_colors: Vec<u32> = vec![
         0xC0C0C0, 0xFFFF99, 0xCCFFCC, 0xCCFFFF, 0x99CCFF, 0xCC99FF, 0xFFCC99, 0x9999FF, 0x33CCCC,
         0xFFCC00, 0xFF99CC, 0xFF9900, 0xFFFFCC, 0xFF8080, 0xCCCCFF,
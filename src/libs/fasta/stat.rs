@@ -98,3 +98,80 @@ pub fn count_bases(seq: &[u8]) -> (usize, [usize; 5]) {
 
     (len, base_cnt)
 }
+
+/// Per-record base composition reported by `pgr fa count --comp`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Composition {
+    /// Sequence length, all characters included.
+    pub len: usize,
+    /// Count of A and T (or U) bases.
+    pub at: usize,
+    /// Count of G and C bases.
+    pub gc: usize,
+    /// Count of N bases.
+    pub n: usize,
+    /// Count of lowercase (soft-masked) characters.
+    pub lower: usize,
+    /// Count of IUPAC ambiguity codes and other characters.
+    pub other: usize,
+}
+
+impl Composition {
+    /// Tally the composition of a sequence; case is ignored except for `lower`.
+    pub fn from_seq(seq: &[u8]) -> Self {
+        let mut comp = Composition {
+            len: seq.len(),
+            ..Default::default()
+        };
+        for &el in seq {
+            if el.is_ascii_lowercase() {
+                comp.lower += 1;
+            }
+            match el.to_ascii_uppercase() {
+                b'A' | b'T' | b'U' => comp.at += 1,
+                b'G' | b'C' => comp.gc += 1,
+                b'N' => comp.n += 1,
+                _ => comp.other += 1,
+            }
+        }
+        comp
+    }
+
+    /// Accumulate another record's counts into this one.
+    pub fn add(&mut self, other: &Self) {
+        self.len += other.len;
+        self.at += other.at;
+        self.gc += other.gc;
+        self.n += other.n;
+        self.lower += other.lower;
+        self.other += other.other;
+    }
+
+    /// GC percentage over A/C/G/T bases; 0 when there are none.
+    pub fn gc_pct(&self) -> f64 {
+        let acgt = self.at + self.gc;
+        if acgt == 0 {
+            0.0
+        } else {
+            self.gc as f64 * 100.0 / acgt as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_composition() {
+        let comp = Composition::from_seq(b"ACGTacgtNNRY");
+        assert_eq!(comp.len, 12);
+        assert_eq!(comp.gc, 4);
+        assert_eq!(comp.n, 2);
+        assert_eq!(comp.lower, 4);
+        assert_eq!(comp.other, 2);
+        assert_eq!(comp.gc_pct(), 50.0);
+
+        assert_eq!(Composition::from_seq(b"NNN").gc_pct(), 0.0);
+    }
+}
@@ -332,8 +332,14 @@ pub enum AxtSortBy {
 pub fn sort_axts(axts: &mut [Axt], by: AxtSortBy, renumber: bool) {
     match by {
         AxtSortBy::Score => {
-            // Sort by score descending (higher is better).
-            axts.sort_by_key(|b| std::cmp::Reverse(b.score.unwrap_or(0)));
+            // Sort by score descending (higher is better); ties keep target order.
+            axts.sort_by(|a, b| {
+                b.score
+                    .unwrap_or(0)
+                    .cmp(&a.score.unwrap_or(0))
+                    .then(a.t_name.cmp(&b.t_name))
+                    .then(a.t_start.cmp(&b.t_start))
+            });
         }
         AxtSortBy::Query => {
             axts.sort_by(|a, b| a.q_name.cmp(&b.q_name).then(a.q_start.cmp(&b.q_start)));
@@ -375,6 +381,9 @@ pub fn axt_query_to_forward_coords(
 /// forward-strand coordinates (per `axtToPsl.c` convention) before invoking
 /// `Psl::from_align`. Records with invalid coordinates are skipped with a
 /// `log::warn!`.
+///
+/// A `-` strand record whose query is missing from `q_sizes` is an error,
+/// since its forward-strand coordinates depend on the query size.
 pub fn axt_to_psl<R: std::io::Read, W: Write>(
     reader: R,
     writer: &mut W,
@@ -386,9 +395,14 @@ pub fn axt_to_psl<R: std::io::Read, W: Write>(
     for result in reader {
         let axt = result?;
 
-        let q_size = *q_sizes
-            .get(&axt.q_name)
-            .ok_or_else(|| anyhow::anyhow!("Query size not found for {}", axt.q_name))?;
+        let q_size = match q_sizes.get(&axt.q_name) {
+            Some(&size) => size,
+            None if axt.q_strand == '-' => anyhow::bail!(
+                "Query size not found for {}: minus-strand records need it to compute query coordinates",
+                axt.q_name
+            ),
+            None => anyhow::bail!("Query size not found for {}", axt.q_name),
+        };
         let t_size = *t_sizes
             .get(&axt.t_name)
             .ok_or_else(|| anyhow::anyhow!("Target size not found for {}", axt.t_name))?;
@@ -41,6 +41,18 @@ pub fn writer_from_writer<W: std::io::Write>(writer: W) -> fasta::io::Writer<W>
         .build_from_writer(writer)
 }
 
+/// Read all records of a FASTA file as `(name, sequence)` pairs.
+pub fn load_records(infile: &str) -> anyhow::Result<Vec<(String, Vec<u8>)>> {
+    let mut fa_in = reader(infile)?;
+    let mut records = vec![];
+    for result in fa_in.records() {
+        let record = result?;
+        let name = String::from_utf8(record.name().into())?;
+        records.push((name, record.sequence()[..].to_vec()));
+    }
+    Ok(records)
+}
+
 /// Build a FASTA record from a name and a sequence byte slice.
 pub fn new_record(name: &str, seq: &[u8]) -> fasta::Record {
     let definition = fasta::record::Definition::new(name, None);
@@ -49,6 +49,86 @@ impl FasEntry {
             seq: seq.to_owned(),
         }
     }
+
+    /// Chromosome positions of all alignment columns, built in one pass.
+    ///
+    /// Minus-strand ranges count down from the range end. Build it once per
+    /// block and look up every column in it.
+    ///
+    /// ```ignore
+    /// # use intspan::Range;
+    /// # use pgr::libs::fmt::fas::FasEntry;
+    /// let entry = FasEntry::from(&Range::from("I", 101, 107), b"-AA--TTTGG");
+    /// let positions = entry.col_positions();
+    /// assert_eq!(positions.get(6), Some(103));
+    /// assert_eq!(positions.get(4), None);
+    /// ```
+    pub fn col_positions(&self) -> ColPositions {
+        let is_minus = self.range.strand() == "-";
+        let start = *self.range.start() as u64;
+        let end = *self.range.end() as u64;
+        let mut nth = 0;
+        let positions = self
+            .seq
+            .iter()
+            .map(|&b| {
+                if b == b'-' {
+                    return None;
+                }
+                nth += 1;
+                Some(if is_minus {
+                    end + 1 - nth
+                } else {
+                    start + nth - 1
+                })
+            })
+            .collect();
+        ColPositions(positions)
+    }
+
+    /// Alignment column (1-based) holding chromosome position `pos` (1-based).
+    ///
+    /// The inverse of [`FasEntry::col_positions`]. Returns `None` when `pos` is
+    /// outside the entry's range.
+    ///
+    /// ```ignore
+    /// # use intspan::Range;
+    /// # use pgr::libs::fmt::fas::FasEntry;
+    /// let entry = FasEntry::from(&Range::from("I", 101, 107), b"-AA--TTTGG");
+    /// assert_eq!(entry.pos_to_col(103), Some(6));
+    /// ```
+    pub fn pos_to_col(&self, pos: u64) -> Option<usize> {
+        let start = *self.range.start() as u64;
+        let end = *self.range.end() as u64;
+        if pos < start || pos > end {
+            return None;
+        }
+        let nth = if self.range.strand() == "-" {
+            end - pos + 1
+        } else {
+            pos - start + 1
+        } as usize;
+        self.seq
+            .iter()
+            .enumerate()
+            .filter(|(_, &b)| b != b'-')
+            .nth(nth - 1)
+            .map(|(i, _)| i + 1)
+    }
+}
+
+/// Chromosome positions (1-based) of the alignment columns of one entry.
+#[derive(Debug, Clone, Default)]
+pub struct ColPositions(Vec<Option<u64>>);
+
+impl ColPositions {
+    /// Chromosome position of the base at alignment column `col` (1-based).
+    ///
+    /// `None` for a gap column or a column outside the alignment.
+    pub fn get(&self, col: usize) -> Option<u64> {
+        col.checked_sub(1)
+            .and_then(|i| self.0.get(i).copied().flatten())
+    }
 }
 
 /// To string
@@ -79,8 +159,137 @@ pub struct FasBlock {
     pub headers: Vec<String>,
 }
 
+impl FasBlock {
+    /// Keep only the entries whose species name satisfies `f`.
+    ///
+    /// `entries`, `names` and `headers` are filtered in lockstep.
+    pub fn retain_names<F: FnMut(&str) -> bool>(&mut self, mut f: F) {
+        let keep: Vec<bool> = self.names.iter().map(|n| f(n)).collect();
+
+        let mut it = keep.iter();
+        self.entries.retain(|_| *it.next().unwrap());
+        let mut it = keep.iter();
+        self.names.retain(|_| *it.next().unwrap());
+        let mut it = keep.iter();
+        self.headers.retain(|_| *it.next().unwrap());
+    }
+
+    /// Species names occurring more than once in this block, in first-seen order.
+    pub fn duplicate_names(&self) -> Vec<String> {
+        let mut seen = std::collections::HashSet::new();
+        let mut dups: Vec<String> = vec![];
+        for name in &self.names {
+            if !seen.insert(name.as_str()) && !dups.contains(name) {
+                dups.push(name.clone());
+            }
+        }
+        dups
+    }
+
+    /// Make species names unique by appending `_1`, `_2`, ... to repeated ones.
+    ///
+    /// The first occurrence keeps its name. Returns the number of renamed entries.
+    pub fn rename_duplicates(&mut self) -> usize {
+        let mut seen: std::collections::HashSet<String> = self.names.iter().cloned().collect();
+        let mut count_of: std::collections::HashMap<String, usize> =
+            std::collections::HashMap::new();
+        let mut renamed = 0;
+
+        for i in 0..self.names.len() {
+            let name = self.names[i].clone();
+            let n = count_of.entry(name.clone()).or_insert(0);
+            *n += 1;
+            if *n == 1 {
+                continue;
+            }
+
+            // Skip suffixes already taken by other entries
+            let mut k = *n - 1;
+            let mut new_name = format!("{}_{}", name, k);
+            while seen.contains(&new_name) {
+                k += 1;
+                new_name = format!("{}_{}", name, k);
+            }
+            *n = k + 1;
+            seen.insert(new_name.clone());
+
+            let mut range = self.entries[i].range().clone();
+            *range.name_mut() = new_name.clone();
+            self.entries[i] = FasEntry::from(&range, self.entries[i].seq());
+            self.headers[i] = range.to_string();
+            self.names[i] = new_name;
+            renamed += 1;
+        }
+
+        renamed
+    }
+
+    /// Chromosome positions of the alignment columns of the reference (first) entry.
+    ///
+    /// See [`FasEntry::col_positions`]; empty for an empty block.
+    pub fn ref_col_positions(&self) -> ColPositions {
+        self.entries
+            .first()
+            .map(|e| e.col_positions())
+            .unwrap_or_default()
+    }
+
+    /// Alignment column of chromosome position `pos` in the reference (first) entry.
+    ///
+    /// See [`FasEntry::pos_to_col`]; `None` for an empty block.
+    pub fn ref_pos_to_col(&self, pos: u64) -> Option<usize> {
+        self.entries.first()?.pos_to_col(pos)
+    }
+
+    /// Write the block in block FA format, followed by the blank separator line.
+    pub fn write<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        for entry in &self.entries {
+            writer.write_all(entry.to_string().as_bytes())?;
+        }
+        writer.write_all(b"\n")
+    }
+}
+
+/// Error returned by [`next_fas_block`].
+///
+/// The end of input is a variant of its own, so a truncated or unreadable
+/// file can never be mistaken for a clean end of input.
+#[derive(Debug)]
+pub enum FasError {
+    /// No more blocks in the input.
+    Eof,
+    /// An I/O failure or malformed block.
+    Io(io::Error),
+}
+
+impl fmt::Display for FasError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FasError::Eof => write!(f, "end of block FA input"),
+            FasError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for FasError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FasError::Eof => None,
+            FasError::Io(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for FasError {
+    fn from(e: io::Error) -> Self {
+        FasError::Io(e)
+    }
+}
+
 /// Get the next FasBlock out of the input.
-pub fn next_fas_block<T: io::BufRead + ?Sized>(mut input: &mut T) -> Result<FasBlock, io::Error> {
+///
+/// Returns [`FasError::Eof`] once the input holds no further block.
+pub fn next_fas_block<T: io::BufRead + ?Sized>(mut input: &mut T) -> Result<FasBlock, FasError> {
     let mut header: Option<String> = None;
     {
         let lines = LinesRef { buf: &mut input };
@@ -100,43 +309,64 @@ pub fn next_fas_block<T: io::BufRead + ?Sized>(mut input: &mut T) -> Result<FasB
             } else {
                 // Shouldn't see this.
                 let preview: String = line.chars().take(80).collect();
-                return Err(io::Error::other(format!(
-                    "Unexpected line in block FA: {}",
-                    preview
-                )));
+                return Err(
+                    io::Error::other(format!("Unexpected line in block FA: {}", preview)).into(),
+                );
             }
         }
     }
-    let block = parse_fas_block(
-        header.ok_or(io::Error::new(io::ErrorKind::UnexpectedEof, "EOF"))?,
-        LinesRef { buf: &mut input },
-    )?;
+    let block = parse_fas_block(header.ok_or(FasError::Eof)?, LinesRef { buf: &mut input })?;
     Ok(block)
 }
 
-/// Iterator over FasBlock records from a reader.
+/// Streaming reader of FasBlock records.
+///
+/// Owns any `BufRead` source and yields one block per iteration; the end of
+/// input ends the iteration, other errors are propagated as `anyhow::Error`.
 ///
-/// Wraps [`next_fas_block`], treating `UnexpectedEof` as the end of iteration
-/// and propagating other errors as `anyhow::Error`.
-pub struct FasBlockIter<'a, R: io::BufRead + ?Sized> {
-    reader: &'a mut R,
+/// ```ignore
+/// let reader = pgr::reader("tests/fas/example.fas").unwrap();
+/// for block in pgr::libs::fmt::fas::FasReader::new(reader) {
+///     let block = block.unwrap();
+///     assert!(!block.entries.is_empty());
+/// }
+/// ```
+pub struct FasReader<R: io::BufRead> {
+    reader: R,
+}
+
+impl<R: io::BufRead> FasReader<R> {
+    /// Creates a reader over `reader`.
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Consumes the FasReader, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
 }
 
-impl<'a, R: io::BufRead + ?Sized> Iterator for FasBlockIter<'a, R> {
+impl<R: io::BufRead> Iterator for FasReader<R> {
     type Item = anyhow::Result<FasBlock>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match next_fas_block(self.reader) {
+        match next_fas_block(&mut self.reader) {
             Ok(block) => Some(Ok(block)),
-            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => None,
+            Err(FasError::Eof) => None,
             Err(e) => Some(Err(anyhow::Error::from(e))),
         }
     }
 }
 
-/// Create a FasBlock iterator from a reader.
+/// Iterator over FasBlock records borrowed from a reader.
+pub type FasBlockIter<'a, R> = FasReader<&'a mut R>;
+
+/// Create a FasBlock iterator from a borrowed reader.
+///
+/// A thin wrapper around [`FasReader::new`] for callers that keep the reader.
 pub fn iter_fas_blocks<R: io::BufRead + ?Sized>(reader: &mut R) -> FasBlockIter<'_, R> {
-    FasBlockIter { reader }
+    FasReader::new(reader)
 }
 
 /// Parse a single FasBlock from its header line and the following non-empty lines.
@@ -397,6 +627,11 @@ pub fn concat_blocks_into<R: io::BufRead>(
 }
 
 /// Process fas blocks from reader, aggregating coverage into res_of.
+///
+/// With `min_cov > 1`, only the alignment columns where at least `min_cov`
+/// entries have a base contribute, and each entry covers just its ungapped
+/// positions within those columns. `trim` applies to each entry's aligned
+/// range before that filter.
 pub fn aggregate_coverage_into<R: io::BufRead>(
     reader: &mut R,
     res_of: &mut std::collections::BTreeMap<
@@ -405,19 +640,26 @@ pub fn aggregate_coverage_into<R: io::BufRead>(
     >,
     name_filter: &str,
     trim: i32,
+    min_cov: usize,
 ) -> anyhow::Result<()> {
     for block_result in iter_fas_blocks(reader) {
         let block = block_result?;
-        let block_names = block.names;
+        let block_names = &block.names;
 
         if !name_filter.is_empty() {
             res_of.entry(name_filter.to_string()).or_default();
         } else {
-            for name in &block_names {
+            for name in block_names {
                 res_of.entry(name.clone()).or_default();
             }
         }
 
+        let covered = if min_cov > 1 {
+            Some(covered_columns(&block, min_cov))
+        } else {
+            None
+        };
+
         for (idx, entry) in block.entries.iter().enumerate() {
             let range = entry.range();
             if !range.is_valid() {
@@ -432,7 +674,12 @@ pub fn aggregate_coverage_into<R: io::BufRead>(
             let res = res_of
                 .get_mut(name)
                 .ok_or_else(|| anyhow::anyhow!("name not found in res_of: {}", name))?;
-            let intspan = range.intspan().clone().trim(trim);
+            // Trim the aligned range first, so --trim never cuts inside it at gaps
+            let trimmed = range.intspan().trim(trim);
+            let intspan = match &covered {
+                Some(cols) => entry_intspan_in(entry, cols).intersect(&trimmed),
+                None => trimmed,
+            };
             res.entry(range.chr().to_string())
                 .or_default()
                 .merge(&intspan);
@@ -441,6 +688,47 @@ pub fn aggregate_coverage_into<R: io::BufRead>(
     Ok(())
 }
 
+/// Flags the alignment columns where at least `min_cov` entries have a base.
+fn covered_columns(block: &FasBlock, min_cov: usize) -> Vec<bool> {
+    let len = block.entries.first().map(|e| e.seq().len()).unwrap_or(0);
+    (0..len)
+        .map(|i| {
+            block
+                .entries
+                .iter()
+                .filter(|e| e.seq().get(i).is_some_and(|b| *b != b'-'))
+                .count()
+                >= min_cov
+        })
+        .collect()
+}
+
+/// Chromosome positions of the entry's bases falling in the flagged columns.
+fn entry_intspan_in(entry: &FasEntry, cols: &[bool]) -> intspan::IntSpan {
+    let range = entry.range();
+    let is_minus = range.strand() == "-";
+    let mut pos = if is_minus {
+        *range.end()
+    } else {
+        *range.start()
+    };
+
+    let mut positions = vec![];
+    for (i, base) in entry.seq().iter().enumerate() {
+        if *base == b'-' {
+            continue;
+        }
+        if cols.get(i).copied().unwrap_or(false) {
+            positions.push(pos);
+        }
+        pos += if is_minus { -1 } else { 1 };
+    }
+
+    let mut ints = intspan::IntSpan::new();
+    ints.add_vec(&positions);
+    ints
+}
+
 /// Find best-to-best bilateral pairs based on sequence distance.
 pub fn find_best_pairs(entries: &[FasEntry]) -> anyhow::Result<Vec<(usize, usize)>> {
     let n = entries.len();
@@ -701,6 +989,57 @@ pub fn compute_block_stat(block: &FasBlock, has_outgroup: bool) -> anyhow::Resul
     })
 }
 
+/// Replace the sequence of `species` with the block consensus.
+///
+/// The consensus is the one `fas consensus` builds with its default POA
+/// settings, gaps included, so the row stays aligned with the others. The row
+/// keeps its name, chromosome, strand and start; its end follows the ungapped
+/// length of the consensus. Blocks without `species` are left unchanged, as
+/// are blocks whose consensus doesn't fit their columns (with a warning).
+pub fn replace_with_consensus(block: &mut FasBlock, species: &str) -> anyhow::Result<()> {
+    let Some(idx) = block.names.iter().position(|n| n == species) else {
+        return Ok(());
+    };
+
+    let params = crate::libs::poa::AlignmentParams::default();
+    let seqs: Vec<&[u8]> = block.entries.iter().map(|e| e.seq()).collect();
+    let cons = crate::libs::alignment::get_consensus_poa_builtin(
+        &seqs,
+        params.match_score,
+        params.mismatch_score,
+        params.gap_open,
+        params.gap_extend,
+        1, // global
+    )?;
+
+    let range = block.entries[idx].range();
+    let width = block.entries[idx].seq().len();
+    if cons.len() != width {
+        log::warn!(
+            "{}: consensus of {} columns doesn't fit a block of {}, left unchanged",
+            range,
+            cons.len(),
+            width
+        );
+        return Ok(());
+    }
+
+    let mut range = range.clone();
+    if range.is_valid() {
+        let length = cons.bytes().filter(|b| *b != b'-').count() as i32;
+        range = Range::from_full(
+            range.name(),
+            range.chr(),
+            range.strand(),
+            *range.start(),
+            *range.start() + length - 1,
+        );
+    }
+    block.headers[idx] = range.to_string();
+    block.entries[idx] = FasEntry::from(&range, cons.as_bytes());
+    Ok(())
+}
+
 /// Write variations (substitutions) from a FasBlock to a writer.
 ///
 /// `has_outgroup` treats the last entry as the outgroup and polarizes
@@ -714,10 +1053,38 @@ pub fn write_variations<W: Write>(
         return Ok(());
     }
 
-    let first = &block.entries[0];
-    let trange = first.range();
-    let t_ints_seq = crate::libs::alignment::seq_intspan(first.seq());
+    let trange = block.entries[0].range();
+
+    let subs = block_subs(block, has_outgroup)?;
+    let ref_positions = block.ref_col_positions();
+
+    for s in subs {
+        let chr = trange.chr();
+        let chr_pos = ref_sub_pos(&ref_positions, s.pos)?;
+        let var_rg = format!("{}:{}", chr, chr_pos);
+        writeln!(
+            writer,
+            "{}\t{}\t{}\t{}\t{}",
+            trange, chr, chr_pos, var_rg, s
+        )?;
+    }
+    Ok(())
+}
+
+/// Chromosome position of a substitution column; substitution columns never
+/// hold a gap, so a missing position means a malformed block.
+fn ref_sub_pos(ref_positions: &ColPositions, col: i32) -> anyhow::Result<i32> {
+    let pos = ref_positions
+        .get(col as usize)
+        .ok_or_else(|| anyhow::anyhow!("column {} has no reference base", col))?;
+    Ok(pos as i32)
+}
 
+/// Substitutions of a block, polarized against the last entry when `has_outgroup`.
+fn block_subs(
+    block: &FasBlock,
+    has_outgroup: bool,
+) -> anyhow::Result<Vec<crate::libs::alignment::Substitution>> {
     let seqs: Vec<&[u8]> = block.entries.iter().map(|e| e.seq()).collect();
     let seq_count = seqs.len();
     if has_outgroup && seq_count < 2 {
@@ -734,31 +1101,82 @@ pub fn write_variations<W: Write>(
     } else {
         crate::libs::alignment::get_subs(&seqs)?
     };
+    Ok(subs)
+}
 
-    for s in subs {
-        let chr = trange.chr();
-        let chr_pos = crate::libs::alignment::align_to_chr(
-            &t_ints_seq,
-            s.pos,
-            trange.start,
-            trange.strand(),
-        )?;
-        let var_rg = format!("{}:{}", chr, chr_pos);
+/// Write per-window substitution counts of a FasBlock to a writer.
+///
+/// Windows of `window` bases advance by `step` along the ungapped coordinates
+/// of the first (reference) entry. The trailing window is truncated at the
+/// end of the reference range and reported with its actual width. Each row
+/// holds the window, its width, the number of segregating sites and their
+/// density (sites per base).
+pub fn write_variation_windows<W: Write>(
+    block: &FasBlock,
+    has_outgroup: bool,
+    window: usize,
+    step: usize,
+    writer: &mut W,
+) -> anyhow::Result<()> {
+    if block.entries.is_empty() {
+        return Ok(());
+    }
+    anyhow::ensure!(window > 0 && step > 0, "window and step must be positive");
+
+    let trange = block.entries[0].range();
+
+    let ref_positions = block.ref_col_positions();
+    let mut positions = vec![];
+    for s in block_subs(block, has_outgroup)? {
+        positions.push(ref_sub_pos(&ref_positions, s.pos)?);
+    }
+
+    let chr = trange.chr();
+    let t_start = *trange.start();
+    let t_end = *trange.end();
+    for start in (t_start..=t_end).step_by(step) {
+        let end = (start + window as i32 - 1).min(t_end);
+        let width = end - start + 1;
+        let count = positions
+            .iter()
+            .filter(|&&p| p >= start && p <= end)
+            .count();
         writeln!(
             writer,
-            "{}\t{}\t{}\t{}\t{}",
-            trange, chr, chr_pos, var_rg, s
+            "{}\t{}\t{}\t{}\t{}\t{}\t{:.4}",
+            trange,
+            chr,
+            start,
+            end,
+            width,
+            count,
+            count as f64 / width as f64
         )?;
+        if end == t_end {
+            break;
+        }
     }
     Ok(())
 }
 
 /// Write VCF rows for a single FasBlock.
 ///
-/// `block_idx` is used only for error messages.
+/// The first entry is the reference and defines REF; every entry is a sample.
+/// A column is emitted as a SNP when its reference base is A/C/G/T and at
+/// least one sample carries a different A/C/G/T base. Samples with a gap or
+/// ambiguous base at the site get a `.` genotype.
+///
+/// With `with_indels`, each run of consecutive gap columns becomes one indel
+/// record, left-anchored on the preceding (gap-free) column as VCF requires.
+/// SNP columns inside such a run, and at its anchor, are folded into the
+/// indel alleles. Runs at the start of a block, or in blocks whose reference
+/// is on the minus strand, have no usable anchor and are skipped with a
+/// warning. `block_idx` is used only for error
+/// messages.
 pub fn write_vcf_block<W: Write>(
     block: &FasBlock,
     block_idx: usize,
+    with_indels: bool,
     writer: &mut W,
 ) -> anyhow::Result<()> {
     if block.entries.is_empty() {
@@ -768,49 +1186,183 @@ pub fn write_vcf_block<W: Write>(
     let seqs: Vec<&[u8]> = block.entries.iter().map(|e| e.seq()).collect();
     let target_entry = &block.entries[0];
     let trange = target_entry.range();
-    let t_ints_seq = crate::libs::alignment::seq_intspan(target_entry.seq());
+    let target = VcfTarget {
+        chr: trange.chr(),
+        positions: target_entry.col_positions(),
+    };
+
+    // Gap runs, 1-based inclusive alignment columns
+    let mut indel_spans: Vec<(i32, i32)> = vec![];
+    if with_indels {
+        let mut indel_set = intspan::IntSpan::new();
+        for seq in &seqs {
+            indel_set.merge(&crate::libs::alignment::indel_intspan(seq));
+        }
+        for (start, end) in indel_set.spans() {
+            if trange.strand() == "-" {
+                log::warn!(
+                    "{}: skipped the indel at columns {}-{}, the target is on the minus strand",
+                    trange,
+                    start,
+                    end
+                );
+            } else if start == 1 {
+                log::warn!(
+                    "{}: skipped the indel at columns {}-{}, no anchor base at the block start",
+                    trange,
+                    start,
+                    end
+                );
+            } else {
+                indel_spans.push((start, end));
+            }
+        }
+    }
+    let mut spans = indel_spans.iter().peekable();
 
-    let subs = crate::libs::alignment::get_subs(&seqs)?;
+    for (pos_idx, &base) in seqs[0].iter().enumerate() {
+        let pos = pos_idx as i32 + 1;
 
-    for s in subs {
-        let chr = trange.chr();
-        let chr_pos =
-            crate::libs::alignment::align_to_chr(&t_ints_seq, s.pos, trange.start, trange.strand())
-                .with_context(|| format!("align_to_chr at pos {} in block {}", s.pos, block_idx))?;
-
-        let pos_idx = usize::try_from(s.pos).map_err(|_| {
-            anyhow::anyhow!("invalid substitution pos {} in block {}", s.pos, block_idx)
-        })?;
-        let pos_idx = pos_idx.checked_sub(1).ok_or_else(|| {
-            anyhow::anyhow!("invalid substitution pos {} in block {}", s.pos, block_idx)
-        })?;
-        if pos_idx >= seqs[0].len() {
-            anyhow::bail!(
-                "substitution pos {} out of range (seq len {}) in block {}",
-                s.pos,
-                seqs[0].len(),
-                block_idx
-            );
+        // Columns covered by an indel record are not reported as SNPs
+        while spans.peek().is_some_and(|(_, end)| *end < pos) {
+            spans.next();
+        }
+        if let Some(&&(start, end)) = spans.peek() {
+            if start == pos + 1 {
+                // The current column anchors the following indel, and its
+                // substitutions are part of that record
+                if !write_indel(writer, &target, &seqs, start, end, block_idx)? {
+                    write_snp_column(writer, &target, &seqs, pos_idx, block_idx)?;
+                }
+                continue;
+            }
+            if start <= pos {
+                continue;
+            }
         }
-        let ref_base = char::from(seqs[0][pos_idx]).to_ascii_uppercase();
-        let alt_bases = crate::libs::alignment::vcf_alt_bases(&s);
-        let sample_bases: Vec<u8> = seqs
-            .iter()
-            .map(|seq| seq.get(pos_idx).copied().unwrap_or(b'-'))
-            .collect();
 
-        crate::libs::fmt::vcf::write_snp_row(
-            writer,
-            chr,
-            chr_pos,
-            ref_base,
-            &alt_bases,
-            &sample_bases,
-        )?;
+        let ref_base = char::from(base).to_ascii_uppercase();
+        if !matches!(ref_base, 'A' | 'C' | 'G' | 'T') {
+            continue;
+        }
+        write_snp_column(writer, &target, &seqs, pos_idx, block_idx)?;
     }
     Ok(())
 }
 
+/// Chromosome and column positions of the reference entry of a VCF block.
+struct VcfTarget<'a> {
+    chr: &'a str,
+    positions: ColPositions,
+}
+
+/// Write the SNP record of one alignment column, if it has an ALT allele.
+fn write_snp_column<W: Write>(
+    writer: &mut W,
+    target: &VcfTarget,
+    seqs: &[&[u8]],
+    pos_idx: usize,
+    block_idx: usize,
+) -> anyhow::Result<()> {
+    let ref_base = char::from(seqs[0][pos_idx]).to_ascii_uppercase();
+    if !matches!(ref_base, 'A' | 'C' | 'G' | 'T') {
+        return Ok(());
+    }
+
+    let sample_bases: Vec<u8> = seqs
+        .iter()
+        .map(|seq| seq.get(pos_idx).copied().unwrap_or(b'-'))
+        .collect();
+
+    // ALT alleles in order of appearance
+    let mut alt_bases: Vec<char> = vec![];
+    for &b in &sample_bases {
+        let c = char::from(b).to_ascii_uppercase();
+        if matches!(c, 'A' | 'C' | 'G' | 'T') && c != ref_base && !alt_bases.contains(&c) {
+            alt_bases.push(c);
+        }
+    }
+    if alt_bases.is_empty() {
+        return Ok(());
+    }
+
+    let pos = pos_idx + 1;
+    let chr_pos = target
+        .positions
+        .get(pos)
+        .with_context(|| format!("no reference base at column {} in block {}", pos, block_idx))?;
+
+    crate::libs::fmt::vcf::write_snp_row(
+        writer,
+        target.chr,
+        chr_pos as i32,
+        ref_base,
+        &alt_bases,
+        &sample_bases,
+    )
+}
+
+/// Write the indel record of gap columns `start..=end`, anchored on `start - 1`.
+///
+/// Returns `false` when no record is written (ambiguous reference allele, or
+/// no ALT allele).
+fn write_indel<W: Write>(
+    writer: &mut W,
+    target: &VcfTarget,
+    seqs: &[&[u8]],
+    start: i32,
+    end: i32,
+    block_idx: usize,
+) -> anyhow::Result<bool> {
+    let anchor = (start - 2) as usize;
+    let alleles: Vec<Option<String>> = seqs
+        .iter()
+        .map(|seq| {
+            let allele: String = seq[anchor..end as usize]
+                .iter()
+                .filter(|&&b| b != b'-')
+                .map(|&b| char::from(b).to_ascii_uppercase())
+                .collect();
+            if allele.chars().all(|c| matches!(c, 'A' | 'C' | 'G' | 'T')) {
+                Some(allele)
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let Some(ref_allele) = alleles[0].clone() else {
+        return Ok(false);
+    };
+    let mut alt_alleles: Vec<String> = vec![];
+    for allele in alleles.iter().flatten() {
+        if *allele != ref_allele && !alt_alleles.contains(allele) {
+            alt_alleles.push(allele.clone());
+        }
+    }
+    if alt_alleles.is_empty() {
+        return Ok(false);
+    }
+
+    let anchor_col = (start - 1) as usize;
+    let chr_pos = target.positions.get(anchor_col).with_context(|| {
+        format!(
+            "no reference base at column {} in block {}",
+            anchor_col, block_idx
+        )
+    })?;
+
+    crate::libs::fmt::vcf::write_allele_row(
+        writer,
+        target.chr,
+        chr_pos as i32,
+        &ref_allele,
+        &alt_alleles,
+        &alleles,
+    )?;
+    Ok(true)
+}
+
 /// Concatenate accumulated sequences and write them in FASTA or relaxed PHYLIP format.
 pub fn write_concat_output<W: Write>(
     writer: &mut W,
@@ -897,6 +1449,89 @@ pub fn create_from_links<R: io::BufRead, W: Write>(
     Ok(())
 }
 
+/// Build a two-species block from a chain and the target/query sequences it
+/// aligns.
+///
+/// Aligned blocks are copied from both sequences; each `dt` gap inserts target
+/// bases against dashes in the query row, each `dq` gap the reverse. A
+/// minus-strand query is reverse-complemented, and its range is reported in
+/// forward-strand coordinates with a `-` strand, as in `axt to-fas`.
+pub fn chain_to_block<S: crate::libs::io::SequenceReader>(
+    chain: &crate::libs::chain::Chain,
+    t_2bit: &mut S,
+    q_2bit: &mut S,
+    t_name: &str,
+    q_name: &str,
+) -> anyhow::Result<FasBlock> {
+    let h = &chain.header;
+
+    let t_all = t_2bit
+        .read_sequence(&h.t_name, Some(h.t_start as usize), Some(h.t_end as usize))?
+        .into_bytes();
+
+    let (q_fwd_start, q_fwd_end) = if h.q_strand == '-' {
+        crate::libs::alignment::coords::reverse_range_pair(h.q_start, h.q_end, h.q_size)
+    } else {
+        (h.q_start, h.q_end)
+    };
+    let mut q_all = q_2bit
+        .read_sequence(
+            &h.q_name,
+            Some(q_fwd_start as usize),
+            Some(q_fwd_end as usize),
+        )?
+        .into_bytes();
+    if h.q_strand == '-' {
+        q_all = crate::libs::nt::rev_comp(&q_all).collect();
+    }
+
+    let slice = |all: &[u8], pos: usize, len: usize, side: &str| -> anyhow::Result<Vec<u8>> {
+        all.get(pos..pos + len)
+            .map(|s| s.to_vec())
+            .ok_or_else(|| anyhow::anyhow!("chain {}: {} blocks exceed its range", h.id, side))
+    };
+
+    let mut t_seq: Vec<u8> = vec![];
+    let mut q_seq: Vec<u8> = vec![];
+    let (mut t_pos, mut q_pos) = (0usize, 0usize);
+    for d in &chain.data {
+        let (size, dt, dq) = (d.size as usize, d.dt as usize, d.dq as usize);
+        t_seq.extend(slice(&t_all, t_pos, size, "target")?);
+        q_seq.extend(slice(&q_all, q_pos, size, "query")?);
+        t_pos += size;
+        q_pos += size;
+
+        t_seq.extend(slice(&t_all, t_pos, dt, "target")?);
+        q_seq.resize(q_seq.len() + dt, b'-');
+        t_pos += dt;
+
+        q_seq.extend(slice(&q_all, q_pos, dq, "query")?);
+        t_seq.resize(t_seq.len() + dq, b'-');
+        q_pos += dq;
+    }
+
+    let to_i32 = |v: u64| {
+        i32::try_from(v).map_err(|_| anyhow::anyhow!("coordinate {} exceeds i32 range", v))
+    };
+
+    let mut t_range = Range::from(&h.t_name, to_i32(h.t_start + 1)?, to_i32(h.t_end)?);
+    *t_range.name_mut() = t_name.to_string();
+    *t_range.strand_mut() = "+".to_string();
+
+    let mut q_range = Range::from(&h.q_name, to_i32(q_fwd_start + 1)?, to_i32(q_fwd_end)?);
+    *q_range.name_mut() = q_name.to_string();
+    *q_range.strand_mut() = h.q_strand.to_string();
+
+    Ok(FasBlock {
+        entries: vec![
+            FasEntry::from(&t_range, &t_seq),
+            FasEntry::from(&q_range, &q_seq),
+        ],
+        names: vec![t_name.to_string(), q_name.to_string()],
+        headers: vec![t_range.to_string(), q_range.to_string()],
+    })
+}
+
 /// Returns the file key used to group a FasBlock when splitting.
 ///
 /// When `is_chr` is true, the key is `{name}.{chr}` using the first entry's
@@ -913,6 +1548,20 @@ pub fn split_block_key(block: &FasBlock, is_chr: bool) -> Option<String> {
     Some(key)
 }
 
+/// Returns the file key used by `split --by-chrom`.
+///
+/// The key is the chromosome of the first entry whose species is `ref_name`,
+/// or `unassigned` when the block has no such entry.
+pub fn split_block_chrom(block: &FasBlock, ref_name: &str) -> String {
+    block
+        .entries
+        .iter()
+        .zip(&block.names)
+        .find(|(_, name)| *name == ref_name)
+        .map(|(entry, _)| entry.range().chr().to_string())
+        .unwrap_or_else(|| "unassigned".to_string())
+}
+
 /// Format one FasBlock for the `split` command.
 ///
 /// Each entry is written as `>{header}\n{seq}\n`. When `is_simple` is true,
@@ -949,6 +1598,8 @@ pub struct ConsensusOptions {
     pub params: crate::libs::poa::AlignmentParams,
     /// Alignment mode code: 0=local, 1=global, 2=semi_global.
     pub algo_code: i32,
+    /// Emit the built-in POA graph in DOT format instead of the consensus.
+    pub dot: bool,
 }
 
 /// Build consensus for one [`FasBlock`] and return a fas-formatted string.
@@ -979,6 +1630,14 @@ pub fn consensus_block(block: &FasBlock, opts: &ConsensusOptions) -> anyhow::Res
         seqs.pop(); // Remove the outgroup sequence
     }
 
+    if opts.dot {
+        return Ok(crate::libs::alignment::get_poa_dot_builtin(
+            &seqs,
+            &opts.params,
+            opts.algo_code,
+        ));
+    }
+
     // Generate consensus sequence
     let mut cons = match opts.engine.as_str() {
         "spoa" => crate::libs::alignment::get_consensus_poa_external(
@@ -1037,6 +1696,10 @@ pub struct RefineOptions<'a> {
     pub pad: usize,
     /// In quick mode, fill holes between indels up to this distance.
     pub fill: usize,
+    /// Codon-aware mode: keep gaps in multiples of three when the reference is in frame.
+    pub is_codon: bool,
+    /// Scoring parameters for the `"builtin"` POA engine.
+    pub params: crate::libs::poa::AlignmentParams,
 }
 
 /// Realign and trim one [`FasBlock`], return a fas-formatted string.
@@ -1058,6 +1721,13 @@ pub fn refine_block(block: &FasBlock, opts: &RefineOptions) -> anyhow::Result<St
         ranges.push(entry.range().clone());
     }
 
+    // Blocks whose reference is out of frame fall back to nucleotide alignment
+    let codon_aligned = if opts.is_codon && opts.engine != "none" && !opts.is_quick {
+        crate::libs::alignment::align_seqs_codon(&seqs, opts.engine, &opts.params)?
+    } else {
+        None
+    };
+
     let mut aligned = vec![];
     if opts.engine == "none" {
         aligned = seqs;
@@ -1066,7 +1736,17 @@ pub fn refine_block(block: &FasBlock, opts: &RefineOptions) -> anyhow::Result<St
             .map_err(|_| anyhow::anyhow!("--indel-pad {} exceeds i32 range", opts.pad))?;
         let fill_i32 = i32::try_from(opts.fill)
             .map_err(|_| anyhow::anyhow!("--fill {} exceeds i32 range", opts.fill))?;
-        aligned = crate::libs::alignment::align_seqs_quick(&seqs, opts.engine, pad_i32, fill_i32)?;
+        aligned = crate::libs::alignment::align_seqs_quick(
+            &seqs,
+            opts.engine,
+            &opts.params,
+            pad_i32,
+            fill_i32,
+        )?;
+    } else if let Some(codon_aligned) = codon_aligned {
+        aligned = codon_aligned;
+    } else if opts.engine == "builtin" {
+        aligned = crate::libs::alignment::align_seqs_builtin(&seqs, &opts.params);
     } else {
         aligned = crate::libs::alignment::align_seqs(&seqs, opts.engine)?;
     }
@@ -1096,6 +1776,72 @@ pub fn refine_block(block: &FasBlock, opts: &RefineOptions) -> anyhow::Result<St
 mod fas_tests {
     use std::io::BufReader;
 
+    #[test]
+    fn ref_column_mapping_with_interior_gaps() {
+        use crate::libs::fmt::fas::{FasBlock, FasEntry};
+        use intspan::Range;
+
+        let block_of = |header: &str| {
+            let range = Range::from_str(header);
+            FasBlock {
+                entries: vec![
+                    FasEntry::from(&range, b"-AA--TTTGG"),
+                    FasEntry::from(&Range::from_str("II(+):1-10"), b"CAATCTTTGG"),
+                ],
+                names: vec!["S288c".to_string(), "Spar".to_string()],
+                headers: vec![header.to_string(), "II(+):1-10".to_string()],
+            }
+        };
+
+        let block = block_of("I(+):101-107");
+        let positions = block.ref_col_positions();
+        assert_eq!(positions.get(2), Some(101));
+        assert_eq!(positions.get(3), Some(102));
+        assert_eq!(positions.get(6), Some(103));
+        assert_eq!(positions.get(10), Some(107));
+        for col in [0, 1, 4, 5, 11] {
+            assert_eq!(positions.get(col), None, "column {}", col);
+        }
+        assert_eq!(block.ref_pos_to_col(103), Some(6));
+        assert_eq!(block.ref_pos_to_col(100), None);
+        assert_eq!(block.ref_pos_to_col(108), None);
+
+        let block = block_of("I(-):101-107");
+        let positions = block.ref_col_positions();
+        assert_eq!(positions.get(2), Some(107));
+        assert_eq!(positions.get(6), Some(105));
+        assert_eq!(positions.get(10), Some(101));
+        assert_eq!(block.ref_pos_to_col(107), Some(2));
+
+        // Every reference base round-trips
+        for block in [block_of("I(+):101-107"), block_of("I(-):101-107")] {
+            for pos in 101..=107 {
+                let col = block.ref_pos_to_col(pos).unwrap();
+                assert_eq!(block.ref_col_positions().get(col), Some(pos));
+            }
+        }
+    }
+
+    #[test]
+    fn fas_reader_counts_blocks() {
+        let reader = crate::reader("tests/fas/example.fas").unwrap();
+        let blocks = crate::libs::fmt::fas::FasReader::new(reader)
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].names[0], "S288c");
+
+        let mut out: Vec<u8> = vec![];
+        for block in &blocks {
+            block.write(&mut out).unwrap();
+        }
+        let again = crate::libs::fmt::fas::FasReader::new(BufReader::new(out.as_slice()))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(again.len(), 3);
+        assert_eq!(again[1].entries[0].seq(), blocks[1].entries[0].seq());
+    }
+
     #[test]
     fn parse_fas_block_range() {
         let input = ">S288c.I(+):13267-13287
@@ -1148,6 +1894,34 @@ ACGT\n";
         );
     }
 
+    #[test]
+    fn next_fas_block_eof_is_distinct_from_parse_error() {
+        use crate::libs::fmt::fas::{next_fas_block, FasError};
+
+        let mut reader = BufReader::new("# comment only\n\n".as_bytes());
+        assert!(matches!(next_fas_block(&mut reader), Err(FasError::Eof)));
+
+        let mut reader = BufReader::new("ACGT\n".as_bytes());
+        match next_fas_block(&mut reader) {
+            Err(FasError::Io(e)) => assert!(e.to_string().contains("Unexpected line")),
+            Err(FasError::Eof) => panic!("malformed input must not be reported as EOF"),
+            Ok(_) => panic!("malformed input must be rejected"),
+        }
+
+        // A reader error is not end of input, even when its kind is UnexpectedEof
+        struct Truncated;
+        impl std::io::Read for Truncated {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "EOF",
+                ))
+            }
+        }
+        let mut reader = BufReader::new(Truncated);
+        assert!(matches!(next_fas_block(&mut reader), Err(FasError::Io(_))));
+    }
+
     #[test]
     fn parse_fas_block_handles_whitespace_separator() {
         let input = ">S288c.I(+):13267-13287
@@ -1162,4 +1936,43 @@ GCATATAATATGAACCAATATCTA\n";
         let block = crate::libs::fmt::fas::next_fas_block(&mut reader).unwrap();
         assert_eq!(block.entries.len(), 1, "second block should have one entry");
     }
+
+    #[test]
+    fn refine_codon_keeps_gaps_in_frame() {
+        use crate::libs::fmt::fas::{next_fas_block, refine_block, RefineOptions};
+
+        // The query has a three-base deletion next to a substitution; aligned as
+        // nucleotides, the deletion splits into gaps of two and one
+        let input = ">S288c.I(+):1-24
+ATGGTGCACCAACTAAGATCCTAA
+>Spar.I(+):1-21
+ATGGTGCACCCAAGATCCTAA
+
+";
+        let block = next_fas_block(&mut BufReader::new(input.as_bytes())).unwrap();
+        let opts = RefineOptions {
+            engine: "builtin",
+            has_outgroup: false,
+            chop: 0,
+            is_quick: false,
+            pad: 50,
+            fill: 50,
+            is_codon: true,
+            params: crate::libs::poa::AlignmentParams::default(),
+        };
+
+        let out = refine_block(&block, &opts).unwrap();
+        let seqs: Vec<&str> = out
+            .lines()
+            .filter(|l| !l.is_empty() && !l.starts_with('>'))
+            .collect();
+        assert_eq!(seqs.len(), 2);
+        assert_eq!(seqs[0], "ATGGTGCACCAACTAAGATCCTAA");
+        for seq in seqs {
+            assert_eq!(seq.len() % 3, 0, "{}", seq);
+            for gap in seq.split(|c| c != '-').filter(|g| !g.is_empty()) {
+                assert_eq!(gap.len() % 3, 0, "gap of {} in {}", gap.len(), seq);
+            }
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use anyhow::Context;
 use std::io::{self, BufRead, Write};
 
 use crate::libs::fmt::psl::Psl;
@@ -395,11 +396,45 @@ pub fn blocks_to_psl(
     psl
 }
 
+/// Check that gapped blocks lie within the sequences and advance on both sides.
+fn validate_blocks(blocks: &[Block], t_size: u32, q_size: u32) -> anyhow::Result<()> {
+    for (i, block) in blocks.iter().enumerate() {
+        if block.t_start < 0 || block.t_end > t_size as i64 {
+            anyhow::bail!(
+                "block {} target range {}-{} exceeds size {}",
+                i + 1,
+                block.t_start,
+                block.t_end,
+                t_size
+            );
+        }
+        if block.q_start < 0 || block.q_end > q_size as i64 {
+            anyhow::bail!(
+                "block {} query range {}-{} exceeds size {}",
+                i + 1,
+                block.q_start,
+                block.q_end,
+                q_size
+            );
+        }
+        if i > 0 {
+            let prev = &blocks[i - 1];
+            if block.t_start < prev.t_end || block.q_start < prev.q_end {
+                anyhow::bail!("block {} overlaps or precedes block {}", i + 1, i);
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Convert a LAV stream to PSL with optional target strand annotation.
 ///
 /// Iterates LAV stanzas, accumulating sizes/header state, and emits one PSL
-/// per Alignment stanza. Unknown stanzas trigger a `log::warn!` unless
-/// `strict` is set, in which case they bail.
+/// per Alignment stanza. Each `s` stanza resets the sequence names, so every
+/// alignment is paired with the `h` stanza of its own sequence pair, and the
+/// gapped blocks are checked to be in range and non-overlapping. `d`, `m` and
+/// `x` stanzas are ignored; other unknown stanzas trigger a `log::warn!`
+/// unless `strict` is set, in which case they bail.
 pub fn lav_to_psl<R: BufRead, W: Write>(
     reader: R,
     writer: &mut W,
@@ -424,6 +459,10 @@ pub fn lav_to_psl<R: BufRead, W: Write>(
                     Some(u32::try_from(t).map_err(|_| anyhow::anyhow!("invalid t_size: {}", t))?);
                 q_size =
                     Some(u32::try_from(q).map_err(|_| anyhow::anyhow!("invalid q_size: {}", q))?);
+                // A new sequence pair starts; its names come from the following h stanza
+                t_name = None;
+                q_name = None;
+                strand = None;
             }
             LavStanza::Header {
                 t_name: t,
@@ -459,6 +498,10 @@ pub fn lav_to_psl<R: BufRead, W: Write>(
                     anyhow::anyhow!("Alignment stanza encountered before Header stanza")
                 })?;
 
+                validate_blocks(&blocks, t_size, q_size).with_context(|| {
+                    format!("invalid alignment stanza for {} vs {}", t_name, q_name)
+                })?;
+
                 let mut psl = blocks_to_psl(&blocks, t_size, q_size, t_name, q_name, strand);
 
                 if let Some(ts) = target_strand {
@@ -474,6 +517,9 @@ pub fn lav_to_psl<R: BufRead, W: Write>(
 
                 psl.write_to(writer)?;
             }
+            // Parameters, masking and summary stanzas carry nothing for PSL
+            LavStanza::Data { .. } => {}
+            LavStanza::Unknown(ref line) if line.starts_with("m {") || line.starts_with("x {") => {}
             other => {
                 if strict {
                     anyhow::bail!("unknown lav stanza: {:?}", other);
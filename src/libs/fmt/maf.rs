@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::io::{self, Write};
 
 use crate::libs::io::LinesRef;
@@ -205,6 +206,91 @@ impl<W: Write> MafWriter<W> {
         writeln!(self.writer)?;
         Ok(())
     }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Species part of a MAF `src` field (the text before the first `.`).
+pub fn maf_species(src: &str) -> &str {
+    src.split_once('.').map_or(src, |(species, _)| species)
+}
+
+/// Summary counts accumulated over MAF blocks.
+#[derive(Debug, Default)]
+pub struct MafStats {
+    /// Number of alignment blocks.
+    pub blocks: usize,
+    /// Total alignment columns over all blocks.
+    pub columns: usize,
+    /// Number of `s` rows per species.
+    pub rows: BTreeMap<String, usize>,
+}
+
+impl MafStats {
+    /// Add one block. Fails if the rows of the block have unequal text lengths.
+    pub fn add_block(&mut self, ali: &MafAli) -> Result<(), io::Error> {
+        let width = ali.components.first().map_or(0, |c| c.text.len());
+        if let Some(comp) = ali.components.iter().find(|c| c.text.len() != width) {
+            return Err(io::Error::other(format!(
+                "row {} has {} columns, expected {}",
+                comp.src,
+                comp.text.len(),
+                width
+            )));
+        }
+
+        self.blocks += 1;
+        self.columns += width;
+        for comp in &ali.components {
+            *self
+                .rows
+                .entry(maf_species(&comp.src).to_string())
+                .or_insert(0) += 1;
+        }
+        Ok(())
+    }
+
+    /// Write the report as `key<TAB>value` lines.
+    pub fn write_tsv<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "blocks\t{}", self.blocks)?;
+        writeln!(writer, "species\t{}", self.rows.len())?;
+        writeln!(writer, "columns\t{}", self.columns)?;
+        for (species, count) in &self.rows {
+            writeln!(writer, "rows:{}\t{}", species, count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Block-level criteria for filtering MAF alignments.
+#[derive(Debug, Default, Clone)]
+pub struct MafFilter {
+    /// Minimum `score=` value; blocks without a score count as 0.
+    pub min_score: Option<f64>,
+    /// Minimum number of distinct species in a block.
+    pub min_species: usize,
+    /// Species that must all be present in a block.
+    pub require: Vec<String>,
+}
+
+impl MafFilter {
+    /// Whether the block passes all criteria.
+    pub fn passes(&self, ali: &MafAli) -> bool {
+        if let Some(min) = self.min_score {
+            if ali.score.unwrap_or(0.0) < min {
+                return false;
+            }
+        }
+
+        let species: std::collections::BTreeSet<&str> =
+            ali.components.iter().map(|c| maf_species(&c.src)).collect();
+        if species.len() < self.min_species {
+            return false;
+        }
+        self.require.iter().all(|r| species.contains(r.as_str()))
+    }
 }
 
 #[cfg(test)]
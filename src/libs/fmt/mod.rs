@@ -4,6 +4,7 @@ pub mod fas;
 pub mod fq;
 pub mod lav;
 pub mod maf;
+pub mod phylip;
 pub mod psl;
 pub mod twobit;
 pub mod vcf;
@@ -0,0 +1,251 @@
+//! PHYLIP distance matrices: reading, writing and clean-up helpers used by
+//! `pgr mat`.
+
+use anyhow::Context;
+use std::io::Write;
+
+/// Read a PHYLIP distance matrix into taxon names and a square matrix.
+///
+/// The first non-empty line is the taxon count. Each following line is a
+/// name and its distances, either full rows or a lower triangle (row `i`
+/// holds `i` values, the diagonal optional). The layout is decided by the
+/// first row; lower triangles are mirrored, full rows are kept as-is.
+///
+/// ```ignore
+/// let (names, matrix) = pgr::libs::fmt::phylip::read_matrix("tests/mat/line.phy").unwrap();
+/// assert_eq!(names.len(), matrix.len());
+/// ```
+pub fn read_matrix(path: &str) -> anyhow::Result<(Vec<String>, Vec<Vec<f64>>)> {
+    let mut lines = crate::libs::io::read_lines(path)?
+        .into_iter()
+        .filter(|line| !line.trim().is_empty());
+
+    let header = lines
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("{}: empty matrix file", path))?;
+    let n: usize = header
+        .trim()
+        .parse()
+        .with_context(|| format!("{}: invalid taxon count: {}", path, header))?;
+
+    let mut names = Vec::with_capacity(n);
+    let mut matrix = vec![vec![0.0; n]; n];
+    let mut is_lower = false;
+    for (i, line) in lines.enumerate() {
+        anyhow::ensure!(i < n, "{}: more than {} rows", path, n);
+        let mut fields = line.split_whitespace();
+        names.push(fields.next().unwrap().to_string());
+        let values = fields
+            .map(|v| {
+                v.parse::<f64>()
+                    .with_context(|| format!("{}: invalid distance: {}", path, v))
+            })
+            .collect::<anyhow::Result<Vec<f64>>>()?;
+        if i == 0 {
+            is_lower = values.len() <= 1 && n > 1;
+        }
+        let expected_ok = if is_lower {
+            values.len() == i || values.len() == i + 1
+        } else {
+            values.len() == n
+        };
+        anyhow::ensure!(
+            expected_ok,
+            "{}: row {} has {} values, expected {}",
+            path,
+            names[i],
+            values.len(),
+            if is_lower { i } else { n }
+        );
+        for (j, v) in values.into_iter().enumerate() {
+            matrix[i][j] = v;
+            if is_lower {
+                matrix[j][i] = v;
+            }
+        }
+    }
+    anyhow::ensure!(
+        names.len() == n,
+        "{}: expected {} rows, found {}",
+        path,
+        n,
+        names.len()
+    );
+
+    Ok((names, matrix))
+}
+
+/// Write a full PHYLIP matrix, tab-separated.
+///
+/// With `precision`, values are printed with that many decimal places;
+/// otherwise the shortest representation that round-trips is used.
+pub fn write_matrix<W: Write>(
+    writer: &mut W,
+    names: &[String],
+    matrix: &[Vec<f64>],
+    precision: Option<usize>,
+) -> anyhow::Result<()> {
+    writeln!(writer, "{}", names.len())?;
+    for (name, row) in names.iter().zip(matrix) {
+        let values: Vec<String> = row
+            .iter()
+            .map(|v| match precision {
+                Some(p) => format!("{:.*}", p, v),
+                None => format!("{}", v),
+            })
+            .collect();
+        writeln!(writer, "{}\t{}", name, values.join("\t"))?;
+    }
+    Ok(())
+}
+
+/// Enforce symmetry on a square matrix.
+///
+/// * `avg`: replace both triangles with their mean
+/// * `lower`: mirror the lower triangle onto the upper
+/// * `upper`: mirror the upper triangle onto the lower
+#[allow(clippy::needless_range_loop)]
+pub fn symmetrize(matrix: &mut [Vec<f64>], mode: &str) -> anyhow::Result<()> {
+    let n = matrix.len();
+    for i in 0..n {
+        for j in 0..i {
+            let v = match mode {
+                "avg" => (matrix[i][j] + matrix[j][i]) / 2.0,
+                "lower" => matrix[i][j],
+                "upper" => matrix[j][i],
+                _ => anyhow::bail!("unknown symmetrize mode: {}", mode),
+            };
+            matrix[i][j] = v;
+            matrix[j][i] = v;
+        }
+    }
+    Ok(())
+}
+
+/// Rescale all entries of a matrix.
+///
+/// * `none`: leave as-is
+/// * `max`: divide by the largest entry
+/// * `minmax`: map the smallest entry to 0 and the largest to 1
+///
+/// A matrix whose scale is 0 (all zeros, or all equal for `minmax`) is left
+/// unchanged.
+pub fn normalize(matrix: &mut [Vec<f64>], mode: &str) -> anyhow::Result<()> {
+    let values = matrix.iter().flatten();
+    let max = values.clone().copied().fold(f64::NEG_INFINITY, f64::max);
+    let min = values.copied().fold(f64::INFINITY, f64::min);
+
+    let (offset, scale) = match mode {
+        "none" => return Ok(()),
+        "max" => (0.0, max),
+        "minmax" => (min, max - min),
+        _ => anyhow::bail!("unknown normalize mode: {}", mode),
+    };
+    if scale == 0.0 || !scale.is_finite() {
+        return Ok(());
+    }
+
+    for v in matrix.iter_mut().flatten() {
+        *v = (*v - offset) / scale;
+    }
+    Ok(())
+}
+
+/// Select the rows/columns of `wanted` taxa.
+///
+/// The submatrix follows the order of `wanted`, or the original order with
+/// `keep_order`. Names absent from the matrix are an error unless `lenient`,
+/// in which case they are skipped.
+pub fn subset(
+    names: &[String],
+    matrix: &[Vec<f64>],
+    wanted: &[String],
+    keep_order: bool,
+    lenient: bool,
+) -> anyhow::Result<(Vec<String>, Vec<Vec<f64>>)> {
+    let index: std::collections::HashMap<&str, usize> = names
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.as_str(), i))
+        .collect();
+
+    let mut picked: Vec<usize> = vec![];
+    for name in wanted {
+        match index.get(name.as_str()) {
+            Some(&i) => {
+                if !picked.contains(&i) {
+                    picked.push(i);
+                }
+            }
+            None if lenient => {}
+            None => anyhow::bail!("taxon not found in matrix: {}", name),
+        }
+    }
+    if keep_order {
+        picked.sort_unstable();
+    }
+
+    let sub_names = picked.iter().map(|&i| names[i].clone()).collect();
+    let sub_matrix = picked
+        .iter()
+        .map(|&i| picked.iter().map(|&j| matrix[i][j]).collect())
+        .collect();
+    Ok((sub_names, sub_matrix))
+}
+
+/// Flatten a matrix to `(i, j, value)` pairs with `i < j`, in row order.
+///
+/// Only pairs accepted by `keep` are returned. With `include_self`, the
+/// diagonal `(i, i)` is emitted too and is subject to the same filter.
+pub fn to_pairs<F>(matrix: &[Vec<f64>], keep: F, include_self: bool) -> Vec<(usize, usize, f64)>
+where
+    F: Fn(f64) -> bool,
+{
+    let mut pairs = vec![];
+    for (i, row) in matrix.iter().enumerate() {
+        let first = if include_self { i } else { i + 1 };
+        for (j, &v) in row.iter().enumerate().skip(first) {
+            if keep(v) {
+                pairs.push((i, j, v));
+            }
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symmetrize_and_normalize() {
+        let mut m = vec![vec![0.0, 2.0], vec![4.0, 0.0]];
+        symmetrize(&mut m, "avg").unwrap();
+        assert_eq!(m, vec![vec![0.0, 3.0], vec![3.0, 0.0]]);
+
+        let mut m = vec![vec![0.0, 2.0], vec![4.0, 0.0]];
+        symmetrize(&mut m, "upper").unwrap();
+        assert_eq!(m[1][0], 2.0);
+
+        normalize(&mut m, "max").unwrap();
+        assert_eq!(m, vec![vec![0.0, 1.0], vec![1.0, 0.0]]);
+
+        let mut m = vec![vec![1.0, 3.0], vec![3.0, 1.0]];
+        normalize(&mut m, "minmax").unwrap();
+        assert_eq!(m, vec![vec![0.0, 1.0], vec![1.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_to_pairs() {
+        let m = vec![
+            vec![0.0, 0.1, 0.8],
+            vec![0.1, 0.0, 0.3],
+            vec![0.8, 0.3, 0.0],
+        ];
+        assert_eq!(
+            to_pairs(&m, |v| v <= 0.5, false),
+            vec![(0, 1, 0.1), (1, 2, 0.3)]
+        );
+        assert_eq!(to_pairs(&m, |_| true, true).len(), 6);
+    }
+}
@@ -345,6 +345,23 @@ impl Psl {
         raw.try_into().unwrap_or(i32::MAX)
     }
 
+    /// Blastz-style chain score computed from the PSL counts alone.
+    ///
+    /// Matching bases score +100 and mismatches or Ns -100 (the default
+    /// identity matrix used by `psl chain`), minus `gap_calc` costs for the
+    /// gaps between consecutive blocks.
+    pub fn blastz_score(&self, gap_calc: &crate::libs::ds::GapCalc) -> f64 {
+        let mut score = 100.0 * (self.match_count as f64 + self.rep_match as f64)
+            - 100.0 * (self.mismatch_count as f64 + self.n_count as f64);
+        for i in 1..self.block_count as usize {
+            let prev_size = self.block_sizes[i - 1];
+            let dt = self.t_starts[i].saturating_sub(self.t_starts[i - 1] + prev_size);
+            let dq = self.q_starts[i].saturating_sub(self.q_starts[i - 1] + prev_size);
+            score -= gap_calc.calc(dq as i32, dt as i32) as f64;
+        }
+        score
+    }
+
     /// Total aligned bases (matches + mismatches + rep_matches + Ns).
     pub fn calc_aligned(&self) -> u32 {
         self.match_count + self.mismatch_count + self.rep_match + self.n_count
@@ -527,7 +544,16 @@ impl Psl {
 
     /// Write this PSL record to a writer in UCSC Chain format.
     pub fn write_chain<W: io::Write>(&self, writer: &mut W, chain_id: u64) -> io::Result<()> {
-        let score = self.score();
+        self.write_chain_with_score(writer, chain_id, self.score() as f64)
+    }
+
+    /// Write this PSL record as a chain with the given score.
+    pub fn write_chain_with_score<W: io::Write>(
+        &self,
+        writer: &mut W,
+        chain_id: u64,
+        score: f64,
+    ) -> io::Result<()> {
         let q_strand_char = self.strand.chars().next().unwrap_or('+');
 
         // Chain format: tStrand is always +, qStrand can be + or -.
@@ -540,7 +566,7 @@ impl Psl {
 
         writeln!(
             writer,
-            "chain {} {} {} + {} {} {} {} {} {} {} {}",
+            "chain {:.0} {} {} + {} {} {} {} {} {} {} {}",
             score,
             self.t_name,
             self.t_size,
@@ -1159,6 +1185,82 @@ pub fn histogram<R: BufRead, W: Write>(
     Ok(())
 }
 
+/// Bin a per-alignment PSL quantity into a histogram, writing `bin_start\tcount`.
+///
+/// `field` is one of `identity`, `coverage`, `score`, `qsize` or `tsize`.
+/// Identity and coverage are binned over `[0, 1]`; other fields over the
+/// observed range. Bins have a fixed `width` when given, otherwise the range is
+/// split into `bins` equal bins. The last bin includes the upper bound. When
+/// `non_zero` is true, empty bins are omitted.
+pub fn histogram_binned<R: BufRead, W: Write>(
+    reader: R,
+    writer: &mut W,
+    field: &str,
+    bins: usize,
+    width: Option<f64>,
+    non_zero: bool,
+) -> anyhow::Result<()> {
+    let mut values: Vec<f64> = Vec::new();
+    for psl in iter_psl(reader) {
+        let psl = psl?;
+        let value = match field {
+            "identity" => psl.ident() as f64,
+            "coverage" => psl.cover() as f64,
+            "score" => psl.score() as f64,
+            "qsize" => psl.q_size as f64,
+            "tsize" => psl.t_size as f64,
+            _ => anyhow::bail!("unsupported histogram field: {}", field),
+        };
+        values.push(value);
+    }
+    if values.is_empty() {
+        return Ok(());
+    }
+
+    let (lo, hi) = if field == "identity" || field == "coverage" {
+        (0.0, 1.0)
+    } else {
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        (min, max)
+    };
+
+    // Tolerance for values that sit on a bin boundary after f32 rounding
+    let eps = 1e-6;
+    let (lo, width, n_bins) = match width {
+        Some(w) => {
+            anyhow::ensure!(w > 0.0, "--width must be positive: {}", w);
+            let lo = (lo / w + eps).floor() * w;
+            let n = (((hi - lo) / w - eps).ceil() as usize).max(1);
+            (lo, w, n)
+        }
+        None => {
+            anyhow::ensure!(bins > 0, "--bins must be positive: {}", bins);
+            let w = if hi > lo {
+                (hi - lo) / bins as f64
+            } else {
+                1.0
+            };
+            (lo, w, bins)
+        }
+    };
+
+    let mut counts = vec![0usize; n_bins];
+    for v in &values {
+        let idx = (((v - lo) / width + eps).floor().max(0.0) as usize).min(n_bins - 1);
+        counts[idx] += 1;
+    }
+
+    for (i, count) in counts.iter().enumerate() {
+        if non_zero && *count == 0 {
+            continue;
+        }
+        let start = ((lo + i as f64 * width) * 1e6).round() / 1e6;
+        writeln!(writer, "{}\t{}", start, count)?;
+    }
+    Ok(())
+}
+
 /// Swap target and query for all PSL records. When `no_rc` is false, records
 /// are reverse-complemented to keep target strand explicit.
 pub fn swap_records<R: BufRead, W: Write>(
@@ -1215,13 +1317,15 @@ pub fn to_ranges<R: BufRead, W: Write>(
 
 /// Convert PSL records to Chain format. When `fix_strand` is true, records
 /// with '-' target strand are reverse-complemented before conversion;
-/// otherwise such records cause an error. `strict` controls parse-failure
+/// otherwise such records cause an error. `score_fn` computes each chain score
+/// (after any strand fix). `strict` controls parse-failure
 /// behavior.
-pub fn to_chain<R: BufRead, W: Write>(
+pub fn to_chain<R: BufRead, W: Write, F: FnMut(&Psl) -> anyhow::Result<f64>>(
     reader: R,
     writer: &mut W,
     fix_strand: bool,
     strict: bool,
+    mut score_fn: F,
 ) -> anyhow::Result<()> {
     let mut chain_id: u64 = 1;
     for line in reader.lines() {
@@ -1249,7 +1353,8 @@ pub fn to_chain<R: BufRead, W: Write>(
                 anyhow::bail!("PSL record has '-' for target strand. Use --fix-strand to fix.");
             }
         }
-        psl.write_chain(writer, chain_id)?;
+        let score = score_fn(&psl)?;
+        psl.write_chain_with_score(writer, chain_id, score)?;
         chain_id += 1;
     }
     Ok(())
@@ -1439,6 +1544,37 @@ mod tests {
         assert_eq!(psl.strand, "+-");
     }
 
+    #[test]
+    fn test_swap_minus_multi_block() {
+        let mut psl = Psl::default();
+        psl.q_name = "q".to_string();
+        psl.t_name = "t".to_string();
+        psl.q_size = 100;
+        psl.t_size = 200;
+        psl.strand = "-".to_string();
+        psl.block_count = 2;
+        psl.block_sizes = vec![10, 20];
+        psl.q_starts = vec![5, 30];
+        psl.t_starts = vec![50, 70];
+        psl.q_start = 50;
+        psl.q_end = 95;
+        psl.t_start = 50;
+        psl.t_end = 90;
+        let orig = psl.clone();
+
+        psl.swap(false);
+        assert_eq!(psl.strand, "-");
+        assert_eq!(psl.block_sizes, vec![20, 10]);
+        // Query (old target) starts are re-based onto its minus strand
+        assert_eq!(psl.q_starts, vec![110, 140]);
+        // Target (old query) starts are back on the plus strand
+        assert_eq!(psl.t_starts, vec![50, 85]);
+        assert_eq!((psl.t_start, psl.t_end), (50, 95));
+
+        psl.swap(false);
+        assert_eq!(psl.to_string(), orig.to_string());
+    }
+
     #[test]
     fn test_swap_translated() {
         let mut psl = Psl::default();
@@ -1554,7 +1690,7 @@ mod tests {
             "10\t0\t0\t0\t0\t0\t0\t0\t+\tq\t100\t10\t20\tt\t200\t50\t60\t1\t10,\t10,\t50,";
         let input = std::io::Cursor::new(psl_line);
         let mut output = Vec::new();
-        to_chain(input, &mut output, false, false).unwrap();
+        to_chain(input, &mut output, false, false, |p| Ok(p.score() as f64)).unwrap();
         let chain = String::from_utf8(output).unwrap();
         assert!(chain.starts_with("chain 10 t 200 + 50 60 q 100 + 10 20 1"));
     }
@@ -1565,9 +1701,20 @@ mod tests {
             "10\t0\t0\t0\t0\t0\t0\t0\t-\tq\t100\t10\t20\tt\t200\t50\t60\t1\t10,\t10,\t50,";
         let input = std::io::Cursor::new(psl_line);
         let mut output = Vec::new();
-        to_chain(input, &mut output, false, false).unwrap();
+        to_chain(input, &mut output, false, false, |p| Ok(p.score() as f64)).unwrap();
         let chain = String::from_utf8(output).unwrap();
         // write_chain reverses qStart/qEnd for negative query strand.
         assert!(chain.starts_with("chain 10 t 200 + 50 60 q 100 - 80 90 1"));
     }
+
+    #[test]
+    fn test_blastz_score() {
+        // Two 10 bp blocks with one mismatch and a 1 bp target gap
+        let psl_line =
+            "19\t1\t0\t0\t0\t0\t1\t1\t+\tq\t20\t0\t20\tt\t21\t0\t21\t2\t10,10,\t0,10,\t0,11,";
+        let psl: Psl = psl_line.parse().unwrap();
+        let gap_calc = crate::libs::ds::GapCalc::loose();
+        // 19 * 100 - 1 * 100 - 350
+        assert_eq!(psl.blastz_score(&gap_calc), 1450.0);
+    }
 }
@@ -1,5 +1,5 @@
 //! VCF (Variant Call Format) header and record writers shared by commands
-//! that emit SNP-only VCF output.
+//! that emit VCF output.
 
 use std::collections::BTreeMap;
 use std::io::Write;
@@ -82,3 +82,44 @@ pub fn write_snp_row<W: Write>(
     writer.write_all(row.as_ref())?;
     Ok(())
 }
+
+/// Write a single VCF row with multi-base alleles (e.g. an anchored indel).
+///
+/// * `ref_allele` — reference allele, including the anchor base.
+/// * `alt_alleles` — deduplicated alternate alleles (no ref).
+/// * `sample_alleles` — one allele per sample; `None` gives a `.` genotype.
+pub fn write_allele_row<W: Write>(
+    writer: &mut W,
+    chrom: &str,
+    pos: i32,
+    ref_allele: &str,
+    alt_alleles: &[String],
+    sample_alleles: &[Option<String>],
+) -> anyhow::Result<()> {
+    let alt_str = if alt_alleles.is_empty() {
+        ".".to_string()
+    } else {
+        alt_alleles.join(",")
+    };
+
+    let mut row = format!(
+        "{}\t{}\t.\t{}\t{}\t.\t.\t.\tGT",
+        chrom, pos, ref_allele, alt_str
+    );
+    for allele in sample_alleles {
+        row.push('\t');
+        let gt = match allele {
+            None => ".".to_string(),
+            Some(a) if a == ref_allele => "0".to_string(),
+            Some(a) => match alt_alleles.iter().position(|x| x == a) {
+                Some(idx) => (idx + 1).to_string(),
+                None => ".".to_string(),
+            },
+        };
+        row.push_str(&gt);
+    }
+
+    row.push('\n');
+    writer.write_all(row.as_ref())?;
+    Ok(())
+}
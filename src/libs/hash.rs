@@ -1,5 +1,6 @@
 use itertools::Itertools;
 use minimizer_iter::MinimizerBuilder;
+use std::collections::HashMap;
 use std::iter::FromIterator;
 
 // These codes were adapted from https://curiouscoding.nl/posts/fast-minimizers/
@@ -123,6 +124,21 @@ pub fn seq_mins(
     Ok(hashset)
 }
 
+/// Check that `kmer` fits the hash width of `hasher`.
+///
+/// The `mod` hasher packs 2-bit bases into a `u64`, so it caps `k` at 32.
+pub fn check_kmer(hasher: &str, kmer: usize) -> anyhow::Result<()> {
+    anyhow::ensure!(kmer > 0, "--kmer must be positive: {}", kmer);
+    if hasher == "mod" {
+        anyhow::ensure!(
+            kmer <= 32,
+            "--kmer {} exceeds the 64-bit hash width of the `mod` hasher (max 32)",
+            kmer
+        );
+    }
+    Ok(())
+}
+
 /// Compute the Mash distance from a Jaccard index and k-mer size.
 ///
 /// See <https://mash.readthedocs.io/en/latest/distances.html#mash-distance-formulation>.
@@ -368,10 +384,287 @@ pub fn load_minimizers(
     Ok(entries)
 }
 
+/// Hash every canonical k-mer (`k <= 32`) of a DNA sequence.
+///
+/// K-mers containing non-ACGT bases are skipped.
+pub fn canonical_kmer_hashes(seq: &[u8], k: usize) -> Vec<u64> {
+    canonical_kmers(seq, k)
+        .into_iter()
+        .map(|canon| rapidhash::rapidhash(&canon.to_le_bytes()))
+        .collect()
+}
+
+/// 2-bit encodings (A=0, C=1, G=2, T=3) of every canonical k-mer (`k <= 32`)
+/// of a DNA sequence.
+///
+/// K-mers containing non-ACGT bases are skipped.
+pub fn canonical_kmers(seq: &[u8], k: usize) -> Vec<u64> {
+    let mask: u64 = if k == 32 {
+        u64::MAX
+    } else {
+        (1u64 << (2 * k)) - 1
+    };
+    let shift = 2 * (k - 1);
+
+    let mut kmers = Vec::with_capacity(seq.len());
+    let mut fwd = 0u64;
+    let mut rev = 0u64;
+    let mut len = 0usize;
+    for &b in seq {
+        let v = crate::libs::nt::NT_VAL[b as usize];
+        if v > 3 {
+            len = 0;
+            continue;
+        }
+        let v = v as u64;
+        fwd = ((fwd << 2) | v) & mask;
+        rev = (rev >> 2) | ((3 - v) << shift);
+        len += 1;
+        if len >= k {
+            kmers.push(fwd.min(rev));
+        }
+    }
+    kmers
+}
+
+// Reverse complement of a 2-bit encoded k-mer.
+fn rev_comp_code(code: u64, k: usize) -> u64 {
+    let mut rc = 0u64;
+    for i in 0..k {
+        rc = (rc << 2) | (3 - ((code >> (2 * i)) & 3));
+    }
+    rc
+}
+
+/// Keep the `size` smallest distinct hashes, sorted ascending (a bottom-k MinHash sketch).
+pub fn bottom_k(mut hashes: Vec<u64>, size: usize) -> Vec<u64> {
+    hashes.sort_unstable();
+    hashes.dedup();
+    hashes.truncate(size);
+    hashes
+}
+
+/// Estimate Jaccard, Containment, and Mash distance from two bottom-k sketches.
+///
+/// Follows Mash: the bottom `size` hashes of the union are the sample, and
+/// `inter`/`union` report the shared and sampled counts.
+pub fn sketch_distances(s1: &[u64], s2: &[u64], size: usize, kmer: usize) -> SetDistances {
+    let mut i = 0;
+    let mut j = 0;
+    let mut sampled = 0;
+    let mut shared = 0;
+    let mut in1 = 0;
+    while sampled < size && (i < s1.len() || j < s2.len()) {
+        if i < s1.len() && j < s2.len() && s1[i] == s2[j] {
+            shared += 1;
+            in1 += 1;
+            i += 1;
+            j += 1;
+        } else if j >= s2.len() || (i < s1.len() && s1[i] < s2[j]) {
+            in1 += 1;
+            i += 1;
+        } else {
+            j += 1;
+        }
+        sampled += 1;
+    }
+
+    let jaccard = if sampled == 0 {
+        0.0
+    } else {
+        shared as f64 / sampled as f64
+    };
+    let containment = if in1 == 0 {
+        0.0
+    } else {
+        shared as f64 / in1 as f64
+    };
+
+    SetDistances {
+        total1: s1.len(),
+        total2: s2.len(),
+        inter: shared,
+        union: sampled,
+        mash: mash_distance(jaccard, kmer),
+        jaccard,
+        containment,
+    }
+}
+
+/// A named bottom-k sketch, compared by `pgr dist seq --minhash`.
+#[derive(Debug, Default, Clone)]
+pub struct SketchEntry {
+    pub name: String,
+    pub sketch: Vec<u64>,
+}
+
+/// Read a FASTA file and build a `SketchEntry` per record (or one merged entry with `is_merge`).
+pub fn load_sketches(
+    infile: &str,
+    kmer: usize,
+    size: usize,
+    is_merge: bool,
+) -> anyhow::Result<Vec<SketchEntry>> {
+    let mut fa_in = crate::libs::fmt::fa::reader(infile)?;
+
+    let mut entries = vec![];
+    let mut all_hashes: Vec<u64> = vec![];
+
+    for result in fa_in.records() {
+        let record = result?;
+
+        let name = String::from_utf8(record.name().into())?;
+        let sketch = bottom_k(canonical_kmer_hashes(&record.sequence()[..], kmer), size);
+
+        if is_merge {
+            all_hashes.extend(sketch);
+        } else {
+            entries.push(SketchEntry { name, sketch });
+        }
+    }
+
+    if is_merge {
+        entries.push(SketchEntry {
+            name: infile.to_string(),
+            sketch: bottom_k(all_hashes, size),
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Canonical k-mer counts and base counts of DNA, compared by `pgr dist seq --d2star`.
+#[derive(Debug, Default, Clone)]
+pub struct ProfileEntry {
+    pub name: String,
+    /// Counts of canonical k-mers, keyed by their 2-bit encoding.
+    pub counts: HashMap<u64, u32>,
+    /// Counts of A, C, G and T.
+    pub bases: [u64; 4],
+}
+
+impl ProfileEntry {
+    /// Add the k-mers and bases of `seq` to the profile.
+    pub fn add_seq(&mut self, seq: &[u8], k: usize) {
+        for code in canonical_kmers(seq, k) {
+            *self.counts.entry(code).or_insert(0) += 1;
+        }
+        for &b in seq {
+            let v = crate::libs::nt::NT_VAL[b as usize];
+            if v <= 3 {
+                self.bases[v] += 1;
+            }
+        }
+    }
+}
+
+/// Read a FASTA file and build a `ProfileEntry` per record (or one merged entry with `is_merge`).
+pub fn load_profiles(
+    infile: &str,
+    kmer: usize,
+    is_merge: bool,
+) -> anyhow::Result<Vec<ProfileEntry>> {
+    let mut fa_in = crate::libs::fmt::fa::reader(infile)?;
+
+    let mut entries = vec![];
+    let mut all = ProfileEntry {
+        name: infile.to_string(),
+        ..Default::default()
+    };
+
+    for result in fa_in.records() {
+        let record = result?;
+        let seq = &record.sequence()[..];
+
+        if is_merge {
+            all.add_seq(seq, kmer);
+        } else {
+            let mut entry = ProfileEntry {
+                name: String::from_utf8(record.name().into())?,
+                ..Default::default()
+            };
+            entry.add_seq(seq, kmer);
+            entries.push(entry);
+        }
+    }
+
+    if is_merge {
+        entries.push(all);
+    }
+
+    Ok(entries)
+}
+
+/// The d2* dissimilarity between two k-mer profiles, in `[0, 1]`.
+///
+/// Counts are centered on their expectation under an i.i.d. background
+/// estimated from the pooled, strand-symmetrized base composition of both
+/// profiles; d2* is `(1 - cos) / 2` of the centered, variance-scaled vectors
+/// (Reinert et al. 2009). Identical profiles give 0. The sums run over the
+/// observed k-mers only, as unobserved words contribute a closed-form term.
+pub fn d2star(p1: &ProfileEntry, p2: &ProfileEntry, kmer: usize) -> f64 {
+    let total: u64 = p1.bases.iter().chain(&p2.bases).sum();
+    if total == 0 {
+        return 0.5;
+    }
+    let at = (p1.bases[0] + p1.bases[3] + p2.bases[0] + p2.bases[3]) as f64 / (2 * total) as f64;
+    let cg = 0.5 - at;
+
+    // Probability of a canonical word: the word or its reverse complement.
+    let prob = |code: u64| {
+        let n_at = (0..kmer)
+            .filter(|i| matches!((code >> (2 * i)) & 3, 0 | 3))
+            .count() as i32;
+        let p = at.powi(n_at) * cg.powi(kmer as i32 - n_at);
+        if code == rev_comp_code(code, kmer) {
+            p
+        } else {
+            2.0 * p
+        }
+    };
+
+    let n1: f64 = p1.counts.values().map(|&c| c as f64).sum();
+    let n2: f64 = p2.counts.values().map(|&c| c as f64).sum();
+
+    let mut dot = -n1 * n2;
+    let mut ss1 = -n1 * n1;
+    for (code, &c1) in &p1.counts {
+        let p = prob(*code);
+        ss1 += (c1 as f64).powi(2) / p;
+        if let Some(&c2) = p2.counts.get(code) {
+            dot += c1 as f64 * c2 as f64 / p;
+        }
+    }
+    let mut ss2 = -n2 * n2;
+    for (code, &c2) in &p2.counts {
+        ss2 += (c2 as f64).powi(2) / prob(*code);
+    }
+
+    if ss1 <= 0.0 || ss2 <= 0.0 {
+        return 0.5;
+    }
+    let cos = dot / (ss1.sqrt() * ss2.sqrt());
+    ((1.0 - cos) / 2.0).clamp(0.0, 1.0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_d2star() {
+        let seq = b"GCTAAAGACAATTACATAACATACACGTCAGCACGAAACTTGTTGGCCCAGTGTG";
+        let mut p1 = ProfileEntry::default();
+        p1.add_seq(seq, 4);
+        let mut p2 = ProfileEntry::default();
+        p2.add_seq(&crate::libs::nt::rev_comp(seq).collect::<Vec<u8>>(), 4);
+
+        assert_eq!(rev_comp_code(0b00_01_10_11, 4), 0b00_01_10_11); // ACGT
+        assert_eq!(rev_comp_code(0b00_00_00_01, 4), 0b10_11_11_11); // AAAC -> GTTT
+        assert!(d2star(&p1, &p1, 4).abs() < 1e-9);
+        assert!(d2star(&p1, &p2, 4).abs() < 1e-9);
+    }
+
     #[test]
     fn test_seq_sketch_basic() {
         let seq = b"ACGTACGT";
@@ -415,6 +708,45 @@ mod tests {
         assert_eq!(mins_mask[1].pos, 8);
     }
 
+    #[test]
+    fn test_check_kmer() {
+        assert!(check_kmer("rapid", 64).is_ok());
+        assert!(check_kmer("mod", 32).is_ok());
+        assert!(check_kmer("mod", 33).is_err());
+        assert!(check_kmer("fx", 0).is_err());
+    }
+
+    #[test]
+    fn test_canonical_kmer_hashes() {
+        let fwd = canonical_kmer_hashes(b"ACGGTTCAG", 5);
+        let rc: Vec<u8> = crate::libs::nt::rev_comp(b"ACGGTTCAG").collect();
+        let mut rev = canonical_kmer_hashes(&rc, 5);
+        rev.reverse();
+        assert_eq!(fwd.len(), 5);
+        assert_eq!(fwd, rev);
+
+        // N breaks the k-mer run
+        assert_eq!(canonical_kmer_hashes(b"ACGNACG", 3).len(), 2);
+    }
+
+    #[test]
+    fn test_sketch_distances() {
+        let s1 = bottom_k(vec![5, 1, 3, 2, 4, 1], 4);
+        assert_eq!(s1, vec![1, 2, 3, 4]);
+
+        let d = sketch_distances(&s1, &s1, 4, 21);
+        assert_eq!(d.jaccard, 1.0);
+        assert_eq!(d.mash, 0.0);
+
+        let s2 = vec![1, 3, 6, 7];
+        // union bottom-4: 1 2 3 4, shared: 1 3
+        let d = sketch_distances(&s1, &s2, 4, 21);
+        assert_eq!(d.inter, 2);
+        assert_eq!(d.union, 4);
+        assert_eq!(d.jaccard, 0.5);
+        assert_eq!(d.containment, 0.5);
+    }
+
     #[test]
     fn test_seq_sketch_strand() {
         // AAAA (fwd) vs TTTT (rev)
@@ -89,13 +89,54 @@ pub fn read_runlist(path: &str) -> anyhow::Result<BTreeMap<String, intspan::IntS
 
 /// Buffered writer that flushes on drop and reports flush errors to stderr.
 ///
-/// Wraps a `BufWriter<Box<dyn Write>>` so that `BufWriter`'s silent flush-on-drop
-/// behavior is replaced with a best-effort flush that emits a warning to stderr
-/// if flushing fails (e.g. broken pipe, disk full). Callers that need to
-/// propagate flush errors should call `flush()?` explicitly before the writer
-/// goes out of scope.
+/// Wraps a `BufWriter` so that `BufWriter`'s silent flush-on-drop behavior is
+/// replaced with a best-effort flush that emits a warning to stderr if
+/// flushing fails (e.g. broken pipe, disk full). Callers that need to
+/// propagate flush errors should call `flush()?` (or [`PgrWriter::finish`] for
+/// writers from [`gz_writer`]) explicitly before the writer goes out of scope.
 pub struct PgrWriter {
-    inner: BufWriter<Box<dyn Write>>,
+    inner: BufWriter<Sink>,
+    finished: bool,
+}
+
+/// Destination of a [`PgrWriter`].
+enum Sink {
+    Plain(Box<dyn Write>),
+    Gz(flate2::write::GzEncoder<File>),
+}
+
+impl Write for Sink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Sink::Plain(w) => w.write(buf),
+            Sink::Gz(w) => w.write(buf),
+        }
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Sink::Plain(w) => w.flush(),
+            Sink::Gz(w) => w.flush(),
+        }
+    }
+}
+
+impl PgrWriter {
+    /// Flush buffered data and, for gzip output, write the gzip trailer.
+    pub fn finish(mut self) -> std::io::Result<()> {
+        self.close()
+    }
+
+    fn close(&mut self) -> std::io::Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+        self.finished = true;
+        self.inner.flush()?;
+        if let Sink::Gz(encoder) = self.inner.get_mut() {
+            encoder.try_finish()?;
+        }
+        Ok(())
+    }
 }
 
 impl Write for PgrWriter {
@@ -109,7 +150,7 @@ impl Write for PgrWriter {
 
 impl Drop for PgrWriter {
     fn drop(&mut self) {
-        if let Err(e) = self.inner.flush() {
+        if let Err(e) = self.close() {
             // Best-effort: report to stderr but never panic from Drop.
             let _ = writeln!(
                 std::io::stderr(),
@@ -133,7 +174,25 @@ pub fn writer(output: &str) -> anyhow::Result<PgrWriter> {
         Box::new(file)
     };
     Ok(PgrWriter {
-        inner: BufWriter::new(boxed),
+        inner: BufWriter::new(Sink::Plain(boxed)),
+        finished: false,
+    })
+}
+
+/// Open a writer like [`writer`], gzip-compressing file paths ending in `.gz`.
+///
+/// Call [`PgrWriter::finish`] to write the gzip trailer and get its error.
+pub fn gz_writer(output: &str) -> anyhow::Result<PgrWriter> {
+    if output == "stdout" || !output.ends_with(".gz") {
+        return writer(output);
+    }
+    let file = File::create(output).with_context(|| format!("could not create {}", output))?;
+    Ok(PgrWriter {
+        inner: BufWriter::new(Sink::Gz(flate2::write::GzEncoder::new(
+            file,
+            flate2::Compression::default(),
+        ))),
+        finished: false,
     })
 }
 
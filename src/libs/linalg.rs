@@ -393,3 +393,338 @@ pub fn vector_score(
 
     Ok(score)
 }
+
+/// A named numeric vector, the basic unit compared by `pgr dist vector`.
+#[derive(Debug, Default, Clone)]
+pub struct VectorEntry {
+    pub name: String,
+    pub list: Vec<f32>,
+}
+
+/// Read `name<TAB>v1,v2,...` lines into `VectorEntry`s.
+///
+/// With `is_binary`, every non-zero value becomes 1. Blank lines are skipped.
+pub fn load_vectors(infile: &str, is_binary: bool) -> anyhow::Result<Vec<VectorEntry>> {
+    let mut entries = vec![];
+    for line in crate::libs::io::read_lines(infile)? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (name, values) = line
+            .split_once('\t')
+            .ok_or_else(|| anyhow::anyhow!("{}: expected name<TAB>values: {}", infile, line))?;
+        let mut list = values
+            .split(',')
+            .map(|v| {
+                v.trim()
+                    .parse::<f32>()
+                    .map_err(|e| anyhow::anyhow!("{}: invalid value {:?}: {}", infile, v, e))
+            })
+            .collect::<anyhow::Result<Vec<f32>>>()?;
+        if is_binary {
+            for v in list.iter_mut() {
+                *v = if *v != 0.0 { 1.0 } else { 0.0 };
+            }
+        }
+        entries.push(VectorEntry {
+            name: name.to_string(),
+            list,
+        });
+    }
+    Ok(entries)
+}
+
+/// Principal component analysis of the rows of `matrix`.
+///
+/// Columns are centered, then the covariance matrix is eigen-decomposed. When
+/// there are fewer rows than columns the (smaller) Gram matrix is used instead;
+/// both give the same projections. Returns the row coordinates on the first
+/// `components` axes and the fraction of total variance each axis explains.
+///
+/// Each axis is oriented so that its largest-magnitude coordinate is positive,
+/// which keeps the output deterministic.
+///
+/// # Examples
+/// ```ignore
+/// let m = vec![vec![1.0, 1.0], vec![2.0, 2.0], vec![3.0, 3.0]];
+/// let (coords, ratios) = pgr::libs::linalg::pca(&m, 1);
+/// assert!((ratios[0] - 1.0).abs() < 1e-9);
+/// assert_eq!(coords.len(), 3);
+/// ```
+pub fn pca(matrix: &[Vec<f64>], components: usize) -> (Vec<Vec<f64>>, Vec<f64>) {
+    let n = matrix.len();
+    if n == 0 {
+        return (vec![], vec![]);
+    }
+    let d = matrix[0].len();
+    let k = components.min(n).min(d);
+
+    // Center columns
+    let mut x: Vec<Vec<f64>> = matrix.to_vec();
+    for j in 0..d {
+        let mean = x.iter().map(|row| row[j]).sum::<f64>() / n as f64;
+        for row in x.iter_mut() {
+            row[j] -= mean;
+        }
+    }
+    let denom = if n > 1 { (n - 1) as f64 } else { 1.0 };
+
+    let mut coords = vec![vec![0.0; k]; n];
+    let mut eigvals: Vec<f64>;
+    if n < d {
+        // Gram matrix: X X^T / (n - 1)
+        let mut gram = vec![vec![0.0; n]; n];
+        for i in 0..n {
+            for j in i..n {
+                let v = x[i].iter().zip(&x[j]).map(|(a, b)| a * b).sum::<f64>() / denom;
+                gram[i][j] = v;
+                gram[j][i] = v;
+            }
+        }
+        let (vals, vecs) = symmetric_eigen(gram);
+        eigvals = vals;
+        for (c, val) in eigvals.iter().take(k).enumerate() {
+            let scale = (val.max(0.0) * denom).sqrt();
+            for (i, row) in coords.iter_mut().enumerate() {
+                row[c] = vecs[i][c] * scale;
+            }
+        }
+    } else {
+        // Covariance matrix: X^T X / (n - 1)
+        let mut cov = vec![vec![0.0; d]; d];
+        for a in 0..d {
+            for b in a..d {
+                let v = x.iter().map(|row| row[a] * row[b]).sum::<f64>() / denom;
+                cov[a][b] = v;
+                cov[b][a] = v;
+            }
+        }
+        let (vals, vecs) = symmetric_eigen(cov);
+        eigvals = vals;
+        for (i, row) in coords.iter_mut().enumerate() {
+            for (c, v) in row.iter_mut().enumerate() {
+                *v = (0..d).map(|j| x[i][j] * vecs[j][c]).sum();
+            }
+        }
+    }
+
+    orient_columns(&mut coords, k);
+
+    for v in eigvals.iter_mut() {
+        *v = v.max(0.0);
+    }
+    let total: f64 = eigvals.iter().sum();
+    let ratios = eigvals
+        .iter()
+        .take(k)
+        .map(|v| if total > 0.0 { v / total } else { 0.0 })
+        .collect();
+
+    (coords, ratios)
+}
+
+/// Classical (metric) multidimensional scaling, also known as PCoA.
+///
+/// Double-centers the squared distance matrix, `B = -1/2 J D^2 J`, and embeds
+/// each taxon on the top `components` eigenvectors of `B` scaled by the square
+/// root of their eigenvalues. Non-Euclidean distances yield negative
+/// eigenvalues; these are clamped to 0 for the coordinates. Returns the
+/// coordinates and all eigenvalues (unclamped, descending) so callers can
+/// report how non-Euclidean the input is.
+///
+/// # Examples
+/// ```ignore
+/// let d = vec![vec![0.0, 1.0, 2.0], vec![1.0, 0.0, 1.0], vec![2.0, 1.0, 0.0]];
+/// let (coords, eigvals) = pgr::libs::linalg::mds(&d, 1);
+/// assert!(((coords[0][0] - coords[2][0]).abs() - 2.0).abs() < 1e-9);
+/// assert!(eigvals[1].abs() < 1e-9);
+/// ```
+pub fn mds(dist: &[Vec<f64>], components: usize) -> (Vec<Vec<f64>>, Vec<f64>) {
+    let n = dist.len();
+    if n == 0 {
+        return (vec![], vec![]);
+    }
+    let k = components.min(n);
+
+    let sq: Vec<Vec<f64>> = dist
+        .iter()
+        .map(|row| row.iter().map(|v| v * v).collect())
+        .collect();
+    let row_means: Vec<f64> = sq
+        .iter()
+        .map(|row| row.iter().sum::<f64>() / n as f64)
+        .collect();
+    let grand_mean = row_means.iter().sum::<f64>() / n as f64;
+
+    let b: Vec<Vec<f64>> = sq
+        .iter()
+        .zip(&row_means)
+        .map(|(row, ri)| {
+            row.iter()
+                .zip(&row_means)
+                .map(|(v, rj)| -0.5 * (v - ri - rj + grand_mean))
+                .collect()
+        })
+        .collect();
+
+    let (eigvals, vecs) = symmetric_eigen(b);
+    let mut coords = vec![vec![0.0; k]; n];
+    for (c, val) in eigvals.iter().take(k).enumerate() {
+        let scale = val.max(0.0).sqrt();
+        for (i, row) in coords.iter_mut().enumerate() {
+            row[c] = vecs[i][c] * scale;
+        }
+    }
+    orient_columns(&mut coords, k);
+
+    (coords, eigvals)
+}
+
+/// Flip each of the first `k` columns so its largest-magnitude entry is
+/// positive, making eigenvector-based outputs deterministic.
+fn orient_columns(coords: &mut [Vec<f64>], k: usize) {
+    for c in 0..k {
+        let pivot =
+            coords
+                .iter()
+                .map(|row| row[c])
+                .fold(0.0f64, |acc, v| if v.abs() > acc.abs() { v } else { acc });
+        if pivot < 0.0 {
+            for row in coords.iter_mut() {
+                row[c] = -row[c];
+            }
+        }
+    }
+}
+
+/// Eigen-decompose a symmetric matrix with the cyclic Jacobi method.
+///
+/// Returns eigenvalues in descending order and the matching eigenvectors as
+/// columns of the second matrix.
+fn symmetric_eigen(mut a: Vec<Vec<f64>>) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = a.len();
+    let mut v = vec![vec![0.0; n]; n];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+
+    for _ in 0..100 {
+        let off: f64 = (0..n)
+            .flat_map(|p| (p + 1..n).map(move |q| (p, q)))
+            .map(|(p, q)| a[p][q] * a[p][q])
+            .sum();
+        if off < 1e-22 {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let akp = row[p];
+                    let akq = row[q];
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (head, tail) = a.split_at_mut(q);
+                for (apk, aqk) in head[p].iter_mut().zip(tail[0].iter_mut()) {
+                    let (x, y) = (*apk, *aqk);
+                    *apk = c * x - s * y;
+                    *aqk = s * x + c * y;
+                }
+                for row in v.iter_mut() {
+                    let vkp = row[p];
+                    let vkq = row[q];
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| a[j][j].total_cmp(&a[i][i]));
+    let vals = order.iter().map(|&i| a[i][i]).collect();
+    let vecs = (0..n)
+        .map(|r| order.iter().map(|&i| v[r][i]).collect())
+        .collect();
+    (vals, vecs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pca_principal_axis() {
+        // Points spread unevenly along y = 2x with small orthogonal noise, so
+        // the largest PC1 score is unique and fixes the axis sign
+        let m: Vec<Vec<f64>> = (0..10)
+            .map(|i| {
+                let t = (i * i) as f64;
+                let noise = if i % 2 == 0 { 0.05 } else { -0.05 };
+                vec![t - 2.0 * noise, 2.0 * t + noise]
+            })
+            .collect();
+        let (coords, ratios) = pca(&m, 2);
+
+        assert_eq!(coords.len(), 10);
+        assert_eq!(ratios.len(), 2);
+        assert!(ratios[0] > 0.99, "{:?}", ratios);
+        assert!((ratios[0] + ratios[1] - 1.0).abs() < 1e-9);
+        // Coordinates along PC1 are monotonic in t and centered
+        let pc1: Vec<f64> = coords.iter().map(|r| r[0]).collect();
+        assert!(pc1.windows(2).all(|w| w[1] > w[0]));
+        assert!(pc1.iter().sum::<f64>().abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mds_points_on_line() {
+        // Taxa at positions 0, 1, 3, 6 on a line
+        let pos = [0.0f64, 1.0, 3.0, 6.0];
+        let d: Vec<Vec<f64>> = pos
+            .iter()
+            .map(|a| pos.iter().map(|b| (a - b).abs()).collect())
+            .collect();
+        let (coords, eigvals) = mds(&d, 2);
+
+        assert_eq!(coords.len(), 4);
+        // A single non-zero eigenvalue: the embedding is 1-D
+        assert!(eigvals[0] > 1.0);
+        assert!(eigvals[1..].iter().all(|v| v.abs() < 1e-9));
+        // Distances along the first axis reproduce the input
+        for (i, ci) in coords.iter().enumerate() {
+            assert!(ci[1].abs() < 1e-6);
+            for (j, cj) in coords.iter().enumerate() {
+                assert!(((ci[0] - cj[0]).abs() - d[i][j]).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pca_gram_matches_covariance() {
+        // 3 rows x 4 columns goes through the Gram path; duplicating the rows
+        // switches to the covariance path without changing the projections.
+        let m = vec![
+            vec![1.0, 2.0, 0.0, 4.0],
+            vec![2.0, 1.0, 1.0, 3.0],
+            vec![4.0, 0.0, 3.0, 1.0],
+        ];
+        let (coords, ratios) = pca(&m, 2);
+        let mut padded = m.clone();
+        padded.extend(m.iter().cloned());
+        let (coords2, ratios2) = pca(&padded, 2);
+
+        for c in 0..2 {
+            assert!((ratios[c] - ratios2[c]).abs() < 1e-9);
+            for i in 0..3 {
+                assert!((coords[i][c] - coords2[i][c]).abs() < 1e-9);
+            }
+        }
+    }
+}
@@ -60,6 +60,194 @@ pub fn create_loc(infile: &str, locfile: &str, is_bgzf: bool) -> anyhow::Result<
     Ok(())
 }
 
+/// One record of a samtools-compatible `.fai` index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaiEntry {
+    pub name: String,
+    /// Number of bases
+    pub length: u64,
+    /// Byte offset of the first base
+    pub offset: u64,
+    /// Bases per line
+    pub line_bases: u64,
+    /// Bytes per line, including the line terminator
+    pub line_width: u64,
+}
+
+impl FaiEntry {
+    /// Byte offset of the 0-based base `pos`.
+    pub fn base_offset(&self, pos: u64) -> u64 {
+        if self.line_bases == 0 {
+            return self.offset;
+        }
+        self.offset + pos / self.line_bases * self.line_width + pos % self.line_bases
+    }
+}
+
+/// Scan a FASTA file once and build `.fai` entries, as `samtools faidx` does.
+///
+/// Within a record every line but the last must have the same length, and
+/// the last may not be longer; otherwise the record can't be indexed and an
+/// error naming the record and line is returned. Offsets of BGZF input are
+/// uncompressed offsets; other `.gz` input is rejected.
+pub fn create_fai(infile: &str) -> anyhow::Result<Vec<FaiEntry>> {
+    if infile.ends_with(".gz") && !crate::is_bgzf(infile) {
+        anyhow::bail!(
+            "{}: gzip input must be BGZF-compressed to be indexed; recompress it with `pgr fa gz`",
+            infile
+        );
+    }
+    let mut reader: Box<dyn std::io::BufRead> = if crate::is_bgzf(infile) {
+        Box::new(bgzf::io::indexed_reader::Builder::default().build_from_path(infile)?)
+    } else {
+        crate::libs::io::reader(infile)?
+    };
+
+    let mut entries: Vec<FaiEntry> = vec![];
+    // Set once a record has a line shorter than `line_bases` (or a blank line)
+    let mut is_closed = false;
+    let mut offset: u64 = 0;
+    let mut line_no = 0usize;
+    let mut line: Vec<u8> = vec![];
+    loop {
+        line.clear();
+        let num = reader.read_until(b'\n', &mut line)?;
+        if num == 0 {
+            break;
+        }
+        line_no += 1;
+        offset += num as u64;
+
+        if let Some(stripped) = line.strip_prefix(b">") {
+            let header = String::from_utf8_lossy(stripped);
+            let name = header
+                .split(|c: char| c.is_ascii_whitespace())
+                .next()
+                .unwrap_or("")
+                .to_string();
+            entries.push(FaiEntry {
+                name,
+                length: 0,
+                offset,
+                line_bases: 0,
+                line_width: 0,
+            });
+            is_closed = false;
+            continue;
+        }
+
+        let Some(entry) = entries.last_mut() else {
+            anyhow::ensure!(
+                line.iter().all(|b| b.is_ascii_whitespace()),
+                "{}: line {}: sequence before the first header",
+                infile,
+                line_no
+            );
+            continue;
+        };
+
+        let width = num as u64;
+        let bases = line
+            .iter()
+            .rev()
+            .skip_while(|&&b| b == b'\n' || b == b'\r')
+            .count() as u64;
+        if bases == 0 {
+            is_closed = true;
+            continue;
+        }
+        anyhow::ensure!(
+            !is_closed,
+            "{}: line {}: record {} has inconsistent line lengths, which faidx can't index",
+            infile,
+            line_no,
+            entry.name
+        );
+
+        if entry.line_bases == 0 {
+            entry.line_bases = bases;
+            entry.line_width = width;
+        } else if bases != entry.line_bases || width != entry.line_width {
+            anyhow::ensure!(
+                bases < entry.line_bases,
+                "{}: line {}: record {} has inconsistent line lengths, which faidx can't index",
+                infile,
+                line_no,
+                entry.name
+            );
+            is_closed = true;
+        }
+        entry.length += bases;
+    }
+
+    Ok(entries)
+}
+
+/// Write `.fai` entries as `name\tlength\toffset\tlinebases\tlinewidth`.
+pub fn write_fai(entries: &[FaiEntry], outfile: &str) -> anyhow::Result<()> {
+    let mut writer = crate::libs::io::writer(outfile)?;
+    for e in entries {
+        writer.write_fmt(format_args!(
+            "{}\t{}\t{}\t{}\t{}\n",
+            e.name, e.length, e.offset, e.line_bases, e.line_width
+        ))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Load a `.fai` index, keyed by sequence name in file order.
+pub fn load_fai(fai_file: &str) -> anyhow::Result<IndexMap<String, FaiEntry>> {
+    let mut fai_of: IndexMap<String, FaiEntry> = IndexMap::new();
+    for line in crate::libs::io::read_lines(fai_file)? {
+        let fields: Vec<&str> = line.trim().split('\t').collect();
+        if fields.len() < 5 {
+            continue;
+        }
+        let parse = |i: usize| -> anyhow::Result<u64> {
+            fields[i]
+                .parse::<u64>()
+                .map_err(|e| anyhow::anyhow!("{}: invalid field {:?}: {}", fai_file, fields[i], e))
+        };
+        let entry = FaiEntry {
+            name: fields[0].to_string(),
+            length: parse(1)?,
+            offset: parse(2)?,
+            line_bases: parse(3)?,
+            line_width: parse(4)?,
+        };
+        fai_of.insert(entry.name.clone(), entry);
+    }
+    Ok(fai_of)
+}
+
+/// Read bases `start..=end` (1-based, clamped by the caller) of an indexed
+/// record, seeking straight to them and dropping line terminators.
+pub fn fetch_fai_bases(
+    reader: &mut Input,
+    entry: &FaiEntry,
+    start: u64,
+    end: u64,
+) -> anyhow::Result<Vec<u8>> {
+    anyhow::ensure!(
+        start >= 1 && start <= end && end <= entry.length,
+        "[{}:{}-{}] is outside {} ({} bp)",
+        entry.name,
+        start,
+        end,
+        entry.name,
+        entry.length
+    );
+    let first = entry.base_offset(start - 1);
+    let last = entry.base_offset(end - 1);
+    let data_buf = read_offset(reader, first, (last - first + 1) as usize)?;
+
+    Ok(data_buf
+        .into_iter()
+        .filter(|&b| b != b'\n' && b != b'\r')
+        .collect())
+}
+
 /// Open a FASTA file as `Input` (plain `File` or BGZF `IndexedReader`).
 pub fn open_input(infile: &str, is_bgzf: bool) -> anyhow::Result<Input> {
     if is_bgzf {
@@ -1,5 +1,6 @@
 pub mod alignment;
 pub mod chain;
+pub mod clust;
 pub mod ds;
 pub mod fas_multiz;
 pub mod fas_xlsx;
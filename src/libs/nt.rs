@@ -262,7 +262,78 @@ pub fn complement<'a>(seq: &'a [u8]) -> impl DoubleEndedIterator<Item = u8> + 'a
 /// let seq_with_iupac = b"MRWSYKVHDBN";
 /// let rev_complemented_iupac: Vec<u8> = pgr::libs::nt::rev_comp(seq_with_iupac).collect();
 /// assert_eq!(rev_complemented_iupac, b"NVHDBMRSWYK"); // Reverse complement of "MRWSYKVHDBN"
+///
+/// // Characters outside `NT_COMP` are kept as-is
+/// let rev_complemented_other: Vec<u8> = pgr::libs::nt::rev_comp(b"A*X").collect();
+/// assert_eq!(rev_complemented_other, b"X*T");
 /// ```
 pub fn rev_comp<'a>(seq: &'a [u8]) -> impl Iterator<Item = u8> + 'a {
-    seq.iter().rev().map(|&b| NT_COMP[b as usize])
+    seq.iter().rev().map(|&b| match NT_COMP[b as usize] {
+        255 => b,
+        c => c,
+    })
+}
+
+/// Translate a nucleotide sequence in one reading frame.
+///
+/// `frame` is 1, 2 or 3 on the forward strand and -1, -2 or -3 on the
+/// reverse complement. `table` is an NCBI translation table id, see
+/// [`crate::libs::translate::codon_table`]. Stop codons become `*`.
+///
+/// ```ignore
+/// assert_eq!(pgr::libs::nt::translate(b"ATGGCCTAA", 1, 1).unwrap(), b"MA*");
+/// ```
+pub fn translate(seq: &[u8], frame: i8, table: u8) -> anyhow::Result<Vec<u8>> {
+    let tab = crate::libs::translate::codon_table(table)
+        .ok_or_else(|| anyhow::anyhow!("unsupported codon table: {}", table))?;
+    anyhow::ensure!(
+        matches!(frame, 1..=3 | -3..=-1),
+        "frame must be 1, 2, 3, -1, -2 or -3: {}",
+        frame
+    );
+
+    let offset = (frame.unsigned_abs() - 1) as usize;
+    let protein = if frame < 0 {
+        let rc: Vec<u8> = rev_comp(seq).collect();
+        crate::libs::translate::translate_with(rc.get(offset..).unwrap_or(&[]), tab)
+    } else {
+        crate::libs::translate::translate_with(seq.get(offset..).unwrap_or(&[]), tab)
+    };
+    Ok(protein.into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rev_comp_ambiguous() {
+        let rc = |seq: &[u8]| rev_comp(seq).collect::<Vec<u8>>();
+        assert_eq!(rc(b"ACGT"), b"ACGT");
+        // R <-> Y, W and S are their own complements
+        assert_eq!(rc(b"RYWS"), b"SWRY");
+        assert_eq!(rc(b"MKVBHDN"), b"NHDVBMK");
+        assert_eq!(rc(b"aRn-"), b"-nYt");
+        // Unknown characters pass through
+        assert_eq!(rc(b"A*X"), b"X*T");
+    }
+
+    #[test]
+    fn test_translate_frames() {
+        // start to stop
+        assert_eq!(translate(b"ATGGCCTAA", 1, 1).unwrap(), b"MA*");
+        assert_eq!(translate(b"CATGGCCTAA", 2, 1).unwrap(), b"MA*");
+        // TTAGGCCAT is the reverse complement of ATGGCCTAA
+        assert_eq!(translate(b"TTAGGCCAT", -1, 1).unwrap(), b"MA*");
+        assert_eq!(translate(b"TTAGGCCATG", -2, 1).unwrap(), b"MA*");
+        // a frame longer than the sequence is empty
+        assert!(translate(b"AT", 3, 1).unwrap().is_empty());
+
+        // vertebrate mitochondrial: ATA is Met, TGA is Trp, AGA is a stop
+        assert_eq!(translate(b"ATATGAAGA", 1, 2).unwrap(), b"MW*");
+        assert_eq!(translate(b"ATATGAAGA", 1, 1).unwrap(), b"I*R");
+
+        assert!(translate(b"ATG", 0, 1).is_err());
+        assert!(translate(b"ATG", 1, 99).is_err());
+    }
 }
@@ -3,12 +3,17 @@
 use cmd_lib::run_cmd;
 use intspan::absolute_path;
 
+/// Resolve `path` to an absolute path string.
+pub fn abs_path(path: &str) -> anyhow::Result<String> {
+    Ok(absolute_path(path)?.display().to_string())
+}
+
 /// Resolve `path` to an absolute path string. `stdout` is passed through as-is.
 pub fn abs_path_or_stdout(path: &str) -> anyhow::Result<String> {
     if path == "stdout" {
         Ok(path.to_string())
     } else {
-        Ok(absolute_path(path)?.display().to_string())
+        abs_path(path)
     }
 }
 
@@ -44,7 +49,7 @@ impl PipelineCtx {
 
     /// Resolve `p` to an absolute path string.
     pub fn abs_path(&self, p: &str) -> anyhow::Result<String> {
-        Ok(absolute_path(p)?.display().to_string())
+        abs_path(p)
     }
 
     /// Switch the current working directory into the tempdir.
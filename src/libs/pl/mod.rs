@@ -1,15 +1,17 @@
 //! Shared helpers for `pgr pl` pipeline subcommands.
 //!
 //! Pure pipeline orchestration logic (no clap dependency): workflow context,
-//! path resolution, and external-tool driver functions (FastK / Profex / spanr).
+//! path resolution, `--dry-run`/`--resume` step plans, and external-tool driver functions
+//! (FastK / Profex / spanr).
 
 mod ctx;
+mod plan;
 mod repeat;
 
-pub use ctx::{abs_path_or_stdout, PipelineCtx};
+pub use ctx::{abs_path, abs_path_or_stdout, PipelineCtx};
+pub use plan::{is_up_to_date, run_command, shell_quote, Plan, PlanStep};
 pub use repeat::{
-    parse_trf_output, run_profex_per_chr, run_repeat_pipeline, run_repeat_spanr_pipeline,
-    RepeatOpts,
+    parse_trf_output, repeat_plan, run_profex_per_chr, run_repeat_pipeline, RepeatOpts,
 };
 
 use std::path::PathBuf;
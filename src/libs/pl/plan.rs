@@ -0,0 +1,212 @@
+//! Step plans for `--dry-run` and `--resume` of pipeline subcommands.
+//!
+//! A plan is the single description of a pipeline: `--dry-run` prints it and
+//! the run executes its commands.
+
+use cmd_lib::run_cmd;
+use std::borrow::Cow;
+use std::io::Write;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// One stage of a pipeline: the commands it runs and the files it creates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanStep {
+    /// Stage name, as printed in the `==>` progress lines.
+    pub name: String,
+    /// Commands of the stage as shell lines; `<key>` marks a placeholder
+    /// filled in at run time.
+    pub commands: Vec<String>,
+    /// Files read by the stage.
+    pub inputs: Vec<String>,
+    /// Intermediate or output files created by the stage.
+    pub outputs: Vec<String>,
+}
+
+impl PlanStep {
+    /// Whether the stage can be skipped; see [`is_up_to_date`].
+    pub fn is_up_to_date(&self) -> bool {
+        is_up_to_date(&self.inputs, &self.outputs)
+    }
+
+    /// Run every command of the stage; see [`run_command`].
+    pub fn run(&self, vars: &[(&str, &str)]) -> anyhow::Result<()> {
+        for cmd in &self.commands {
+            run_command(cmd, vars)?;
+        }
+        Ok(())
+    }
+}
+
+/// Run a plan command with `bash`, after replacing each `<key>` placeholder
+/// with its value from `vars`.
+///
+/// As with `run_cmd!`, a failure anywhere in a pipe fails the command.
+pub fn run_command(command: &str, vars: &[(&str, &str)]) -> anyhow::Result<()> {
+    let line = fill_placeholders(command, vars);
+    run_cmd!(bash -o pipefail -c ${line})?;
+    Ok(())
+}
+
+// Values are shell-quoted, so a placeholder may stand for any string.
+fn fill_placeholders(command: &str, vars: &[(&str, &str)]) -> String {
+    let mut line = command.to_string();
+    for (key, value) in vars {
+        line = line.replace(&format!("<{}>", key), &shell_quote(value));
+    }
+    line
+}
+
+/// Quote `s` for a POSIX shell, unless it only holds characters that need no
+/// quoting.
+pub fn shell_quote(s: &str) -> Cow<'_, str> {
+    let is_plain = |c: char| c.is_ascii_alphanumeric() || "-_./:=+,@%".contains(c);
+    if !s.is_empty() && s.chars().all(is_plain) {
+        Cow::Borrowed(s)
+    } else {
+        Cow::Owned(format!("'{}'", s.replace('\'', "'\\''")))
+    }
+}
+
+/// Make-style freshness check: true when there is at least one output, every
+/// output exists, and none is older than any of the inputs.
+pub fn is_up_to_date<P: AsRef<Path>, Q: AsRef<Path>>(inputs: &[P], outputs: &[Q]) -> bool {
+    let mtime = |p: &Path| std::fs::metadata(p).and_then(|m| m.modified()).ok();
+
+    let mut oldest_output: Option<SystemTime> = None;
+    for out in outputs {
+        match mtime(out.as_ref()) {
+            Some(t) => oldest_output = Some(oldest_output.map_or(t, |o| o.min(t))),
+            None => return false,
+        }
+    }
+    let Some(oldest_output) = oldest_output else {
+        return false;
+    };
+
+    inputs.iter().all(|input| match mtime(input.as_ref()) {
+        Some(t) => t <= oldest_output,
+        None => false,
+    })
+}
+
+/// Ordered list of pipeline stages.
+#[derive(Debug, Clone, Default)]
+pub struct Plan {
+    /// Stages in the order they run.
+    pub steps: Vec<PlanStep>,
+}
+
+impl Plan {
+    /// Create an empty plan.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a stage.
+    pub fn step(&mut self, name: &str, commands: Vec<String>, outputs: Vec<String>) {
+        self.step_with_inputs(name, commands, vec![], outputs);
+    }
+
+    /// Append a stage that declares the files it reads.
+    pub fn step_with_inputs(
+        &mut self,
+        name: &str,
+        commands: Vec<String>,
+        inputs: Vec<String>,
+        outputs: Vec<String>,
+    ) {
+        self.steps.push(PlanStep {
+            name: name.to_string(),
+            commands,
+            inputs,
+            outputs,
+        });
+    }
+
+    /// Write the numbered steps, their commands (`$`) and created files (`+`).
+    pub fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        for (i, step) in self.steps.iter().enumerate() {
+            writeln!(writer, "{}. {}", i + 1, step.name)?;
+            for cmd in &step.commands {
+                writeln!(writer, "    $ {}", cmd)?;
+            }
+            for out in &step.outputs {
+                writeln!(writer, "    + {}", out)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_write() {
+        let mut plan = Plan::new();
+        plan.step(
+            "pgr fa size",
+            vec!["pgr fa size in.fa -o chr.sizes".to_string()],
+            vec!["chr.sizes".to_string()],
+        );
+        plan.step("spanr cover", vec!["spanr cover *.rg".to_string()], vec![]);
+
+        let mut buf = Vec::new();
+        plan.write(&mut buf).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "1. pgr fa size\n    $ pgr fa size in.fa -o chr.sizes\n    + chr.sizes\n2. spanr cover\n    $ spanr cover *.rg\n"
+        );
+    }
+
+    #[test]
+    fn test_fill_placeholders() {
+        assert_eq!(shell_quote("chr1.fa"), "chr1.fa");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+
+        let vars = [("chr", "I"), ("i", "0")];
+        assert_eq!(
+            fill_placeholders("trf <chr>.fa > trf.<i>.dat", &vars),
+            "trf I.fa > trf.0.dat"
+        );
+        assert_eq!(
+            fill_placeholders("fas cover --name <target_name>", &[("target_name", "a b")]),
+            "fas cover --name 'a b'"
+        );
+    }
+
+    #[test]
+    fn test_is_up_to_date() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("in.txt");
+        let output = dir.path().join("out.txt");
+
+        std::fs::write(&input, "in").unwrap();
+        assert!(!is_up_to_date(&[&input], &[&output]));
+
+        std::fs::write(&output, "out").unwrap();
+        let older = SystemTime::now() - std::time::Duration::from_secs(60);
+        std::fs::File::options()
+            .write(true)
+            .open(&input)
+            .unwrap()
+            .set_modified(older)
+            .unwrap();
+        assert!(is_up_to_date(&[&input], &[&output]));
+
+        // A newer input makes the output stale
+        std::fs::File::options()
+            .write(true)
+            .open(&input)
+            .unwrap()
+            .set_modified(SystemTime::now() + std::time::Duration::from_secs(60))
+            .unwrap();
+        assert!(!is_up_to_date(&[&input], &[&output]));
+
+        // No outputs means nothing to reuse
+        assert!(!is_up_to_date::<_, &Path>(&[&input], &[]));
+    }
+}
@@ -3,24 +3,22 @@
 use cmd_lib::run_cmd;
 use std::io::{BufRead, Write};
 
-/// Run `Profex -z genome` per chromosome and write `.rg` files.
+/// Run the Profex command of the plan per chromosome and write `.rg` files.
 ///
-/// For each chromosome, runs `Profex -z genome <sn>` writing `prof.<sn>.txt`,
-/// then scans lines with `re_prof` capturing `start` and `end` (1-based inclusive
-/// in output). If `min_depth` is set and the regex has a `depth` capture group,
-/// entries with depth below the threshold are skipped. Returns the list of
-/// `prof.<sn>.rg` file names.
+/// For each chromosome, runs `profex_cmd` with `<sn>` set to its serial
+/// number, which writes `prof.<sn>.txt`, then scans lines with `re_prof`
+/// capturing `start` and `end` (1-based inclusive in output). If `min_depth`
+/// is set and the regex has a `depth` capture group, entries with depth below
+/// the threshold are skipped. Each chromosome gets a `prof.<sn>.rg` file.
 pub fn run_profex_per_chr(
     chrs: &[String],
+    profex_cmd: &str,
     re_prof: &regex::Regex,
     min_depth: Option<usize>,
-) -> anyhow::Result<Vec<String>> {
-    let mut rg_files = vec![];
+) -> anyhow::Result<()> {
     for (i, chr) in chrs.iter().enumerate() {
         let sn = i + 1;
-        run_cmd!(
-            Profex -z genome ${sn} > prof.${sn}.txt
-        )?;
+        super::run_command(profex_cmd, &[("sn", &sn.to_string())])?;
 
         let reader = crate::reader(&format!("prof.{}.txt", sn))?;
 
@@ -49,9 +47,9 @@ pub fn run_profex_per_chr(
 
             writer.write_fmt(format_args!("{}:{}-{}\n", chr, start, end))?;
         }
-        rg_files.push(rg_file);
+        writer.flush()?;
     }
-    Ok(rg_files)
+    Ok(())
 }
 
 /// Options for the shared repeat-identification pipeline (ir/rept).
@@ -76,66 +74,97 @@ pub struct RepeatOpts {
 
 /// Run the shared FastK → Profex → spanr repeat pipeline.
 ///
-/// When `opts.abs_repeat` is set, runs FastK twice (repeat + genome with
-/// `-p:repeat`); otherwise runs FastK once on the genome (`-p`). Then
-/// generates `chr.sizes`, runs Profex per chromosome, and finally the
-/// spanr cover/fill/excise/fill pipeline.
+/// Runs the steps of [`repeat_plan`] in order. The per-chromosome step
+/// generates `chr.sizes` and runs Profex on each chromosome.
 pub fn run_repeat_pipeline(opts: &RepeatOpts) -> anyhow::Result<()> {
-    let pgr = &opts.pgr;
-    let abs_infile = &opts.abs_infile;
-    let opt_kmer = opts.opt_kmer;
+    for step in &repeat_plan(opts).steps {
+        let name = &step.name;
+        run_cmd!(info "==> ${name}")?;
+        if step.name != PER_CHR_STEP {
+            step.run(&[])?;
+            continue;
+        }
 
-    if let Some(abs_repeat) = &opts.abs_repeat {
-        run_cmd!(info "==> FastK on repeat")?;
-        run_cmd!(
-            FastK -t -k${opt_kmer} -Nrepeat ${abs_repeat}
-        )?;
-        run_cmd!(info "==> FastK on genome")?;
-        run_cmd!(
-            FastK -p:repeat -k${opt_kmer} -Ngenome ${abs_infile}
-        )?;
-    } else {
-        run_cmd!(info "==> FastK")?;
-        run_cmd!(
-            FastK -p -k${opt_kmer} -Ngenome ${abs_infile}
-        )?;
+        let [sizes_cmd, profex_cmd] = step.commands.as_slice() else {
+            anyhow::bail!("unexpected commands of step {}", step.name);
+        };
+        super::run_command(sizes_cmd, &[])?;
+        let chrs = crate::libs::io::read_names::<Vec<String>>("chr.sizes")?;
+        run_profex_per_chr(&chrs, profex_cmd, &opts.re_prof, opts.min_depth)?;
     }
 
-    run_cmd!(info "==> Process each chromosome")?;
-    run_cmd!(
-        ${pgr} fa size ${abs_infile} -o chr.sizes
-    )?;
-    let chrs = crate::libs::io::read_names::<Vec<String>>("chr.sizes")?;
-
-    let rg_files = run_profex_per_chr(&chrs, &opts.re_prof, opts.min_depth)?;
-
-    run_repeat_spanr_pipeline(
-        &rg_files,
-        opts.opt_fk,
-        opts.opt_min,
-        opts.opt_ff,
-        &opts.abs_outfile,
-    )?;
-
     Ok(())
 }
 
-/// Run the spanr cover → fill → excise → fill pipeline on `rg_files`.
-pub fn run_repeat_spanr_pipeline(
-    rg_files: &[String],
-    fk: usize,
-    min: usize,
-    ff: usize,
-    abs_outfile: &str,
-) -> anyhow::Result<()> {
-    run_cmd!(info "==> Outputs")?;
-    run_cmd!(
-        spanr cover $[rg_files] |
-            spanr span --op fill -n ${fk} stdin |
-            spanr span --op excise -n ${min} stdin |
-            spanr span --op fill -n ${ff} stdin -o ${abs_outfile}
-    )?;
-    Ok(())
+// Name of the step that runs Profex on each chromosome.
+const PER_CHR_STEP: &str = "Process each chromosome";
+
+/// Steps of [`run_repeat_pipeline`], also printed by `--dry-run`.
+///
+/// When `opts.abs_repeat` is set, runs FastK twice (repeat + genome with
+/// `-p:repeat`); otherwise runs FastK once on the genome (`-p`). The last
+/// step is the spanr cover/fill/excise/fill pipeline.
+///
+/// Intermediate files live in the pipeline tempdir, shown as `<tempdir>`;
+/// per-chromosome commands and files use the placeholder `<sn>` (1-based
+/// serial number).
+pub fn repeat_plan(opts: &RepeatOpts) -> super::Plan {
+    let pgr = super::shell_quote(&opts.pgr);
+    let abs_infile = super::shell_quote(&opts.abs_infile);
+    let k = opts.opt_kmer;
+
+    let mut plan = super::Plan::new();
+    if let Some(abs_repeat) = &opts.abs_repeat {
+        plan.step(
+            "FastK on repeat",
+            vec![format!(
+                "FastK -t -k{} -Nrepeat {}",
+                k,
+                super::shell_quote(abs_repeat)
+            )],
+            vec!["<tempdir>/repeat.ktab".to_string()],
+        );
+        plan.step(
+            "FastK on genome",
+            vec![format!("FastK -p:repeat -k{} -Ngenome {}", k, abs_infile)],
+            vec!["<tempdir>/genome.prof".to_string()],
+        );
+    } else {
+        plan.step(
+            "FastK",
+            vec![format!("FastK -p -k{} -Ngenome {}", k, abs_infile)],
+            vec!["<tempdir>/genome.prof".to_string()],
+        );
+    }
+    plan.step(
+        PER_CHR_STEP,
+        vec![
+            format!("{} fa size {} -o chr.sizes", pgr, abs_infile),
+            "Profex -z genome <sn> > prof.<sn>.txt".to_string(),
+        ],
+        vec![
+            "<tempdir>/chr.sizes".to_string(),
+            "<tempdir>/prof.<sn>.txt".to_string(),
+            "<tempdir>/prof.<sn>.rg".to_string(),
+        ],
+    );
+    let outputs = if opts.abs_outfile == "stdout" {
+        vec![]
+    } else {
+        vec![opts.abs_outfile.clone()]
+    };
+    plan.step(
+        "Outputs",
+        vec![format!(
+            "spanr cover prof.*.rg | spanr span --op fill -n {} stdin | spanr span --op excise -n {} stdin | spanr span --op fill -n {} stdin -o {}",
+            opts.opt_fk,
+            opts.opt_min,
+            opts.opt_ff,
+            super::shell_quote(&opts.abs_outfile)
+        )],
+        outputs,
+    );
+    plan
 }
 
 /// Parse a TRF `.dat` file and write `chr:start-end` lines to `writer`.
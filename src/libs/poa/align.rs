@@ -1,6 +1,9 @@
 use super::graph::PoaGraph;
 use petgraph::graph::NodeIndex;
 use std::collections::HashMap;
+use std::ops::{Index, IndexMut};
+
+const NEG_INF: i32 = -1_000_000_000;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AlignmentType {
@@ -52,43 +55,154 @@ impl ScalarAlignmentEngine {
 
 impl AlignmentEngine for ScalarAlignmentEngine {
     fn align(&self, sequence: &[u8], graph: &PoaGraph) -> Alignment {
-        let sorted_nodes = graph.topological_sort();
-        let n_nodes = sorted_nodes.len();
-        let n_seq = sequence.len();
-
-        if n_nodes == 0 {
-            let mut path = Vec::new();
-            for i in 0..n_seq {
-                path.push((Some(i), None));
-            }
-            return Alignment { score: 0, path };
+        align_in_band(&self.params, self.align_type, sequence, graph, None).0
+    }
+}
+
+/// Restricts the DP to a diagonal band around the expected path.
+///
+/// Node `i` of the topological order is expected to pair with sequence column
+/// `(i + 1) * seq_len / nodes`; only columns within `band` of it are computed, so memory
+/// grows as O(nodes × band) instead of O(nodes × seq_len). When the traceback
+/// touches the band edge the result may be suboptimal, and the engine falls back
+/// to the full alignment of [`ScalarAlignmentEngine`].
+pub struct BandedAlignmentEngine {
+    pub params: AlignmentParams,
+    pub align_type: AlignmentType,
+    pub band: usize,
+}
+
+impl BandedAlignmentEngine {
+    pub fn new(params: AlignmentParams, align_type: AlignmentType, band: usize) -> Self {
+        Self {
+            params,
+            align_type,
+            band,
         }
+    }
+}
 
-        let node_map: HashMap<NodeIndex, usize> = sorted_nodes
-            .iter()
-            .enumerate()
-            .map(|(i, &n)| (n, i))
-            .collect();
+impl AlignmentEngine for BandedAlignmentEngine {
+    fn align(&self, sequence: &[u8], graph: &PoaGraph) -> Alignment {
+        let (alignment, hit_edge) = align_in_band(
+            &self.params,
+            self.align_type,
+            sequence,
+            graph,
+            Some(self.band),
+        );
+        if hit_edge {
+            align_in_band(&self.params, self.align_type, sequence, graph, None).0
+        } else {
+            alignment
+        }
+    }
+}
+
+/// One DP row stored only over its band `lo..=hi`; cells outside read as `NEG_INF`.
+struct BandRow {
+    lo: usize,
+    cells: Vec<i32>,
+}
+
+impl BandRow {
+    fn new(lo: usize, hi: usize) -> Self {
+        Self {
+            lo,
+            cells: vec![NEG_INF; hi + 1 - lo],
+        }
+    }
+}
 
-        let neg_inf = -1_000_000_000;
+impl Index<usize> for BandRow {
+    type Output = i32;
 
-        // Matrices: [node_idx_linear][seq_idx]
-        let mut m = vec![vec![neg_inf; n_seq + 1]; n_nodes];
-        let mut e = vec![vec![neg_inf; n_seq + 1]; n_nodes];
-        let mut f = vec![vec![neg_inf; n_seq + 1]; n_nodes];
+    fn index(&self, j: usize) -> &i32 {
+        if j < self.lo {
+            return &NEG_INF;
+        }
+        self.cells.get(j - self.lo).unwrap_or(&NEG_INF)
+    }
+}
+
+impl IndexMut<usize> for BandRow {
+    fn index_mut(&mut self, j: usize) -> &mut i32 {
+        &mut self.cells[j - self.lo]
+    }
+}
+
+/// Column range `(lo, hi)` computed for each node in topological order.
+/// `None` means the full range `0..=n_seq`.
+fn band_bounds(n_nodes: usize, n_seq: usize, band: Option<usize>) -> Vec<(usize, usize)> {
+    (0..n_nodes)
+        .map(|i| match band {
+            Some(band) => {
+                // Column `j` has consumed `j` bases, as row `i` has consumed `i + 1` nodes
+                let diag = (i + 1) * n_seq / n_nodes;
+                (diag.saturating_sub(band), (diag + band).min(n_seq))
+            }
+            None => (0, n_seq),
+        })
+        .collect()
+}
+
+/// Aligns `sequence` to `graph`, computing only the cells inside the band.
+///
+/// Returns the alignment and whether its traceback touched the band edge (or found
+/// no valid end cell), in which case a wider band may give a better result.
+fn align_in_band(
+    params: &AlignmentParams,
+    align_type: AlignmentType,
+    sequence: &[u8],
+    graph: &PoaGraph,
+    band: Option<usize>,
+) -> (Alignment, bool) {
+    let sorted_nodes = graph.topological_sort();
+    let n_nodes = sorted_nodes.len();
+    let n_seq = sequence.len();
+
+    if n_nodes == 0 {
+        let mut path = Vec::new();
+        for i in 0..n_seq {
+            path.push((Some(i), None));
+        }
+        return (Alignment { score: 0, path }, false);
+    }
 
-        let is_local = self.align_type == AlignmentType::Local;
-        let is_semi = self.align_type == AlignmentType::SemiGlobal;
+    let node_map: HashMap<NodeIndex, usize> = sorted_nodes
+        .iter()
+        .enumerate()
+        .map(|(i, &n)| (n, i))
+        .collect();
 
-        // Initialization
-        for (i, &node_idx) in sorted_nodes.iter().enumerate() {
-            let preds: Vec<NodeIndex> = graph
-                .graph
-                .neighbors_directed(node_idx, petgraph::Direction::Incoming)
-                .collect();
-            let is_start_node = preds.is_empty();
+    let neg_inf = NEG_INF;
 
-            // 1. Initialize Column 0 (Sequence Empty)
+    // Matrices: [node_idx_linear][seq_idx], each row stored over its band only
+    let bounds = band_bounds(n_nodes, n_seq, band);
+    let new_matrix = || -> Vec<BandRow> {
+        bounds
+            .iter()
+            .map(|&(lo, hi)| BandRow::new(lo, hi))
+            .collect()
+    };
+    let mut m = new_matrix();
+    let mut e = new_matrix();
+    let mut f = new_matrix();
+
+    let is_local = align_type == AlignmentType::Local;
+    let is_semi = align_type == AlignmentType::SemiGlobal;
+
+    // Initialization
+    for (i, &node_idx) in sorted_nodes.iter().enumerate() {
+        let preds: Vec<NodeIndex> = graph
+            .graph
+            .neighbors_directed(node_idx, petgraph::Direction::Incoming)
+            .collect();
+        let is_start_node = preds.is_empty();
+        let (lo, hi) = bounds[i];
+
+        // 1. Initialize Column 0 (Sequence Empty), unless it lies outside the band
+        if lo == 0 {
             if is_local || is_semi {
                 // Free start in graph: 0 cost to reach any node with empty sequence
                 f[i][0] = 0;
@@ -96,7 +210,7 @@ impl AlignmentEngine for ScalarAlignmentEngine {
                 e[i][0] = neg_inf;
             } else {
                 if is_start_node {
-                    f[i][0] = self.params.gap_open;
+                    f[i][0] = params.gap_open;
                     m[i][0] = neg_inf;
                     e[i][0] = neg_inf;
                 } else {
@@ -110,7 +224,7 @@ impl AlignmentEngine for ScalarAlignmentEngine {
                         }
                     }
                     if max_prev > neg_inf {
-                        f[i][0] = max_prev + self.params.gap_extend;
+                        f[i][0] = max_prev + params.gap_extend;
                     } else {
                         f[i][0] = neg_inf;
                     }
@@ -118,372 +232,382 @@ impl AlignmentEngine for ScalarAlignmentEngine {
                     e[i][0] = neg_inf;
                 }
             }
+        }
 
-            // 2. Fill rest of columns
-            let node_base = graph.graph[node_idx].base;
+        // 2. Fill rest of columns
+        let node_base = graph.graph[node_idx].base;
 
-            for j in 1..=n_seq {
-                let seq_base = sequence[j - 1];
-                let match_score = if seq_base == node_base {
-                    self.params.match_score
-                } else {
-                    self.params.mismatch_score
-                };
+        for j in lo.max(1)..=hi {
+            let seq_base = sequence[j - 1];
+            let match_score = if seq_base == node_base {
+                params.match_score
+            } else {
+                params.mismatch_score
+            };
 
-                // E[i][j]: Insertion
-                // Derived from M (gap open), E (gap extend), or F (gap open)
-                let from_m = if m[i][j - 1] > neg_inf {
-                    m[i][j - 1] + self.params.gap_open
-                } else {
-                    neg_inf
-                };
-                let from_e = if e[i][j - 1] > neg_inf {
-                    e[i][j - 1] + self.params.gap_extend
-                } else {
-                    neg_inf
-                };
-                let from_f = if f[i][j - 1] > neg_inf {
-                    f[i][j - 1] + self.params.gap_open
-                } else {
-                    neg_inf
-                };
+            // E[i][j]: Insertion
+            // Derived from M (gap open), E (gap extend), or F (gap open)
+            let from_m = if m[i][j - 1] > neg_inf {
+                m[i][j - 1] + params.gap_open
+            } else {
+                neg_inf
+            };
+            let from_e = if e[i][j - 1] > neg_inf {
+                e[i][j - 1] + params.gap_extend
+            } else {
+                neg_inf
+            };
+            let from_f = if f[i][j - 1] > neg_inf {
+                f[i][j - 1] + params.gap_open
+            } else {
+                neg_inf
+            };
 
-                let mut max_e = from_m.max(from_e).max(from_f);
-                if is_local && max_e < 0 {
-                    max_e = neg_inf;
-                }
-                if max_e < neg_inf / 2 {
-                    max_e = neg_inf;
-                }
-                e[i][j] = max_e;
+            let mut max_e = from_m.max(from_e).max(from_f);
+            if is_local && max_e < 0 {
+                max_e = neg_inf;
+            }
+            if max_e < neg_inf / 2 {
+                max_e = neg_inf;
+            }
+            e[i][j] = max_e;
 
-                // M[i][j]: Match/Mismatch
-                let mut max_m = neg_inf;
+            // M[i][j]: Match/Mismatch
+            let mut max_m = neg_inf;
 
-                if is_start_node {
-                    if j == 1 {
-                        // Transition from virtual root
-                        max_m = match_score;
-                    } else {
-                        // Gap from virtual root
-                        // We have j-1 bases before current one.
-                        // They are all insertions.
-                        // Cost = Open + (cnt-1)*Extend.
-                        // cnt = j-1.
-                        // Cost = Open + (j-2)*Extend.
-                        let ins_score =
-                            self.params.gap_open + (j as i32 - 2) * self.params.gap_extend;
-                        max_m = ins_score + match_score;
-                    }
+            if is_start_node {
+                if j == 1 {
+                    // Transition from virtual root
+                    max_m = match_score;
                 } else {
-                    for &pred in &preds {
-                        let u = node_map[&pred];
-                        let src = m[u][j - 1].max(e[u][j - 1]).max(f[u][j - 1]);
-                        if src > neg_inf {
-                            max_m = max_m.max(src + match_score);
-                        }
-                    }
-                    // For Semi/Local: Free start allows starting here from virtual root
-                    if (is_local || is_semi) && j == 1 {
-                        max_m = max_m.max(match_score);
-                    }
-                }
-
-                if is_local && max_m < 0 {
-                    max_m = 0;
+                    // Gap from virtual root
+                    // We have j-1 bases before current one.
+                    // They are all insertions.
+                    // Cost = Open + (cnt-1)*Extend.
+                    // cnt = j-1.
+                    // Cost = Open + (j-2)*Extend.
+                    let ins_score = params.gap_open + (j as i32 - 2) * params.gap_extend;
+                    max_m = ins_score + match_score;
                 }
-                m[i][j] = max_m;
-
-                // F[i][j]: Deletion
-                let mut max_f = neg_inf;
-                if !is_start_node {
-                    for &pred in &preds {
-                        let u = node_map[&pred];
-                        let from_m = if m[u][j] > neg_inf {
-                            m[u][j] + self.params.gap_open
-                        } else {
-                            neg_inf
-                        };
-                        let from_f = if f[u][j] > neg_inf {
-                            f[u][j] + self.params.gap_extend
-                        } else {
-                            neg_inf
-                        };
-                        let from_e = if e[u][j] > neg_inf {
-                            e[u][j] + self.params.gap_open
-                        } else {
-                            neg_inf
-                        };
-
-                        max_f = max_f.max(from_m).max(from_f).max(from_e);
+            } else {
+                for &pred in &preds {
+                    let u = node_map[&pred];
+                    let src = m[u][j - 1].max(e[u][j - 1]).max(f[u][j - 1]);
+                    if src > neg_inf {
+                        max_m = max_m.max(src + match_score);
                     }
                 }
-                // For Semi/Local, F[i][j] coming from virtual root (skipping nodes) is handled by F[i][0] init and propagation?
-                // No, F[i][0]=0. F[i][j] means we have consumed seq[0..j].
-                // If we delete node i, we move from (pred, j) -> (i, j).
-                // So F[i][j] depends on predecessors at j.
-
-                if is_local && max_f < 0 {
-                    max_f = neg_inf;
-                }
-                if max_f < neg_inf / 2 {
-                    max_f = neg_inf;
+                // For Semi/Local: Free start allows starting here from virtual root
+                if (is_local || is_semi) && j == 1 {
+                    max_m = max_m.max(match_score);
                 }
-                f[i][j] = max_f;
             }
-        }
 
-        // Find best end score
-        let mut best_score = neg_inf;
-        let mut best_node_idx = 0;
-        let mut best_col = n_seq;
-        let mut best_state = 0;
+            if is_local && max_m < 0 {
+                max_m = 0;
+            }
+            m[i][j] = max_m;
+
+            // F[i][j]: Deletion
+            let mut max_f = neg_inf;
+            if !is_start_node {
+                for &pred in &preds {
+                    let u = node_map[&pred];
+                    let from_m = if m[u][j] > neg_inf {
+                        m[u][j] + params.gap_open
+                    } else {
+                        neg_inf
+                    };
+                    let from_f = if f[u][j] > neg_inf {
+                        f[u][j] + params.gap_extend
+                    } else {
+                        neg_inf
+                    };
+                    let from_e = if e[u][j] > neg_inf {
+                        e[u][j] + params.gap_open
+                    } else {
+                        neg_inf
+                    };
 
-        if is_local {
-            // Check all cells
-            best_score = 0;
-            for i in 0..n_nodes {
-                for j in 1..=n_seq {
-                    let score = m[i][j].max(e[i][j]).max(f[i][j]);
-                    if score >= best_score {
-                        // Use >= to pick last occurrence? or >?
-                        best_score = score;
-                        best_node_idx = i;
-                        best_col = j;
-                        if score == m[i][j] {
-                            best_state = 0;
-                        } else if score == e[i][j] {
-                            best_state = 1;
-                        } else {
-                            best_state = 2;
-                        }
-                    }
+                    max_f = max_f.max(from_m).max(from_f).max(from_e);
                 }
             }
-        } else if is_semi {
-            // Check all nodes at last column
-            for i in 0..n_nodes {
-                let score = m[i][n_seq].max(e[i][n_seq]).max(f[i][n_seq]);
-                if score > best_score {
+            // For Semi/Local, F[i][j] coming from virtual root (skipping nodes) is handled by F[i][0] init and propagation?
+            // No, F[i][0]=0. F[i][j] means we have consumed seq[0..j].
+            // If we delete node i, we move from (pred, j) -> (i, j).
+            // So F[i][j] depends on predecessors at j.
+
+            if is_local && max_f < 0 {
+                max_f = neg_inf;
+            }
+            if max_f < neg_inf / 2 {
+                max_f = neg_inf;
+            }
+            f[i][j] = max_f;
+        }
+    }
+
+    // Find best end score
+    let mut best_score = neg_inf;
+    let mut best_node_idx = 0;
+    let mut best_col = n_seq;
+    let mut best_state = 0;
+
+    if is_local {
+        // Check all cells
+        best_score = 0;
+        for i in 0..n_nodes {
+            for j in bounds[i].0.max(1)..=bounds[i].1 {
+                let score = m[i][j].max(e[i][j]).max(f[i][j]);
+                if score >= best_score {
+                    // Use >= to pick last occurrence? or >?
                     best_score = score;
                     best_node_idx = i;
-                    best_col = n_seq;
-                    if score == m[i][n_seq] {
+                    best_col = j;
+                    if score == m[i][j] {
                         best_state = 0;
-                    } else if score == e[i][n_seq] {
+                    } else if score == e[i][j] {
                         best_state = 1;
                     } else {
                         best_state = 2;
                     }
                 }
             }
-        } else {
-            // Global: Check all nodes at last column (Free end in graph)
-            // This allows the sequence to end before the graph ends without penalty (Semi-Global in Target)
-            // which is consistent with Spoa behavior for consensus.
-            for i in 0..n_nodes {
-                let score = m[i][n_seq].max(e[i][n_seq]).max(f[i][n_seq]);
-                if score > best_score {
-                    best_score = score;
-                    best_node_idx = i;
-                    best_col = n_seq;
-                    if score == m[i][n_seq] {
-                        best_state = 0;
-                    } else if score == e[i][n_seq] {
-                        best_state = 1;
-                    } else {
-                        best_state = 2;
-                    }
+        }
+    } else if is_semi {
+        // Check all nodes at last column
+        for i in 0..n_nodes {
+            let score = m[i][n_seq].max(e[i][n_seq]).max(f[i][n_seq]);
+            if score > best_score {
+                best_score = score;
+                best_node_idx = i;
+                best_col = n_seq;
+                if score == m[i][n_seq] {
+                    best_state = 0;
+                } else if score == e[i][n_seq] {
+                    best_state = 1;
+                } else {
+                    best_state = 2;
                 }
             }
         }
-
-        // Backtracking
-        let mut path = Vec::new();
-        let mut curr_i = best_node_idx;
-        let mut curr_j = best_col;
-        let mut curr_state = best_state;
-
-        while curr_j > 0 || (curr_i > 0) {
-            let node_idx = sorted_nodes[curr_i];
-            let preds: Vec<NodeIndex> = graph
-                .graph
-                .neighbors_directed(node_idx, petgraph::Direction::Incoming)
-                .collect();
-            let is_start = preds.is_empty();
-
-            // Stop conditions
-            if is_local && best_score == 0 {
-                break;
-            } // If score 0, stop
-            if is_local {
-                let s = match curr_state {
-                    0 => m[curr_i][curr_j],
-                    1 => e[curr_i][curr_j],
-                    _ => f[curr_i][curr_j],
-                };
-                if s <= 0 {
-                    break;
+    } else {
+        // Global: Check all nodes at last column (Free end in graph)
+        // This allows the sequence to end before the graph ends without penalty (Semi-Global in Target)
+        // which is consistent with Spoa behavior for consensus.
+        for i in 0..n_nodes {
+            let score = m[i][n_seq].max(e[i][n_seq]).max(f[i][n_seq]);
+            if score > best_score {
+                best_score = score;
+                best_node_idx = i;
+                best_col = n_seq;
+                if score == m[i][n_seq] {
+                    best_state = 0;
+                } else if score == e[i][n_seq] {
+                    best_state = 1;
+                } else {
+                    best_state = 2;
                 }
             }
-            if is_semi && curr_j == 0 {
-                break;
-            } // Reached start of sequence
+        }
+    }
+
+    // Backtracking
+    let mut path = Vec::new();
+    let mut curr_i = best_node_idx;
+    let mut curr_j = best_col;
+    let mut curr_state = best_state;
+
+    // A cell on the band edge may have lost a better predecessor outside the band
+    let on_edge = |i: usize, j: usize| {
+        let (lo, hi) = bounds[i];
+        (lo > 0 && j <= lo) || (hi < n_seq && j >= hi)
+    };
+    let mut hit_edge = best_score <= neg_inf / 2;
+
+    while curr_j > 0 || (curr_i > 0) {
+        hit_edge |= on_edge(curr_i, curr_j);
+        let node_idx = sorted_nodes[curr_i];
+        let preds: Vec<NodeIndex> = graph
+            .graph
+            .neighbors_directed(node_idx, petgraph::Direction::Incoming)
+            .collect();
+        let is_start = preds.is_empty();
 
-            if curr_j == 0 && is_start {
+        // Stop conditions
+        if is_local && best_score == 0 {
+            break;
+        } // If score 0, stop
+        if is_local {
+            let s = match curr_state {
+                0 => m[curr_i][curr_j],
+                1 => e[curr_i][curr_j],
+                _ => f[curr_i][curr_j],
+            };
+            if s <= 0 {
                 break;
             }
+        }
+        if is_semi && curr_j == 0 {
+            break;
+        } // Reached start of sequence
 
-            match curr_state {
-                0 => {
-                    // M
-                    let match_score = if curr_j > 0 {
-                        if sequence[curr_j - 1] == graph.graph[node_idx].base {
-                            self.params.match_score
-                        } else {
-                            self.params.mismatch_score
-                        }
-                    } else {
-                        0
-                    };
+        if curr_j == 0 && is_start {
+            break;
+        }
 
-                    // If j=1 and Local/Semi, we could have started here
-                    if (is_local || is_semi) && curr_j == 1 {
-                        // Check if we started here (score == match_score)
-                        if m[curr_i][curr_j] == match_score {
-                            path.push((Some(curr_j - 1), Some(node_idx)));
-                            curr_j -= 1;
-                            break;
-                        }
+        match curr_state {
+            0 => {
+                // M
+                let match_score = if curr_j > 0 {
+                    if sequence[curr_j - 1] == graph.graph[node_idx].base {
+                        params.match_score
+                    } else {
+                        params.mismatch_score
                     }
+                } else {
+                    0
+                };
 
-                    if is_start {
-                        if curr_j > 0 {
-                            path.push((Some(curr_j - 1), Some(node_idx)));
-                            curr_j -= 1;
-                        }
+                // If j=1 and Local/Semi, we could have started here
+                if (is_local || is_semi) && curr_j == 1 {
+                    // Check if we started here (score == match_score)
+                    if m[curr_i][curr_j] == match_score {
+                        path.push((Some(curr_j - 1), Some(node_idx)));
+                        curr_j -= 1;
                         break;
-                    } else {
-                        let mut found = false;
-                        for &pred in &preds {
-                            let u = node_map[&pred];
-                            let target = m[curr_i][curr_j] - match_score;
-
-                            if m[u][curr_j - 1] == target {
-                                path.push((Some(curr_j - 1), Some(node_idx)));
-                                curr_i = u;
-                                curr_j -= 1;
-                                curr_state = 0;
-                                found = true;
-                                break;
-                            }
-                            if e[u][curr_j - 1] == target {
-                                path.push((Some(curr_j - 1), Some(node_idx)));
-                                curr_i = u;
-                                curr_j -= 1;
-                                curr_state = 1;
-                                found = true;
-                                break;
-                            }
-                            if f[u][curr_j - 1] == target {
-                                path.push((Some(curr_j - 1), Some(node_idx)));
-                                curr_i = u;
-                                curr_j -= 1;
-                                curr_state = 2;
-                                found = true;
-                                break;
-                            }
-                        }
-                        if !found {
-                            // Could be start of a branch from virtual source?
-                            break;
-                        }
                     }
                 }
-                1 => {
-                    // E
-                    let target = e[curr_i][curr_j];
-                    let score_e = e[curr_i][curr_j - 1] + self.params.gap_extend;
-
-                    path.push((Some(curr_j - 1), None));
 
-                    // Spoa checks H[i][j] == H[i][j-1] + g_ (gap open) for insertion start
-                    // Spoa checks H[i][j] == E[i][j-1] + e_ (gap extend) for insertion extend
-                    if target == score_e {
+                if is_start {
+                    if curr_j > 0 {
+                        path.push((Some(curr_j - 1), Some(node_idx)));
                         curr_j -= 1;
-                        curr_state = 1;
-                    } else {
-                        // Transition from M or F
-                        let score_m = m[curr_i][curr_j - 1] + self.params.gap_open;
-                        if target == score_m {
-                            curr_j -= 1;
-                            curr_state = 0;
-                        } else {
-                            // Must be F
-                            // Verify for correctness/safety
-                            // let score_f = f[curr_i][curr_j-1] + self.params.gap_open;
-                            // if target == score_f { ... }
-                            curr_j -= 1;
-                            curr_state = 2;
-                        }
                     }
-                }
-                2 => {
-                    // F
+                    break;
+                } else {
                     let mut found = false;
                     for &pred in &preds {
                         let u = node_map[&pred];
-                        let target = f[curr_i][curr_j];
-                        if f[u][curr_j] + self.params.gap_extend == target {
-                            path.push((None, Some(node_idx)));
+                        let target = m[curr_i][curr_j] - match_score;
+
+                        if m[u][curr_j - 1] == target {
+                            path.push((Some(curr_j - 1), Some(node_idx)));
                             curr_i = u;
-                            curr_state = 2;
+                            curr_j -= 1;
+                            curr_state = 0;
                             found = true;
                             break;
                         }
-                        if m[u][curr_j] + self.params.gap_open == target {
-                            path.push((None, Some(node_idx)));
+                        if e[u][curr_j - 1] == target {
+                            path.push((Some(curr_j - 1), Some(node_idx)));
                             curr_i = u;
-                            curr_state = 0;
+                            curr_j -= 1;
+                            curr_state = 1;
                             found = true;
                             break;
                         }
-                        if e[u][curr_j] + self.params.gap_open == target {
-                            path.push((None, Some(node_idx)));
+                        if f[u][curr_j - 1] == target {
+                            path.push((Some(curr_j - 1), Some(node_idx)));
                             curr_i = u;
-                            curr_state = 1;
+                            curr_j -= 1;
+                            curr_state = 2;
                             found = true;
                             break;
                         }
                     }
                     if !found {
-                        if is_start {
-                            path.push((None, Some(node_idx)));
-                            break;
-                        }
+                        // Could be start of a branch from virtual source?
                         break;
                     }
                 }
-                _ => break,
             }
-        }
+            1 => {
+                // E
+                let target = e[curr_i][curr_j];
+                let score_e = e[curr_i][curr_j - 1] + params.gap_extend;
 
-        // Fill remaining sequence if any (for Global)
-        // For Local/Semi, we stop.
-        if !is_local && !is_semi {
-            while curr_j > 0 {
                 path.push((Some(curr_j - 1), None));
-                curr_j -= 1;
+
+                // Spoa checks H[i][j] == H[i][j-1] + g_ (gap open) for insertion start
+                // Spoa checks H[i][j] == E[i][j-1] + e_ (gap extend) for insertion extend
+                if target == score_e {
+                    curr_j -= 1;
+                    curr_state = 1;
+                } else {
+                    // Transition from M or F
+                    let score_m = m[curr_i][curr_j - 1] + params.gap_open;
+                    if target == score_m {
+                        curr_j -= 1;
+                        curr_state = 0;
+                    } else {
+                        // Must be F
+                        // Verify for correctness/safety
+                        // let score_f = f[curr_i][curr_j-1] + params.gap_open;
+                        // if target == score_f { ... }
+                        curr_j -= 1;
+                        curr_state = 2;
+                    }
+                }
             }
+            2 => {
+                // F
+                let mut found = false;
+                for &pred in &preds {
+                    let u = node_map[&pred];
+                    let target = f[curr_i][curr_j];
+                    if f[u][curr_j] + params.gap_extend == target {
+                        path.push((None, Some(node_idx)));
+                        curr_i = u;
+                        curr_state = 2;
+                        found = true;
+                        break;
+                    }
+                    if m[u][curr_j] + params.gap_open == target {
+                        path.push((None, Some(node_idx)));
+                        curr_i = u;
+                        curr_state = 0;
+                        found = true;
+                        break;
+                    }
+                    if e[u][curr_j] + params.gap_open == target {
+                        path.push((None, Some(node_idx)));
+                        curr_i = u;
+                        curr_state = 1;
+                        found = true;
+                        break;
+                    }
+                }
+                if !found {
+                    if is_start {
+                        path.push((None, Some(node_idx)));
+                        break;
+                    }
+                    break;
+                }
+            }
+            _ => break,
         }
+    }
 
-        path.reverse();
+    // Fill remaining sequence if any (for Global)
+    // For Local/Semi, we stop.
+    if !is_local && !is_semi {
+        while curr_j > 0 {
+            path.push((Some(curr_j - 1), None));
+            curr_j -= 1;
+        }
+    }
+
+    path.reverse();
 
+    (
         Alignment {
             score: best_score,
             path,
-        }
-    }
+        },
+        hit_edge,
+    )
 }
 
 #[cfg(test)]
@@ -678,4 +802,73 @@ mod tests {
         assert_eq!(alignment.path[0], (Some(2), Some(n4))); // T
         assert_eq!(alignment.path[1], (Some(3), Some(n5))); // T
     }
+
+    #[test]
+    fn test_align_banded_matches_scalar() {
+        let scalar = ScalarAlignmentEngine::new(AlignmentParams::default(), AlignmentType::Global);
+        let banded =
+            BandedAlignmentEngine::new(AlignmentParams::default(), AlignmentType::Global, 8);
+
+        // Build a small graph with a SNP and a deletion branch
+        let mut graph = PoaGraph::new();
+        for seq in [
+            b"ACGTACGTTGCA".as_ref(),
+            b"ACGTACCTTGCA".as_ref(),
+            b"ACGTAGTTGCA".as_ref(),
+        ] {
+            let alignment = scalar.align(seq, &graph);
+            graph.add_alignment(&alignment, seq);
+        }
+
+        for seq in [b"ACGTACGATTGCA".as_ref(), b"ACGTACGTTGCA".as_ref()] {
+            let expected = scalar.align(seq, &graph);
+            let actual = banded.align(seq, &graph);
+            assert_eq!(actual.score, expected.score);
+            assert_eq!(actual.path, expected.path);
+        }
+    }
+
+    // A chain graph of `seq`, one node per base
+    fn chain_graph(seq: &[u8]) -> PoaGraph {
+        let scalar = ScalarAlignmentEngine::new(AlignmentParams::default(), AlignmentType::Global);
+        let mut graph = PoaGraph::new();
+        let alignment = scalar.align(seq, &graph);
+        graph.add_alignment(&alignment, seq);
+        graph
+    }
+
+    #[test]
+    fn test_align_narrow_band_stays_in_band() {
+        let params = AlignmentParams::default();
+        let scalar = ScalarAlignmentEngine::new(params.clone(), AlignmentType::Global);
+        let graph = chain_graph(b"ACGTTGCAACGGTACCTAGGATCCATGCATTGACCAGTTACG");
+
+        // A SNP and a one-base deletion stay near the diagonal, well inside band 2
+        let seq = b"ACGTTGCAACGGTACGTAGGATCCATGCATGACCAGTTACG";
+        let (banded, hit_edge) =
+            align_in_band(&params, AlignmentType::Global, seq, &graph, Some(2));
+        assert!(!hit_edge);
+
+        let expected = scalar.align(seq, &graph);
+        assert_eq!(banded.score, expected.score);
+        assert_eq!(banded.path, expected.path);
+    }
+
+    #[test]
+    fn test_align_band_edge_falls_back() {
+        let params = AlignmentParams::default();
+        let scalar = ScalarAlignmentEngine::new(params.clone(), AlignmentType::Global);
+        let banded = BandedAlignmentEngine::new(params.clone(), AlignmentType::Global, 1);
+        let graph = chain_graph(b"ACGTTGCAACGGTACCTAGGATCC");
+
+        // A leading insertion of eight bases pushes the path off the diagonal
+        let seq = b"TTTTTTTTACGTTGCAACGGTACCTAGGATCC";
+        let (_, hit_edge) = align_in_band(&params, AlignmentType::Global, seq, &graph, Some(1));
+        assert!(hit_edge);
+
+        let expected = scalar.align(seq, &graph);
+        let actual = banded.align(seq, &graph);
+        assert_eq!(actual.score, expected.score);
+        assert_eq!(actual.path, expected.path);
+    }
 }
@@ -8,6 +8,34 @@ use std::collections::HashMap;
 /// Uses a heaviest path algorithm (finding the path with maximum total weight).
 /// Score[u] = NodeWeight[u] + max(Score[v] + EdgeWeight(v, u)) for all predecessors v.
 pub fn generate_consensus(graph: &PoaGraph) -> Vec<u8> {
+    heaviest_path(graph)
+        .into_iter()
+        .map(|node| graph.graph[node].base)
+        .collect()
+}
+
+/// Generates a consensus sequence together with a per-base support score.
+///
+/// The score of each base is the fraction of the `num_seqs` input sequences
+/// passing through its consensus node.
+pub fn generate_consensus_with_scores(graph: &PoaGraph, num_seqs: usize) -> (Vec<u8>, Vec<f64>) {
+    let path = heaviest_path(graph);
+    let bases = path.iter().map(|&node| graph.graph[node].base).collect();
+    let scores = path
+        .iter()
+        .map(|&node| {
+            if num_seqs == 0 {
+                0.0
+            } else {
+                graph.graph[node].weight as f64 / num_seqs as f64
+            }
+        })
+        .collect();
+    (bases, scores)
+}
+
+/// Nodes of the heaviest path through the graph, in topological order.
+fn heaviest_path(graph: &PoaGraph) -> Vec<NodeIndex> {
     let sorted_nodes = graph.topological_sort();
 
     if sorted_nodes.is_empty() {
@@ -109,10 +137,10 @@ pub fn generate_consensus(graph: &PoaGraph) -> Vec<u8> {
     }
 
     // Backtrack
-    let mut consensus = Vec::new();
+    let mut path = Vec::new();
     if let Some(mut curr) = end_node {
         loop {
-            consensus.push(graph.graph[curr].base);
+            path.push(curr);
             if let Some(&prev) = predecessors.get(&curr) {
                 curr = prev;
             } else {
@@ -121,8 +149,8 @@ pub fn generate_consensus(graph: &PoaGraph) -> Vec<u8> {
         }
     }
 
-    consensus.reverse();
-    consensus
+    path.reverse();
+    path
 }
 
 #[cfg(test)]
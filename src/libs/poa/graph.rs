@@ -1,6 +1,7 @@
 use super::align::Alignment;
 use petgraph::graph::{DiGraph, NodeIndex};
-use petgraph::visit::NodeIndexable;
+use petgraph::visit::{EdgeRef, NodeIndexable};
+use petgraph::Direction;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct NodeData {
@@ -136,6 +137,39 @@ impl PoaGraph {
         self.graph.node_count()
     }
 
+    /// Renders the graph in Graphviz DOT format for debugging.
+    /// Nodes are labeled by base and listed in topological order; edges are labeled by weight.
+    pub fn to_dot(&self) -> String {
+        let sorted_nodes = self.topological_sort();
+
+        let mut dot = String::from("digraph poa {\n    rankdir=LR;\n");
+        for &node in &sorted_nodes {
+            dot.push_str(&format!(
+                "    n{} [label=\"{}\"];\n",
+                node.index(),
+                self.graph[node].base as char
+            ));
+        }
+        for &node in &sorted_nodes {
+            let mut edges: Vec<_> = self
+                .graph
+                .edges_directed(node, Direction::Outgoing)
+                .collect();
+            edges.sort_by_key(|e| e.target().index());
+            for edge in edges {
+                dot.push_str(&format!(
+                    "    n{} -> n{} [label=\"{}\"];\n",
+                    node.index(),
+                    edge.target().index(),
+                    edge.weight()
+                ));
+            }
+        }
+        dot.push_str("}\n");
+
+        dot
+    }
+
     /// Adds an alignment to the graph, updating weights and adding new nodes/edges as needed.
     /// Returns the path of node indices corresponding to the sequence.
     pub fn add_alignment(&mut self, alignment: &Alignment, sequence: &[u8]) -> Vec<NodeIndex> {
@@ -351,4 +385,42 @@ mod tests {
         assert!(graph.graph[n2].aligned_nodes.contains(&n1));
         assert!(graph.graph[n1].aligned_nodes.contains(&n2));
     }
+
+    #[test]
+    fn test_to_dot() {
+        // Seq 1: A C G T, Seq 2: A C G A (mismatch at the last base)
+        let mut graph = PoaGraph::new();
+        let seq1 = b"ACGT";
+        let alignment1 = Alignment {
+            score: 0,
+            path: (0..4).map(|i| (Some(i), None)).collect(),
+        };
+        let path1 = graph.add_alignment(&alignment1, seq1);
+
+        let seq2 = b"ACGA";
+        let alignment2 = Alignment {
+            score: 0,
+            path: (0..4).map(|i| (Some(i), Some(path1[i]))).collect(),
+        };
+        graph.add_alignment(&alignment2, seq2);
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph poa {"));
+        assert!(dot.trim_end().ends_with('}'));
+
+        let node_lines = dot
+            .lines()
+            .filter(|l| l.contains("[label=") && !l.contains("->"));
+        assert_eq!(node_lines.count(), graph.num_nodes());
+        assert_eq!(graph.num_nodes(), 5);
+
+        let edge_lines = dot.lines().filter(|l| l.contains("->"));
+        assert_eq!(edge_lines.count(), graph.graph.edge_count());
+        assert_eq!(graph.graph.edge_count(), 4);
+        assert!(dot.contains(&format!(
+            "n{} -> n{} [label=\"2\"];",
+            path1[0].index(),
+            path1[1].index()
+        )));
+    }
 }
@@ -1,5 +1,5 @@
 use super::align::{AlignmentEngine, AlignmentParams, AlignmentType, ScalarAlignmentEngine};
-use super::consensus::generate_consensus;
+use super::consensus::{generate_consensus, generate_consensus_with_scores};
 use super::graph::PoaGraph;
 use super::msa::generate_msa;
 use petgraph::graph::NodeIndex;
@@ -32,6 +32,11 @@ impl Poa {
         generate_consensus(&self.graph)
     }
 
+    /// Consensus plus, for each base, the fraction of sequences supporting it.
+    pub fn consensus_with_scores(&self) -> (Vec<u8>, Vec<f64>) {
+        generate_consensus_with_scores(&self.graph, self.sequences.len())
+    }
+
     pub fn msa(&self) -> Vec<String> {
         generate_msa(&self.graph, &self.sequences, &self.paths)
     }
@@ -86,6 +91,26 @@ mod tests {
         assert_eq!(poa.consensus(), b"ACGT");
     }
 
+    #[test]
+    fn test_poa_consensus_with_scores() {
+        let params = AlignmentParams::default();
+        let mut poa = Poa::new(params, AlignmentType::Global);
+
+        poa.add_sequence(b"ACGTACGT");
+        poa.add_sequence(b"ACGTACGT");
+        // Divergent at position 3
+        poa.add_sequence(b"ACGAACGT");
+
+        let (cons, scores) = poa.consensus_with_scores();
+        assert_eq!(cons, b"ACGTACGT");
+        assert_eq!(cons, poa.consensus());
+        assert_eq!(scores.len(), cons.len());
+        for (i, score) in scores.iter().enumerate() {
+            let expected = if i == 3 { 2.0 / 3.0 } else { 1.0 };
+            assert!((score - expected).abs() < 1e-9, "score at {}", i);
+        }
+    }
+
     #[test]
     fn test_poa_insertion() {
         let params = AlignmentParams::default();
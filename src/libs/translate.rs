@@ -28,6 +28,47 @@ pub static AA_TAB: &[[[char; 4]; 4]; 4] = &[
     ],
 ];
 
+/// NCBI table 2, the vertebrate mitochondrial code.
+///
+/// Differs from the standard code at AGA/AGG (stop), ATA (Met) and TGA (Trp).
+pub static AA_TAB_VMITO: &[[[char; 4]; 4]; 4] = &[
+    [
+        ['K', 'N', 'K', 'N'], // AAA, AAC, AAG, AAU/AAT
+        ['T', 'T', 'T', 'T'], // ACA, ACC, ACG, ACU/ACT
+        ['*', 'S', '*', 'S'], // AGA, AGC, AGG, AGU/AGT
+        ['M', 'I', 'M', 'I'], // AUA/ATA, AUC/ATC, AUG/ATG, AUU/ATT
+    ],
+    [
+        ['Q', 'H', 'Q', 'H'], // CAA, CAC, CAG, CAU/CAT
+        ['P', 'P', 'P', 'P'], // CCA, CCC, CCG, CCU/CCT
+        ['R', 'R', 'R', 'R'], // CGA, CGC, CGG, CGU/CGT
+        ['L', 'L', 'L', 'L'], // CUA/CTA, CUC/CTC, CUG/CTG, CUU/CTT
+    ],
+    [
+        ['E', 'D', 'E', 'D'], // GAA, GAC, GAG, GAU/GAT
+        ['A', 'A', 'A', 'A'], // GCA, GCC, GCG, GCU/GCT
+        ['G', 'G', 'G', 'G'], // GGA, GGC, GGG, GGU/GGT
+        ['V', 'V', 'V', 'V'], // GUA/GTA, GUC/GTC, GUG/GTG, GUU/GTT
+    ],
+    [
+        ['*', 'Y', '*', 'Y'], // UAA/TAA, UAC/TAC, UAG/TAG, UAU/TAT
+        ['S', 'S', 'S', 'S'], // UCA/TCA, UCC/TCC, UCG/TCG, UCU/TCT
+        ['W', 'C', 'W', 'C'], // UGA/TGA, UGC/TGC, UGG/TGG, UGU/TGT
+        ['L', 'F', 'L', 'F'], // UUA/TTA, UUC/TTC, UUG/TTG, UUU/TTT
+    ],
+];
+
+/// Codon table of an NCBI translation table id.
+///
+/// Supported ids are 1 (standard) and 2 (vertebrate mitochondrial).
+pub fn codon_table(id: u8) -> Option<&'static [[[char; 4]; 4]; 4]> {
+    match id {
+        1 => Some(AA_TAB),
+        2 => Some(AA_TAB_VMITO),
+        _ => None,
+    }
+}
+
 /// ```ignore
 /// let dna = b"GCTAGTCGTATCGTAGCTAGTC";
 /// assert_eq!(&pgr::libs::translate::translate(dna), "ASRIVAS");
@@ -41,6 +82,14 @@ pub static AA_TAB: &[[[char; 4]; 4]; 4] = &[
 /// ```
 // https://github.com/dweb0/protein-translate/blob/master/src/lib.rs
 pub fn translate(seq: &[u8]) -> String {
+    translate_with(seq, AA_TAB)
+}
+
+/// Translate with the codon table `tab`, e.g. one returned by [`codon_table`].
+///
+/// Codons with ambiguous or invalid bases become `X`; a trailing partial
+/// codon is ignored.
+pub fn translate_with(seq: &[u8], tab: &[[[char; 4]; 4]; 4]) -> String {
     let mut peptide = String::with_capacity(seq.len() / 3);
 
     for triplet in seq.chunks_exact(3) {
@@ -51,7 +100,7 @@ pub fn translate(seq: &[u8]) -> String {
         if c1 >= Nt::N as usize || c2 >= Nt::N as usize || c3 >= Nt::N as usize {
             peptide.push('X');
         } else {
-            peptide.push(AA_TAB[c1][c2][c3]);
+            peptide.push(tab[c1][c2][c3]);
         }
     }
     peptide
@@ -98,6 +147,29 @@ pub fn find_orfs(protein: &str) -> Vec<(String, usize, usize)> {
     orfs
 }
 
+/// Longest ORF of a translated protein sequence.
+///
+/// An ORF starts at a Met (`M`) and runs up to, but not including, the next
+/// stop (`*`) or the end of the sequence. Ties go to the first ORF. Returns
+/// `None` when no segment contains a Met.
+///
+/// ```ignore
+/// assert_eq!(pgr::libs::translate::longest_orf(b"KMA*GMPPP"), Some(&b"MPPP"[..]));
+/// assert_eq!(pgr::libs::translate::longest_orf(b"KA*"), None);
+/// ```
+pub fn longest_orf(protein: &[u8]) -> Option<&[u8]> {
+    let mut best: Option<&[u8]> = None;
+    for segment in protein.split(|&aa| aa == b'*') {
+        if let Some(m) = segment.iter().position(|&aa| aa == b'M') {
+            let orf = &segment[m..];
+            if best.is_none_or(|b| orf.len() > b.len()) {
+                best = Some(orf);
+            }
+        }
+    }
+    best
+}
+
 /// Translate DNA in all six frames.
 ///
 /// Returns `Vec<(protein, frame, is_reverse)>` where `frame` is the offset
@@ -17,9 +17,11 @@ fn main() -> anyhow::Result<()> {
         .subcommand(cmd_pgr::ms::make_subcommand())
         .subcommand(cmd_pgr::axt::make_subcommand())
         .subcommand(cmd_pgr::chain::make_subcommand())
+        .subcommand(cmd_pgr::clust::make_subcommand())
         .subcommand(cmd_pgr::dist::make_subcommand())
         .subcommand(cmd_pgr::lav::make_subcommand())
         .subcommand(cmd_pgr::maf::make_subcommand())
+        .subcommand(cmd_pgr::mat::make_subcommand())
         .subcommand(cmd_pgr::net::make_subcommand())
         .subcommand(cmd_pgr::paf::make_subcommand())
         .subcommand(cmd_pgr::pbit::make_subcommand())
@@ -52,7 +54,9 @@ fn main() -> anyhow::Result<()> {
     * psl   - PSL statistics, manipulation, and conversion
 
 * Distance:
-    * dist  - Metrics: hv, seq
+    * dist  - Metrics: hv, seq, vector
+    * mat   - Distance matrices: format, mds, subset, to-pair
+    * clust - Clustering: eval
 
 * Simulation:
     * ms    - Hudson's ms simulator tools: to-dna
@@ -71,9 +75,11 @@ fn main() -> anyhow::Result<()> {
         Some(("ms", sub_matches)) => cmd_pgr::ms::execute(sub_matches),
         Some(("axt", sub_matches)) => cmd_pgr::axt::execute(sub_matches),
         Some(("chain", sub_matches)) => cmd_pgr::chain::execute(sub_matches),
+        Some(("clust", sub_matches)) => cmd_pgr::clust::execute(sub_matches),
         Some(("dist", sub_matches)) => cmd_pgr::dist::execute(sub_matches),
         Some(("lav", sub_matches)) => cmd_pgr::lav::execute(sub_matches),
         Some(("maf", sub_matches)) => cmd_pgr::maf::execute(sub_matches),
+        Some(("mat", sub_matches)) => cmd_pgr::mat::execute(sub_matches),
         Some(("net", sub_matches)) => cmd_pgr::net::execute(sub_matches),
         Some(("paf", sub_matches)) => cmd_pgr::paf::execute(sub_matches),
         Some(("pbit", sub_matches)) => cmd_pgr::pbit::execute(sub_matches),
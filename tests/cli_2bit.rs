@@ -116,6 +116,63 @@ fn test_2bit_to_fa_mask() {
     assert!(read_fasta(&unmasked).contains("ACGTACGT"));
 }
 
+#[test]
+fn test_fa_to_2bit_mask_roundtrip() {
+    let temp = TempDir::new().unwrap();
+    let fa = temp.path().join("in.fa");
+    let tb = temp.path().join("in.2bit");
+    let back = temp.path().join("back.fa");
+
+    // Mask runs at the start, in the middle, at the end, and overlapping Ns.
+    let input = ">seq1\nacgtACGTNNnnACgtacGTAc\n>seq2\nACGTACGT\n>seq3\nnnnnacgt\n";
+    fs::write(&fa, input).unwrap();
+
+    PgrCmd::new()
+        .args(&[
+            "fa",
+            "to-2bit",
+            fa.to_str().unwrap(),
+            "-o",
+            tb.to_str().unwrap(),
+        ])
+        .run();
+    PgrCmd::new()
+        .args(&[
+            "2bit",
+            "to-fa",
+            tb.to_str().unwrap(),
+            "-o",
+            back.to_str().unwrap(),
+        ])
+        .run();
+    assert_eq!(read_fasta(&back), input);
+
+    // --no-mask drops the case information.
+    PgrCmd::new()
+        .args(&[
+            "fa",
+            "to-2bit",
+            fa.to_str().unwrap(),
+            "--no-mask",
+            "-o",
+            tb.to_str().unwrap(),
+        ])
+        .run();
+    PgrCmd::new()
+        .args(&[
+            "2bit",
+            "to-fa",
+            tb.to_str().unwrap(),
+            "-o",
+            back.to_str().unwrap(),
+        ])
+        .run();
+    assert_eq!(
+        read_fasta(&back),
+        input.to_uppercase().replace(">SEQ", ">seq")
+    );
+}
+
 #[test]
 fn test_2bit_range_basic() {
     let temp = TempDir::new().unwrap();
@@ -206,6 +263,32 @@ fn test_2bit_size() {
     assert!(stdout.contains("seq2\t4"));
 }
 
+#[test]
+fn test_2bit_size_n_count_total() {
+    // seq1: ACGTNNNNACGT (12 bp, one 4 bp N block); seq2: acgt.
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "2bit",
+            "size",
+            fixture("flags.2bit").to_str().unwrap(),
+            "--n-count",
+            "--total",
+        ])
+        .run();
+    assert_eq!(stdout, "seq1\t12\t4\nseq2\t4\t0\ntotal\t16\t4\n");
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "2bit",
+            "size",
+            fixture("flags.2bit").to_str().unwrap(),
+            "--no-ns",
+            "--total",
+        ])
+        .run();
+    assert_eq!(stdout, "seq1\t8\nseq2\t4\ntotal\t12\n");
+}
+
 #[test]
 fn test_2bit_some() {
     let temp = TempDir::new().unwrap();
@@ -274,6 +357,28 @@ fn test_2bit_some_ignores_comments_and_empty_lines() {
     assert!(!content.contains(">seq2"));
 }
 
+#[test]
+fn test_2bit_some_list_option() {
+    let temp = TempDir::new().unwrap();
+    let list = temp.path().join("list_missing.txt");
+    fs::write(&list, "seq3\nseq1\nseq9\n").unwrap();
+
+    let (stdout, stderr) = PgrCmd::new()
+        .args(&[
+            "2bit",
+            "some",
+            fixture("some.2bit").to_str().unwrap(),
+            "--list",
+            list.to_str().unwrap(),
+        ])
+        .run();
+
+    assert!(stdout.contains(">seq1\nACGT\n"));
+    assert!(stdout.contains(">seq3\nNNNN\n"));
+    assert!(!stdout.contains(">seq2"));
+    assert!(stderr.contains("seq9 not found"));
+}
+
 #[test]
 fn test_2bit_range_seqlist1_file() {
     let temp = TempDir::new().unwrap();
@@ -334,6 +439,44 @@ fn test_2bit_masked() {
     assert!(content_n.contains("startN:7-9"));
 }
 
+#[test]
+fn test_2bit_masked_bed() {
+    let temp = TempDir::new().unwrap();
+    let fa = temp.path().join("blocks.fa");
+    let tb = temp.path().join("blocks.2bit");
+    fs::write(&fa, ">seq1\nACgtACNNNACG\n").unwrap();
+    PgrCmd::new()
+        .args(&[
+            "fa",
+            "to-2bit",
+            fa.to_str().unwrap(),
+            "-o",
+            tb.to_str().unwrap(),
+        ])
+        .run();
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["2bit", "masked", tb.to_str().unwrap(), "--bed"])
+        .run();
+    assert_eq!(stdout, "seq1\t2\t4\tmask\n");
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "2bit",
+            "masked",
+            tb.to_str().unwrap(),
+            "--bed",
+            "--n-blocks",
+        ])
+        .run();
+    assert_eq!(stdout, "seq1\t2\t4\tmask\nseq1\t6\t9\tN\n");
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["2bit", "masked", tb.to_str().unwrap(), "--bed", "--gap"])
+        .run();
+    assert_eq!(stdout, "seq1\t6\t9\tN\n");
+}
+
 #[test]
 fn test_2bit_range_legacy_cases() {
     let temp = TempDir::new().unwrap();
@@ -384,6 +384,63 @@ fn command_axt_to_fas_help() {
     assert!(stdout.contains("Converts AXT format files to block FA format"));
 }
 
+#[test]
+fn command_axt_to_psl_minus_strand_round_trip() {
+    let dir = TempDir::new().unwrap();
+    let input_path = dir.path().join("input.axt");
+    let t_sizes_path = dir.path().join("t.sizes");
+    let q_sizes_path = dir.path().join("q.sizes");
+    let psl_path = dir.path().join("output.psl");
+
+    // Query 21-24 on the minus strand of a 100 bp sequence is 77-80 on the plus strand
+    fs::write(&input_path, "0 chr1 11 14 chr2 21 24 - 100\nACGT\nACGT\n").unwrap();
+    fs::write(&t_sizes_path, "chr1\t100\n").unwrap();
+    fs::write(&q_sizes_path, "chr2\t100\n").unwrap();
+
+    // The query is absent from the sizes file
+    let (_, stderr) = PgrCmd::new()
+        .args(&[
+            "axt",
+            "to-psl",
+            input_path.to_str().unwrap(),
+            "-t",
+            t_sizes_path.to_str().unwrap(),
+            "-q",
+            t_sizes_path.to_str().unwrap(),
+        ])
+        .run_fail();
+    assert!(stderr.contains("minus-strand records need it"));
+
+    PgrCmd::new()
+        .args(&[
+            "axt",
+            "to-psl",
+            input_path.to_str().unwrap(),
+            "-t",
+            t_sizes_path.to_str().unwrap(),
+            "-q",
+            q_sizes_path.to_str().unwrap(),
+            "-o",
+            psl_path.to_str().unwrap(),
+        ])
+        .run();
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["psl", "to-range", psl_path.to_str().unwrap()])
+        .run();
+    assert_eq!(stdout, "chr2:77-80\n");
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "psl",
+            "to-range",
+            psl_path.to_str().unwrap(),
+            "--target-coords",
+        ])
+        .run();
+    assert_eq!(stdout, "chr1:11-14\n");
+}
+
 #[test]
 fn command_axt_to_psl_help() {
     let (stdout, _) = PgrCmd::new().args(&["axt", "to-psl", "--help"]).run();
@@ -432,6 +489,67 @@ TTTT
     assert!(lines[6].contains("0 chr1 11 21"));
 }
 
+#[test]
+fn command_axt_sort_by_qname_gz() {
+    use std::io::{Read, Write};
+
+    let dir = TempDir::new().unwrap();
+    let input_path = dir.path().join("input.axt.gz");
+    let output_path = dir.path().join("output.axt.gz");
+
+    let input_content = "\
+0 chr1 11 21 chrB 11 21 + 100
+ACTG
+ACTG
+
+1 chr2 6 16 chrA 31 41 + 50
+AAAA
+AAAA
+
+2 chr1 31 41 chrA 6 16 + 200
+TTTT
+TTTT
+
+3 chr3 1 11 chrA 6 16 + 80
+GGGG
+GGGG
+";
+    {
+        let mut encoder = flate2::write::GzEncoder::new(
+            fs::File::create(&input_path).unwrap(),
+            flate2::Compression::default(),
+        );
+        encoder.write_all(input_content.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+    }
+
+    PgrCmd::new()
+        .args(&[
+            "axt",
+            "sort",
+            "--by",
+            "qName",
+            input_path.to_str().unwrap(),
+            "-o",
+            output_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let mut output = String::new();
+    flate2::read::GzDecoder::new(fs::File::open(&output_path).unwrap())
+        .read_to_string(&mut output)
+        .unwrap();
+    let summaries: Vec<&str> = output.lines().filter(|l| l.contains(" chr")).collect();
+
+    // chrA ties at start 6 keep input order (2 before 3), then chrA 31, then chrB
+    assert_eq!(summaries.len(), 4);
+    assert!(summaries[0].starts_with("2 chr1 31 41 chrA 6 16"));
+    assert!(summaries[1].starts_with("3 chr3 1 11 chrA 6 16"));
+    assert!(summaries[2].starts_with("1 chr2 6 16 chrA 31 41"));
+    assert!(summaries[3].starts_with("0 chr1 11 21 chrB 11 21"));
+}
+
 #[test]
 fn command_axt_sort_renumber() {
     let dir = TempDir::new().unwrap();
@@ -53,6 +53,97 @@ fn test_chain_net_basic() {
     assert!(q_net_content.contains("fill 0 100 chr1 + 0 100"));
 }
 
+#[test]
+fn test_chain_net_query_side() {
+    let dir = tempdir().unwrap();
+    let chain_path = dir.path().join("in.chain");
+    let t_sizes_path = dir.path().join("t.sizes");
+    let q_sizes_path = dir.path().join("q.sizes");
+    let t_net_path = dir.path().join("t.net");
+    let q_net_path = dir.path().join("q.net");
+
+    // A minus-strand chain: query 10-210 on the reverse strand of a 500 bp
+    // chr2 is 290-490 on the plus strand.
+    let chain_content = "chain 1000 chr1 1000 + 100 300 chr2 500 - 10 210 1\n200\n\n";
+    fs::write(&chain_path, chain_content).unwrap();
+
+    fs::write(&t_sizes_path, "chr1 1000\n").unwrap();
+    fs::write(&q_sizes_path, "chr2 500\n").unwrap();
+
+    PgrCmd::new()
+        .args(&[
+            "chain",
+            "net",
+            chain_path.to_str().unwrap(),
+            t_sizes_path.to_str().unwrap(),
+            q_sizes_path.to_str().unwrap(),
+            t_net_path.to_str().unwrap(),
+            q_net_path.to_str().unwrap(),
+            "--min-score=0",
+            "--min-space=1",
+        ])
+        .run();
+
+    let q_net_content = fs::read_to_string(&q_net_path).unwrap();
+    assert!(q_net_content.contains("net chr2 500"));
+    assert!(q_net_content.contains(" fill 290 200 chr1 - 100 200"));
+    assert!(!q_net_content.contains("net chr1"));
+}
+
+#[test]
+fn test_chain_net_min_fill() {
+    let dir = tempdir().unwrap();
+    let chain_path = dir.path().join("in.chain");
+    let t_sizes_path = dir.path().join("t.sizes");
+    let q_sizes_path = dir.path().join("q.sizes");
+    let q_net_path = dir.path().join("q.net");
+
+    // Chain 1 leaves a 300 bp gap (100-400) that chain 2 fills with 20 bp.
+    let c1 = "chain 5000 chr1 1000 + 0 500 chr2 1000 + 0 500 1\n100 300 300\n100\n\n";
+    let c2 = "chain 3000 chr1 1000 + 150 170 chr2 1000 + 150 170 2\n20\n\n";
+    fs::write(&chain_path, format!("{}{}", c1, c2)).unwrap();
+
+    fs::write(&t_sizes_path, "chr1 1000\n").unwrap();
+    fs::write(&q_sizes_path, "chr2 1000\n").unwrap();
+
+    for (min_fill, has_small) in [("0", true), ("50", false)] {
+        let t_net_path = dir.path().join(format!("t{}.net", min_fill));
+        PgrCmd::new()
+            .args(&[
+                "chain",
+                "net",
+                chain_path.to_str().unwrap(),
+                t_sizes_path.to_str().unwrap(),
+                q_sizes_path.to_str().unwrap(),
+                t_net_path.to_str().unwrap(),
+                q_net_path.to_str().unwrap(),
+                "--min-score=0",
+                "--min-space=1",
+                "--min-fill",
+                min_fill,
+            ])
+            .run();
+
+        let t_net_content = fs::read_to_string(&t_net_path).unwrap();
+        assert!(t_net_content.contains(" fill 0 500 chr2 + 0 500"));
+        assert_eq!(t_net_content.contains("fill 150 20 "), has_small);
+    }
+
+    let (_, stderr) = PgrCmd::new()
+        .args(&[
+            "chain",
+            "net",
+            chain_path.to_str().unwrap(),
+            t_sizes_path.to_str().unwrap(),
+            q_sizes_path.to_str().unwrap(),
+            dir.path().join("t.net").to_str().unwrap(),
+            q_net_path.to_str().unwrap(),
+            "--min-score=-1",
+        ])
+        .run_fail();
+    assert!(stderr.contains("--min-score must not be negative"));
+}
+
 #[test]
 fn test_chain_anti_repeat() -> Result<(), Box<dyn std::error::Error>> {
     let dir = tempdir()?;
@@ -426,6 +517,55 @@ fn test_chain_pre_net_unsorted_fails() {
     );
 }
 
+#[test]
+fn test_chain_pre_net_filters() {
+    let dir = tempdir().unwrap();
+    let chain_path = dir.path().join("in.chain");
+    let t_sizes_path = dir.path().join("t.sizes");
+    let q_sizes_path = dir.path().join("q.sizes");
+    let out_path = dir.path().join("out.chain");
+
+    let c1 = "chain 1000 chr1 1000 + 0 100 chr2 1000 + 0 100 1\n100\n\n";
+    // 20 aligned bases over a 100 bp span
+    let c2 = "chain 500 chr1 1000 + 200 300 chr2 1000 + 200 300 2\n10 80 80\n10\n\n";
+    // Nested inside chain 1
+    let c3 = "chain 100 chr1 1000 + 20 30 chr2 1000 + 20 30 3\n10\n\n";
+    let c4 = "chain 50 chr1 1000 + 500 510 chr2 1000 + 500 510 4\n10\n\n";
+    fs::write(&chain_path, format!("{}{}{}{}", c1, c2, c3, c4)).unwrap();
+
+    fs::write(&t_sizes_path, "chr1 1000\n").unwrap();
+    fs::write(&q_sizes_path, "chr2 1000\n").unwrap();
+
+    let run = |extra: &[&str]| -> Vec<String> {
+        let mut args = vec![
+            "chain",
+            "pre-net",
+            chain_path.to_str().unwrap(),
+            t_sizes_path.to_str().unwrap(),
+            q_sizes_path.to_str().unwrap(),
+            "-o",
+            out_path.to_str().unwrap(),
+        ];
+        args.extend_from_slice(extra);
+        PgrCmd::new().args(&args).run();
+        fs::read_to_string(&out_path)
+            .unwrap()
+            .lines()
+            .filter(|l| l.starts_with("chain"))
+            .map(|l| l.to_string())
+            .collect()
+    };
+
+    // The nested chain is removed
+    let chains = run(&[]);
+    assert_eq!(chains.len(), 3);
+    assert!(chains.iter().all(|l| !l.starts_with("chain 100 ")));
+
+    let chains = run(&["--min-score", "60", "--min-coverage", "0.5"]);
+    assert_eq!(chains.len(), 1);
+    assert!(chains[0].starts_with("chain 1000 "));
+}
+
 // --- chain stitch tests ---
 
 #[test]
@@ -455,6 +595,40 @@ fn test_chain_stitch() {
     assert!(lines[0].contains("chain 300"));
 }
 
+#[test]
+fn test_chain_split_by_field_manifest() {
+    let dir = tempdir().unwrap();
+    let in_path = dir.path().join("in.chain");
+    let outdir = dir.path().join("split_out");
+
+    let c1 = "chain 300 chrA 1000 + 0 10 chrQ 1000 + 0 10 1\n10\n\n";
+    let c2 = "chain 200 chrB 1000 + 20 30 chrQ 1000 + 20 30 2\n10\n\n";
+    let c3 = "chain 100 chrA 1000 + 40 50 chrQ 1000 + 40 50 3\n10\n\n";
+    fs::write(&in_path, format!("{}{}{}", c1, c2, c3)).unwrap();
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "chain",
+            "split",
+            in_path.to_str().unwrap(),
+            "--by",
+            "tName",
+            "--outdir",
+            outdir.to_str().unwrap(),
+        ])
+        .run();
+
+    let manifest: Vec<&str> = stdout.lines().collect();
+    assert_eq!(manifest.len(), 2);
+    assert!(manifest[0].ends_with("chrA.chain"));
+    assert!(manifest[1].ends_with("chrB.chain"));
+
+    let chr_a = fs::read_to_string(outdir.join("chrA.chain")).unwrap();
+    assert_eq!(chr_a.lines().filter(|l| l.starts_with("chain")).count(), 2);
+    let chr_b = fs::read_to_string(outdir.join("chrB.chain")).unwrap();
+    assert_eq!(chr_b.lines().filter(|l| l.starts_with("chain")).count(), 1);
+}
+
 // --- chain split lump tests ---
 
 #[test]
@@ -492,6 +666,65 @@ fn test_chain_split_lump() {
     assert!(content1.contains("chain 200"));
 }
 
+#[test]
+fn test_chain_anti_repeat_penalty() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    let chain_path = dir.path().join("in.chain");
+    let out_path = dir.path().join("out.chain");
+    let t_fa_path = dir.path().join("target.fa");
+    let q_fa_path = dir.path().join("query.fa");
+
+    // Half of the aligned bases are soft-masked
+    let seq = ">chr1\nACGTAcgtac\n";
+    fs::write(&t_fa_path, seq)?;
+    fs::write(&q_fa_path, seq.replace("chr1", "chr2"))?;
+
+    let t_2bit_path = dir.path().join("target.2bit");
+    let q_2bit_path = dir.path().join("query.2bit");
+    for (fa, twobit) in [(&t_fa_path, &t_2bit_path), (&q_fa_path, &q_2bit_path)] {
+        let mut cmd_2bit = assert_cmd::Command::cargo_bin("pgr").unwrap();
+        cmd_2bit
+            .arg("fa")
+            .arg("to-2bit")
+            .arg(fa.to_str().unwrap())
+            .arg("-o")
+            .arg(twobit.to_str().unwrap());
+        cmd_2bit.assert().success();
+    }
+
+    let chain = "chain 1000 chr1 10 + 0 10 chr2 10 + 0 10 1\n10\n\n";
+    fs::write(&chain_path, chain)?;
+
+    let run = |penalty: &str, min_score: &str| -> Result<String, Box<dyn std::error::Error>> {
+        let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+        cmd.arg("chain")
+            .arg("anti-repeat")
+            .arg("--target-2bit")
+            .arg(t_2bit_path.to_str().unwrap())
+            .arg("--query-2bit")
+            .arg(q_2bit_path.to_str().unwrap())
+            .arg(chain_path.to_str().unwrap())
+            .arg("-o")
+            .arg(out_path.to_str().unwrap())
+            .arg("--min-score")
+            .arg(min_score)
+            .arg("--repeat-penalty")
+            .arg(penalty);
+        cmd.assert().success();
+        Ok(fs::read_to_string(&out_path)?)
+    };
+
+    // Default penalty: 1000 * 2 * (1 - 0.5) = 1000, kept
+    assert!(run("1.0", "800")?.contains("chain 1000"));
+    // Stronger penalty: 1000 * 2 * (1 - 0.75) = 500, dropped
+    assert!(!run("1.5", "800")?.contains("chain 1000"));
+    // No penalty: the raw score 1000 is compared, not doubled to 2000
+    assert!(run("0", "800")?.contains("chain 1000"));
+    assert!(!run("0", "1500")?.contains("chain 1000"));
+
+    Ok(())
+}
+
 // --- chain anti-repeat negative strand test ---
 
 #[test]
@@ -556,3 +789,38 @@ fn test_chain_anti_repeat_negative_strand() -> Result<(), Box<dyn std::error::Er
 
     Ok(())
 }
+
+#[test]
+fn test_chain_lift_block_and_gap() {
+    let dir = tempdir().unwrap();
+    let chain_path = dir.path().join("in.chain");
+
+    // Target 100-200 -> query 500-600, target gap 200-250, target 250-350 -> query 650-750
+    let chain = "chain 1000 chrT 1000 + 100 350 chrQ 1000 + 500 750 1\n100\t50\t50\n100\n\n";
+    fs::write(&chain_path, chain).unwrap();
+
+    // 151-230: 50 bases in the first block, 30 bases in the gap
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "chain",
+            "lift",
+            chain_path.to_str().unwrap(),
+            "chrT:151-230",
+            "--min-match",
+            "0.5",
+        ])
+        .run();
+    assert_eq!(stdout, "chrT:151-230\tchrQ:551-600\n");
+
+    // Default --min-match rejects the partially aligned range
+    let (stdout, stderr) = PgrCmd::new()
+        .args(&[
+            "chain",
+            "lift",
+            chain_path.to_str().unwrap(),
+            "chrT:151-230",
+        ])
+        .run();
+    assert!(stdout.is_empty());
+    assert!(stderr.contains("skipped"));
+}
@@ -0,0 +1,34 @@
+#[macro_use]
+#[path = "common/mod.rs"]
+mod common;
+
+use common::PgrCmd;
+
+#[test]
+fn command_clust_eval_separated() {
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "clust",
+            "eval",
+            "tests/clust/two.phy",
+            "--clusters",
+            "tests/clust/two.tsv",
+        ])
+        .run();
+    assert_eq!(
+        stdout,
+        "cluster\tsize\tsilhouette\n1\t2\t0.9000\n2\t2\t0.9000\nall\t4\t0.9000\n"
+    );
+
+    // Mixing the two groups gives a negative score
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "clust",
+            "eval",
+            "tests/clust/two.phy",
+            "--clusters",
+            "tests/clust/mixed.tsv",
+        ])
+        .run();
+    assert!(stdout.ends_with("all\t4\t-0.4500\n"), "{}", stdout);
+}
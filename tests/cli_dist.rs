@@ -132,3 +132,276 @@ fn command_dist_seq_merge() {
         fixture("seq.fa").to_str().unwrap()
     )));
 }
+
+#[test]
+fn command_dist_hv_params_identical() {
+    let seq = fixture("seq.fa");
+    let seq = seq.to_str().unwrap();
+    for (kmer, sketch) in [("5", "256"), ("11", "1024"), ("21", "8192")] {
+        let (stdout, _) = PgrCmd::new()
+            .args(&["dist", "hv", seq, seq, "-k", kmer, "--sketch", sketch])
+            .run();
+
+        let fields: Vec<&str> = stdout.trim_end().split('\t').collect();
+        assert_eq!(fields[6], "0.0000", "k={} sketch={}", kmer, sketch);
+    }
+}
+
+#[test]
+fn command_dist_hv_sketch_resolution() {
+    // Five variants of one random sequence, each with a base changed every
+    // 40 bp at its own offset, so all share the same true Jaccard index with
+    // the original. A larger sketch estimates it more consistently.
+    let tempdir = tempfile::TempDir::new().unwrap();
+    let mut state = 0x2545F4914F6CDD1Du64;
+    let base: Vec<u8> = (0..2000)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            b"ACGT"[(state % 4) as usize]
+        })
+        .collect();
+
+    let write_fa = |name: &str, seq: &[u8]| {
+        let path = tempdir.path().join(format!("{}.fa", name));
+        let record = format!(">{}\n{}\n", name, String::from_utf8_lossy(seq));
+        std::fs::write(&path, record).unwrap();
+        path.to_str().unwrap().to_string()
+    };
+    let base_lst = tempdir.path().join("base.lst");
+    std::fs::write(&base_lst, write_fa("base", &base) + "\n").unwrap();
+    let mut variants = String::new();
+    for v in 0..5 {
+        let seq: Vec<u8> = base
+            .iter()
+            .enumerate()
+            .map(|(i, &b)| match (i % 40 == 5 + v * 7, b) {
+                (false, _) => b,
+                (true, b'A') => b'C',
+                (true, b'C') => b'G',
+                (true, b'G') => b'T',
+                (true, _) => b'A',
+            })
+            .collect();
+        variants += &(write_fa(&format!("var{}", v), &seq) + "\n");
+    }
+    let variants_lst = tempdir.path().join("variants.lst");
+    std::fs::write(&variants_lst, variants).unwrap();
+
+    // Range of the Jaccard estimates across the five pairs
+    let spread = |sketch: &str| -> f64 {
+        let (stdout, _) = PgrCmd::new()
+            .args(&[
+                "dist",
+                "hv",
+                base_lst.to_str().unwrap(),
+                variants_lst.to_str().unwrap(),
+                "--list-files",
+                "-k",
+                "11",
+                "-w",
+                "1",
+                "--sketch",
+                sketch,
+            ])
+            .run();
+        let jaccards: Vec<f64> = stdout
+            .lines()
+            .map(|l| l.split('\t').nth(7).unwrap().parse().unwrap())
+            .collect();
+        assert_eq!(jaccards.len(), 5);
+        let max = jaccards.iter().copied().fold(f64::MIN, f64::max);
+        let min = jaccards.iter().copied().fold(f64::MAX, f64::min);
+        max - min
+    };
+
+    let small = spread("32");
+    let large = spread("4096");
+    assert!(large < small, "{} vs {}", large, small);
+}
+
+#[test]
+fn command_dist_hv_kmer_too_large() {
+    let (_, stderr) = PgrCmd::new()
+        .args(&[
+            "dist",
+            "hv",
+            fixture("seq.fa").to_str().unwrap(),
+            "--hasher",
+            "mod",
+            "-k",
+            "33",
+        ])
+        .run_fail();
+
+    assert!(stderr.contains("hash width"));
+}
+
+#[test]
+fn command_dist_seq_minhash() {
+    let tempdir = tempfile::TempDir::new().unwrap();
+    let infile = tempdir.path().join("minhash.fa");
+    std::fs::write(
+        &infile,
+        ">fwd\nGCTAAAGACAATTACATAACATACACGTCAGCACGAAACTTGTTGGCCCAGTGTGAATCGCTTAAGGGTTAAGTAAGTGTGATGCATACGCCTTTACTTGCTGTGTCCACCCCATCGGAC\n\
+         >dup\nGCTAAAGACAATTACATAACATACACGTCAGCACGAAACTTGTTGGCCCAGTGTGAATCGCTTAAGGGTTAAGTAAGTGTGATGCATACGCCTTTACTTGCTGTGTCCACCCCATCGGAC\n\
+         >rc\nGTCCGATGGGGTGGACACAGCAAGTAAAGGCGTATGCATCACACTTACTTAACCCTTAAGCGATTCACACTGGGCCAACAAGTTTCGTGCTGACGTGTATGTTATGTAATTGTCTTTAGC\n",
+    )
+    .unwrap();
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "dist",
+            "seq",
+            infile.to_str().unwrap(),
+            "--minhash",
+            "-k",
+            "11",
+            "--sketch",
+            "50",
+        ])
+        .run();
+
+    assert_eq!(stdout.lines().count(), 9);
+    assert!(stdout.contains("fwd\tdup\t0.0000\t1.0000\t1.0000"));
+    assert!(stdout.contains("fwd\trc\t0.0000\t1.0000\t1.0000"));
+}
+
+#[test]
+fn command_dist_seq_d2star() {
+    let tempdir = tempfile::TempDir::new().unwrap();
+    let infile = tempdir.path().join("d2star.fa");
+    // dup: identical; mut: two substitutions; other: unrelated random sequence.
+    std::fs::write(
+        &infile,
+        ">fwd\nGCTAAAGACAATTACATAACATACACGTCAGCACGAAACTTGTTGGCCCAGTGTGAATCGCTTAAGGGTTAAGTAAGTGTGATGCATACGCCTTTACTTGCTGTGTCCACCCCATCGGAC\n\
+         >dup\nGCTAAAGACAATTACATAACATACACGTCAGCACGAAACTTGTTGGCCCAGTGTGAATCGCTTAAGGGTTAAGTAAGTGTGATGCATACGCCTTTACTTGCTGTGTCCACCCCATCGGAC\n\
+         >mut\nGCTAAAGACAATTACATAACATACACGTCACCACGAAACTTGTTGGCCCAGTGTGAATCGCTTAAGGGTTAAGTAAGTGTGATGCATACGTCTTTACTTGCTGTGTCCACCCCATCGGAC\n\
+         >other\nTTTCCTCATGCAATTCAAAACCATGTCCGTAATGTAGGCGAAATAGTAAACCATTTTACGGAGGATACCAAATTCCTCCTTATTCAGGACCTAACCTGAGGTAAACCAGGTCTCTCCGCC\n",
+    )
+    .unwrap();
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "dist",
+            "seq",
+            infile.to_str().unwrap(),
+            "--d2star",
+            "-k",
+            "8",
+        ])
+        .run();
+
+    assert_eq!(stdout.lines().count(), 16);
+    let dist = |name: &str| -> f64 {
+        stdout
+            .lines()
+            .find(|l| l.starts_with(&format!("fwd\t{}\t", name)))
+            .unwrap()
+            .split('\t')
+            .nth(2)
+            .unwrap()
+            .parse()
+            .unwrap()
+    };
+    assert_eq!(dist("dup"), 0.0);
+    assert!(dist("mut") > 0.0);
+    assert!(dist("other") > dist("mut"));
+}
+
+#[test]
+fn command_dist_seq_protein() {
+    let tempdir = tempfile::TempDir::new().unwrap();
+    let infile = tempdir.path().join("protein.fa");
+    // 10 comparable sites (X and the gap column are skipped), 2 differences.
+    std::fs::write(&infile, ">p1\nMKVLAGHWTEX-\n>p2\nMKILAGHWSEWW\n").unwrap();
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "dist",
+            "seq",
+            infile.to_str().unwrap(),
+            "--alphabet",
+            "protein",
+        ])
+        .run();
+
+    assert_eq!(stdout.lines().count(), 4);
+    assert!(stdout.contains("p1\tp1\t0.0000\t1.0000\t10\n"));
+    // -ln(1 - 0.2) = 0.2231
+    assert!(stdout.contains("p1\tp2\t0.2231\t0.8000\t10\n"));
+}
+
+#[test]
+fn command_dist_seq_protein_not_comparable() {
+    let tempdir = tempfile::TempDir::new().unwrap();
+    let infile = tempdir.path().join("protein.fa");
+
+    // Unaligned sequences are an error
+    std::fs::write(&infile, ">p1\nMKVLAGHWTE\n>p2\nMKILAGHW\n").unwrap();
+    let (_, stderr) = PgrCmd::new()
+        .args(&[
+            "dist",
+            "seq",
+            infile.to_str().unwrap(),
+            "--alphabet",
+            "protein",
+        ])
+        .run_fail();
+    assert!(stderr.contains("needs aligned sequences"));
+
+    // A pair without comparable residues is skipped with a warning
+    std::fs::write(&infile, ">p1\nMKVLAGHWTE\n>p3\nXXXXXXXXXX\n").unwrap();
+    let (stdout, stderr) = PgrCmd::new()
+        .args(&[
+            "dist",
+            "seq",
+            infile.to_str().unwrap(),
+            "--alphabet",
+            "protein",
+        ])
+        .run();
+    assert_eq!(stdout, "p1\tp1\t0.0000\t1.0000\t10\n");
+    assert!(stderr.contains("skipped p1 vs p3"));
+}
+
+#[test]
+fn command_dist_vector() {
+    let (stdout, _) = PgrCmd::new()
+        .args(&["dist", "vector", fixture("vectors.tsv").to_str().unwrap()])
+        .run();
+
+    assert_eq!(stdout.lines().count(), 16);
+    assert!(stdout.contains("a\ta\t0.0000"));
+    assert!(stdout.contains("a\tb\t2.2361"));
+}
+
+#[test]
+fn command_dist_vector_pca() {
+    let (stdout, stderr) = PgrCmd::new()
+        .args(&[
+            "dist",
+            "vector",
+            fixture("vectors.tsv").to_str().unwrap(),
+            "--pca",
+            "2",
+        ])
+        .run();
+
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0], "name\tPC1\tPC2");
+    let first = |l: &str| l.split('\t').nth(1).unwrap().parse::<f64>().unwrap();
+    assert!(first(lines[1]) * first(lines[4]) < 0.0);
+
+    // The first component carries almost all of the variance
+    let pc1: f64 = stderr
+        .lines()
+        .find_map(|l| l.split_once("PC1: "))
+        .and_then(|(_, rest)| rest.split_whitespace().next())
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(pc1 > 0.99, "{}", stderr);
+}
@@ -44,6 +44,33 @@ fn command_fa_size() {
     assert!(stdout.contains("seq2\t8\n"));
 }
 
+#[test]
+fn command_fa_size_sort_cumulative() {
+    let input = ">a\nACGT\n>b\nACGTACGTAC\n>c\nACGTAC\n";
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "fa",
+            "size",
+            "stdin",
+            "--sort",
+            "desc",
+            "--cumulative",
+            "--total",
+        ])
+        .stdin(input)
+        .run();
+
+    assert_eq!(stdout, "b\t10\t10\nc\t6\t16\na\t4\t20\ntotal\t20\n");
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fa", "size", "stdin", "--sort", "asc"])
+        .stdin(input)
+        .run();
+
+    assert_eq!(stdout, "a\t4\nc\t6\nb\t10\n");
+}
+
 #[test]
 fn command_fa_size_file() {
     let (stdout, _) = PgrCmd::new()
@@ -232,6 +259,79 @@ fn command_rc() {
     assert!(!stdout.contains("GgacTgcggCTagAA"), "read46");
 }
 
+#[test]
+fn command_rc_range_name() {
+    let input = ">chr(+):10-20\nAACCG\n>I:1-4 desc\nACGA\n>plain\nAAC\n";
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fa", "rc", "stdin"])
+        .stdin(input)
+        .run();
+
+    assert!(stdout.contains(">chr(-):10-20\nCGGTT\n"));
+    assert!(stdout.contains(">I(-):1-4 desc\nTCGT\n"));
+    assert!(stdout.contains(">RC_plain\nGTT\n"));
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fa", "rc", "stdin", "--annotate"])
+        .stdin(input)
+        .run();
+
+    assert!(stdout.contains(">chr(-):10-20\n"));
+    assert!(stdout.contains(">plain(RC)\nGTT\n"));
+
+    // -c wins over the strand flip
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fa", "rc", "stdin", "-c"])
+        .stdin(input)
+        .run();
+
+    assert!(stdout.contains(">chr(+):10-20\nCGGTT\n"));
+    assert!(stdout.contains(">I:1-4 desc\nTCGT\n"));
+    assert!(stdout.contains(">plain\nGTT\n"));
+}
+
+#[test]
+fn command_rc_invalid_base() {
+    let (_, stderr) = PgrCmd::new()
+        .args(&["fa", "rc", "stdin"])
+        .stdin(">seq\nAC*GT\n")
+        .run_fail();
+
+    assert!(stderr.contains("seq: invalid base '*'"), "{}", stderr);
+}
+
+#[test]
+fn command_translate() {
+    let input = ">orf\nATGGCCTAA\n";
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fa", "translate", "stdin", "--frame", "1"])
+        .stdin(input)
+        .run();
+    assert_eq!(stdout, ">orf\nMA*\n");
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fa", "translate", "stdin", "--orf"])
+        .stdin(input)
+        .run();
+    assert_eq!(stdout, ">orf\nMA\n");
+
+    // TTAGGCCAT is the reverse complement
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fa", "translate", "stdin", "--frame", "-1"])
+        .stdin(">rc\nTTAGGCCAT\n")
+        .run();
+    assert_eq!(stdout, ">rc\nMA*\n");
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fa", "translate", "stdin", "--frame", "all"])
+        .stdin(input)
+        .run();
+    assert_eq!(stdout.lines().filter(|l| l.starts_with('>')).count(), 6);
+    assert!(stdout.contains(">orf|frame=-3\n"));
+}
+
 #[test]
 fn command_count() {
     let (stdout, _) = PgrCmd::new()
@@ -274,6 +374,28 @@ fn command_replace() {
     assert!(!stdout.contains(">read0"), "read0");
 }
 
+#[test]
+fn command_replace_map_regex() {
+    let temp = TempDir::new().unwrap();
+    let map = temp.path().join("map.tsv");
+    fs::write(&map, "seq1\tchrA\nseq2\tchrB\n").unwrap();
+    let input = ">seq1 first\nACGT\n>seq2\nGGCC\n>seq3\nTTAA\n";
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fa", "replace", "stdin", "--map", map.to_str().unwrap()])
+        .stdin(input)
+        .run();
+
+    assert_eq!(stdout, ">chrA first\nACGT\n>chrB\nGGCC\n>seq3\nTTAA\n");
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fa", "replace", "stdin", "--regex", r"^seq(\d)$", "chr$1"])
+        .stdin(input)
+        .run();
+
+    assert_eq!(stdout, ">chr1 first\nACGT\n>chr2\nGGCC\n>chr3\nTTAA\n");
+}
+
 #[test]
 fn command_filter() {
     let (stdout, _) = PgrCmd::new()
@@ -339,6 +461,44 @@ fn command_filter_fmt() {
     assert!(stdout.contains(">read simplify\nAGGG"), "simplify");
 }
 
+#[test]
+fn command_filter_name_regex() {
+    let input = ">chr1\nACGT\n>chr10\nACGT\n>chrUn_1\nACGT\n>scaffold2\nACGT\n";
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fa", "filter", "stdin", "--name-regex", r"^chr\d+$"])
+        .stdin(input)
+        .run();
+
+    assert_eq!(stdout, ">chr1\nACGT\n>chr10\nACGT\n");
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "fa",
+            "filter",
+            "stdin",
+            "--name-regex",
+            r"^chr\d+$",
+            "--invert",
+        ])
+        .stdin(input)
+        .run();
+
+    assert_eq!(stdout, ">chrUn_1\nACGT\n>scaffold2\nACGT\n");
+
+    let (_, stderr) = PgrCmd::new()
+        .args(&[
+            "fa",
+            "filter",
+            "tests/fasta/ufasta.fa",
+            "--name-regex",
+            "chr(",
+        ])
+        .run_fail();
+
+    assert!(stderr.contains("Invalid --name-regex"));
+}
+
 #[test]
 fn command_dedup() {
     let (stdout, _) = PgrCmd::new()
@@ -616,3 +776,72 @@ fn command_fa_one_success() {
     assert!(stdout.contains(">seq2"));
     assert!(stdout.contains("ACGTACGT"));
 }
+
+#[test]
+fn command_count_comp() {
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fa", "count", "stdin", "--comp", "--total"])
+        .stdin(">r1\nACGTacgtNNRY\n>r2\nGGGG\n")
+        .run();
+
+    assert_eq!(stdout.lines().count(), 4);
+    assert!(stdout.contains("#seq\tlen\tGC%\tN\tlower\tother\n"));
+    assert!(stdout.contains("r1\t12\t50.00\t2\t4\t2\n"));
+    assert!(stdout.contains("r2\t4\t100.00\t0\t0\t0\n"));
+    assert!(stdout.contains("total\t16\t66.67\t2\t4\t2\n"));
+}
+
+#[test]
+fn command_range_strand_list() {
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "fa",
+            "range",
+            "tests/index/final.contigs.fa",
+            "k81_130:11-20,k81_130(-):11-20",
+        ])
+        .run();
+
+    assert_eq!(stdout.lines().count(), 4);
+    assert!(stdout.contains(">k81_130:11-20\nGGTGAATCAA\n"));
+    assert!(stdout.contains(">k81_130(-):11-20\nTTGATTCACC\n"));
+}
+
+#[test]
+fn command_range_clamp() {
+    let (stdout, stderr) = PgrCmd::new()
+        .args(&[
+            "fa",
+            "range",
+            "tests/index/final.contigs.fa",
+            "k81_130:225-300",
+            "k81_130:300-400",
+        ])
+        .run();
+
+    assert_eq!(stdout.lines().count(), 2);
+    assert!(stdout.contains(">k81_130:225-232\nGAGGTTCA\n"));
+    assert!(stderr.contains("clamped"));
+    assert!(stderr.contains("skipped"));
+}
+
+#[test]
+fn command_order_append_missing() {
+    let tempdir = TempDir::new().unwrap();
+    let list = tempdir.path().join("list.txt");
+    fs::write(&list, "seqC\nseqA\nseqX\n").unwrap();
+
+    let (stdout, stderr) = PgrCmd::new()
+        .args(&[
+            "fa",
+            "order",
+            "stdin",
+            list.to_str().unwrap(),
+            "--append-missing",
+        ])
+        .stdin(">seqA\nAAAA\n>seqB\nCCCC\n>seqC\nGGGG\n")
+        .run();
+
+    assert_eq!(stdout, ">seqC\nGGGG\n>seqA\nAAAA\n>seqB\nCCCC\n");
+    assert!(stderr.contains("seqX not found"));
+}
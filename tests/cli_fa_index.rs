@@ -112,6 +112,41 @@ fn command_fa_gz_level() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn command_fa_gz_plain_gzip_roundtrip() -> anyhow::Result<()> {
+    use std::io::Read;
+
+    let temp = TempDir::new()?;
+    let infile = "tests/fasta/ufasta.fa";
+    let outfile = temp.path().join("ufasta.fa.gz");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    cmd.arg("fa")
+        .arg("gz")
+        .arg(infile)
+        .arg("-o")
+        .arg(outfile.to_str().unwrap())
+        .arg("--level")
+        .arg("6")
+        .assert()
+        .success();
+
+    // BGZF ends with an empty-block EOF marker
+    let compressed = fs::read(&outfile)?;
+    let eof_marker: [u8; 28] = [
+        0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, 0x42, 0x43, 0x02,
+        0x00, 0x1b, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+    assert!(compressed.ends_with(&eof_marker));
+
+    // A standard multi-member gzip decoder restores the original bytes
+    let mut decoded = vec![];
+    flate2::read::MultiGzDecoder::new(&compressed[..]).read_to_end(&mut decoded)?;
+    assert_eq!(decoded, fs::read(infile)?);
+
+    Ok(())
+}
+
 #[test]
 fn command_fa_gz_reindex() -> anyhow::Result<()> {
     let temp = TempDir::new()?;
@@ -165,6 +200,110 @@ fn command_fa_gz_reindex_fail_not_bgzf() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn command_fa_index() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let infile = temp.path().join("small.fa");
+    fs::write(&infile, ">a desc\nACGTA\nCGTAC\nGG\n>b\nTTTT\n")?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    cmd.arg("fa")
+        .arg("index")
+        .arg(infile.to_str().unwrap())
+        .assert()
+        .success();
+
+    // a: header is 8 bytes; 12 bases in lines of 5 (6 bytes)
+    // b: starts at 8 + 6 + 6 + 3 + 3 = 26
+    let fai = fs::read_to_string(temp.path().join("small.fa.fai"))?;
+    assert_eq!(fai, "a\t12\t8\t5\t6\nb\t4\t26\t4\t5\n");
+
+    Ok(())
+}
+
+#[test]
+fn command_fa_index_inconsistent_lines() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let infile = temp.path().join("ragged.fa");
+    fs::write(&infile, ">ok\nACGT\nAC\n>ragged\nACG\nACGTA\n")?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    cmd.arg("fa")
+        .arg("index")
+        .arg(infile.to_str().unwrap())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "record ragged has inconsistent line lengths",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn command_fa_index_plain_gzip() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let infile = temp.path().join("ufasta.fa.gz");
+    fs::copy("tests/fasta/ufasta.fa.gz", &infile)?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    cmd.arg("fa")
+        .arg("index")
+        .arg(infile.to_str().unwrap())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must be BGZF-compressed"));
+    assert!(!temp.path().join("ufasta.fa.gz.fai").exists());
+
+    Ok(())
+}
+
+#[test]
+fn command_range_fai() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+
+    // Two records wrapped at 10 bases, so regions cross line breaks
+    let seq1: String = "ACGTTGCAAG".repeat(9) + "ACGTT";
+    let seq2: String = "GGGCCCATAT".repeat(3);
+    let wrap = |s: &str| {
+        s.as_bytes()
+            .chunks(10)
+            .map(|c| format!("{}\n", std::str::from_utf8(c).unwrap()))
+            .collect::<String>()
+    };
+    let infile = temp.path().join("wrapped.fa");
+    fs::write(
+        &infile,
+        format!(">s1 desc\n{}>s2\n{}", wrap(&seq1), wrap(&seq2)),
+    )?;
+    let infile = infile.to_str().unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    cmd.arg("fa").arg("index").arg(infile).assert().success();
+
+    let ranges = ["s1:8-23", "s1(-):1-95", "s2:30-30", "s2", "s1:90-120"];
+    let run = |extra: &[&str]| -> anyhow::Result<String> {
+        let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+        let output = cmd
+            .arg("fa")
+            .arg("range")
+            .arg(infile)
+            .args(ranges)
+            .args(extra)
+            .output()?;
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    let scanned = run(&[])?;
+    let indexed = run(&["--fai", &format!("{}.fai", infile)])?;
+
+    assert_eq!(scanned.lines().count(), 10);
+    assert!(indexed.contains(">s1:8-23\nAAGACGTTGCAAGACG\n"));
+    assert_eq!(indexed, scanned);
+
+    Ok(())
+}
+
 #[test]
 fn command_range() -> anyhow::Result<()> {
     let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
@@ -24,6 +24,30 @@ fn command_name() {
     assert!(stdout.contains("S288c\t3\nYJM789\t3\nRM11"), "name order");
 }
 
+#[test]
+fn command_name_dup() {
+    let input = ">A.I(+):1-4\nACGT\n>B.I(+):1-4\nACGT\n\n\
+                 >A.I(+):1-4\nACGT\n>A.II(+):1-4\nACGA\n\n";
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "name", "stdin", "--check-dup"])
+        .stdin(input)
+        .run();
+    assert_eq!(stdout, "1\tA\n");
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "name", "stdin", "--rename-dup"])
+        .stdin(input)
+        .run();
+    assert!(stdout.contains(">A.I(+):1-4\nACGT\n>A_1.II(+):1-4\nACGA\n"));
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "name", "stdin"])
+        .stdin(&stdout)
+        .run();
+    assert_eq!(stdout, "A\nB\nA_1\n");
+}
+
 #[test]
 fn command_cover() {
     let (stdout, _) = PgrCmd::new()
@@ -54,6 +78,52 @@ fn command_cover() {
     assert!(stdout.contains("13277,184906"), "trimmed");
 }
 
+#[test]
+fn command_cover_ranges() {
+    // S1 covers two adjacent blocks; S2 has a gap in the first one
+    let input = ">S1.I(+):1-5\nACGTA\n>S2.II(+):1-4\nAC-TA\n\n\
+                 >S1.I(+):6-10\nCCGGT\n>S2.II(+):11-15\nCCGGT\n\n";
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "cover", "stdin", "--ranges"])
+        .stdin(input)
+        .run();
+    assert_eq!(stdout, "S1.I:1-10\nS2.II:1-4\nS2.II:11-15\n");
+
+    // --min-cov drops the column where only S1 has a base
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "fas",
+            "cover",
+            "stdin",
+            "--ranges",
+            "--min-cov",
+            "2",
+            "--name",
+            "S1",
+        ])
+        .stdin(input)
+        .run();
+    assert_eq!(stdout, "I:1-2\nI:4-10\n");
+
+    // --trim shrinks each aligned range before the --min-cov filter
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "fas",
+            "cover",
+            "stdin",
+            "--ranges",
+            "--min-cov",
+            "2",
+            "--trim",
+            "1",
+            "--name",
+            "S1",
+        ])
+        .stdin(input)
+        .run();
+    assert_eq!(stdout, "I:2-2\nI:4-4\nI:7-9\n");
+}
+
 #[test]
 fn command_concat() {
     let (stdout, _) = PgrCmd::new()
@@ -123,6 +193,46 @@ fn command_subset() {
     assert!(stdout.lines().next().unwrap().contains("Spar")); // >Spar.
 }
 
+#[test]
+fn command_subset_keep_drop() {
+    let tempdir = TempDir::new().unwrap();
+    let list = tempdir.path().join("keep.lst");
+    fs::write(&list, "Human\nMouse\n").unwrap();
+
+    let input = ">Human.chr1(+):1-4\nACGT\n>Mouse.chr2(+):1-4\nACGA\n>Rat.chr3(+):1-4\nACGG\n\n>Rat.chr3(+):11-14\nTTTT\n\n";
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "subset", "stdin", "--keep", list.to_str().unwrap()])
+        .stdin(input)
+        .run();
+    assert_eq!(
+        stdout,
+        ">Human.chr1(+):1-4\nACGT\n>Mouse.chr2(+):1-4\nACGA\n\n"
+    );
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "subset", "stdin", "--drop", list.to_str().unwrap()])
+        .stdin(input)
+        .run();
+    assert_eq!(
+        stdout,
+        ">Rat.chr3(+):1-4\nACGG\n\n>Rat.chr3(+):11-14\nTTTT\n\n"
+    );
+
+    PgrCmd::new()
+        .args(&[
+            "fas",
+            "subset",
+            "stdin",
+            "--keep",
+            list.to_str().unwrap(),
+            "--drop",
+            list.to_str().unwrap(),
+        ])
+        .stdin(input)
+        .run_fail();
+}
+
 #[test]
 fn command_link() {
     let (stdout, _) = PgrCmd::new()
@@ -149,6 +259,23 @@ fn command_link() {
     assert_eq!(stdout.lines().next().unwrap().split_whitespace().count(), 2);
 }
 
+#[test]
+fn command_link_edges() {
+    // 4 columns where both have a base, one of them mismatched
+    let input = ">S1.I(+):1-5\nACGT-A\n>S2.I(+):11-15\nAC-TTC\n\n";
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "link", "stdin", "--edges"])
+        .stdin(input)
+        .run();
+    assert_eq!(stdout, "S1.I(+):1-5\tS2.I(+):11-15\t4\n");
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "link", "stdin", "--edges", "--weight", "identity"])
+        .stdin(input)
+        .run();
+    assert_eq!(stdout, "S1.I(+):1-5\tS2.I(+):11-15\t0.7500\n");
+}
+
 #[test]
 fn command_replace() {
     let (stdout, _) = PgrCmd::new()
@@ -197,6 +324,30 @@ fn command_replace() {
     assert!(!stdout.contains("13267-13287"), "block removed");
 }
 
+#[test]
+fn command_replace_with_consensus() {
+    let input =
+        ">A.I(+):1-5\nACGTA\n>B.I(+):11-15\nACTTA\n>C.I(+):21-25\nGCTTA\n>D.I(+):31-34\nAC-TC\n\n";
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "replace", "stdin", "--with-consensus", "A"])
+        .stdin(input)
+        .run();
+
+    // A's row takes the majority base of every column; its length and header are unchanged
+    assert_eq!(
+        stdout,
+        ">A.I(+):1-5\nACTTA\n>B.I(+):11-15\nACTTA\n>C.I(+):21-25\nGCTTA\n>D.I(+):31-34\nAC-TC\n\n"
+    );
+
+    // Filling a gap lengthens the row, and its end moves with it
+    let input = ">A.I(+):1-4\nAC-GT\n>B.I(+):11-15\nACAGT\n>C.I(+):21-25\nACAGT\n\n";
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "replace", "stdin", "--with-consensus", "A"])
+        .stdin(input)
+        .run();
+    assert!(stdout.starts_with(">A.I(+):1-5\nACAGT\n"), "{}", stdout);
+}
+
 #[test]
 fn command_check() {
     let (stdout, _) = PgrCmd::new()
@@ -250,6 +401,41 @@ fn command_create() {
     assert!(stdout.lines().next().unwrap().contains(">S288c."));
 }
 
+#[test]
+fn command_create_from_chain() {
+    let tempdir = TempDir::new().unwrap();
+    let t_2bit = tempdir.path().join("t.2bit");
+    let q_2bit = tempdir.path().join("q.2bit");
+    for (fa, twobit) in [
+        ("tests/fas/create.t.fa", &t_2bit),
+        ("tests/fas/create.q.fa", &q_2bit),
+    ] {
+        PgrCmd::new()
+            .args(&["fa", "to-2bit", fa, "-o", twobit.to_str().unwrap()])
+            .run();
+    }
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "fas",
+            "create",
+            "--from-chain",
+            "tests/fas/create.chain",
+            "--t-2bit",
+            t_2bit.to_str().unwrap(),
+            "--q-2bit",
+            q_2bit.to_str().unwrap(),
+        ])
+        .run();
+
+    // The target row is the target slice; the minus-strand query is
+    // reverse-complemented, with a dash run for the dt gap
+    assert_eq!(
+        stdout,
+        ">target.tchr(+):1-12\nAAAACCCCGGGG\n>query.qchr(-):3-12\nAAAA--CCGGGG\n\n"
+    );
+}
+
 #[test]
 fn command_separate() {
     let (stdout, _) = PgrCmd::new()
@@ -291,6 +477,64 @@ fn command_separate_to() {
     tempdir.close().unwrap();
 }
 
+#[test]
+fn command_separate_template() {
+    let tempdir = TempDir::new().unwrap();
+    let tempdir_str = tempdir.path().to_str().unwrap();
+    let input = ">Human.chr1(+):11-20\nACGTACGTAC\n>Mouse.chr2(-):101-110\nACGTACGTAC\n\n";
+
+    PgrCmd::new()
+        .args(&[
+            "fas",
+            "separate",
+            "stdin",
+            "--template",
+            "{species}/{chr}_{start}-{end}.fa",
+            "-o",
+            tempdir_str,
+        ])
+        .stdin(input)
+        .assert()
+        .success();
+
+    assert!(tempdir.path().join("Human/chr1_11-20.fa").is_file());
+    assert!(tempdir.path().join("Mouse/chr2_101-110.fa").is_file());
+
+    // Without {species}, two species would share one file; nothing is written
+    let faildir = TempDir::new().unwrap();
+    PgrCmd::new()
+        .args(&[
+            "fas",
+            "separate",
+            "stdin",
+            "--template",
+            "{chr}.fa",
+            "-o",
+            faildir.path().to_str().unwrap(),
+        ])
+        .stdin(input)
+        .run_fail();
+    assert!(!faildir.path().join("chr1.fa").exists());
+
+    // A range seen twice keeps both records in its file
+    let input = ">Human.chr1(+):11-20\nACGTACGTAC\n\n>Human.chr1(+):11-20\nACGTACGTAA\n\n";
+    PgrCmd::new()
+        .args(&[
+            "fas",
+            "separate",
+            "stdin",
+            "--template",
+            "{chr}_{start}.fa",
+            "-o",
+            tempdir_str,
+        ])
+        .stdin(input)
+        .assert()
+        .success();
+    let content = fs::read_to_string(tempdir.path().join("chr1_11.fa")).unwrap();
+    assert_eq!(content.matches('>').count(), 2);
+}
+
 #[test]
 fn command_split() {
     let (stdout, _) = PgrCmd::new()
@@ -359,6 +603,43 @@ fn command_split_to_simple() {
     tempdir.close().unwrap();
 }
 
+#[test]
+fn command_split_by_chrom() {
+    let tempdir = TempDir::new().unwrap();
+    let tempdir_str = tempdir.path().to_str().unwrap();
+    let infile = tempdir.path().join("two_chroms.fas");
+    fs::write(
+        &infile,
+        ">S288c.I(+):1-4\nACGT\n>Spar.1(+):1-4\nACGA\n\n\
+         >S288c.II(+):11-14\nTTGC\n>Spar.2(-):5-8\nTTGC\n\n\
+         >Spar.3(+):1-4\nGGCC\n>S288c.I(+):21-24\nGGCA\n\n",
+    )
+    .unwrap();
+
+    PgrCmd::new()
+        .args(&[
+            "fas",
+            "split",
+            infile.to_str().unwrap(),
+            "--by-chrom",
+            "S288c",
+            "-o",
+            tempdir_str,
+        ])
+        .assert()
+        .success()
+        .stdout(predicates::str::is_empty());
+
+    // The reference row is found regardless of its position in the block
+    let chr_i = fs::read_to_string(tempdir.path().join("I.fas")).unwrap();
+    assert_eq!(chr_i.matches(">S288c.I(+)").count(), 2);
+    let chr_ii = fs::read_to_string(tempdir.path().join("II.fas")).unwrap();
+    assert!(chr_ii.contains(">S288c.II(+):11-14\n"));
+    assert!(!tempdir.path().join("unassigned.fas").exists());
+
+    tempdir.close().unwrap();
+}
+
 #[test]
 fn command_refine() {
     let (stdout, _) = PgrCmd::new()
@@ -703,6 +984,28 @@ fn command_variation() {
     assert!(stdout.lines().count() > 1, "has data rows");
 }
 
+#[test]
+fn command_variation_window() {
+    // Three variants clustered in 1-10, one at 25
+    let input = ">S1.I(+):1-30\nAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA\n>S2.I(+):1-30\nATATATAAAAAAAAAAAAAAAAAATAAAAA\n\n";
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "variation", "stdin", "--window", "10", "--step", "8"])
+        .stdin(input)
+        .run();
+
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines[0],
+        "#target\tchr\tstart\tend\twidth\tvariants\tdensity"
+    );
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[1], "S1.I(+):1-30\tI\t1\t10\t10\t3\t0.3000");
+    assert_eq!(lines[2], "S1.I(+):1-30\tI\t9\t18\t10\t0\t0.0000");
+    assert_eq!(lines[3], "S1.I(+):1-30\tI\t17\t26\t10\t1\t0.1000");
+    // Partial trailing window keeps its actual width
+    assert_eq!(lines[4], "S1.I(+):1-30\tI\t25\t30\t6\t1\t0.1667");
+}
+
 #[test]
 fn command_to_vcf() {
     let (stdout, _) = PgrCmd::new()
@@ -985,3 +1288,36 @@ fn command_split_simple_stdout() {
         "simple headers should not contain coordinates"
     );
 }
+
+#[test]
+fn command_fas_logo() {
+    let temp = TempDir::new().unwrap();
+    let fas_file = temp.path().join("logo.fas");
+    fs::write(
+        &fas_file,
+        ">A.chr1(+):1-3\nAA-\n>B.chr1(+):1-3\nAC-\n>C.chr1(+):1-3\nAGA\n>D.chr1(+):1-3\nATA\n",
+    )
+    .unwrap();
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "logo", fas_file.to_str().unwrap()])
+        .run();
+
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 4, "header plus three columns");
+    assert_eq!(lines[0], "target\tcol\tA\tC\tG\tT\t-\tic");
+    assert_eq!(lines[1], "A.chr1(+):1-3\t1\t4\t0\t0\t0\t0\t2.0000");
+    assert_eq!(lines[2], "A.chr1(+):1-3\t2\t1\t1\t1\t1\t0\t0.0000");
+    assert_eq!(lines[3], "A.chr1(+):1-3\t3\t2\t0\t0\t0\t2\t2.0000");
+
+    // ic of 2, 0, 2 averaged over 3 columns, truncated at the ends
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "logo", fas_file.to_str().unwrap(), "--window", "3"])
+        .run();
+    let ics: Vec<&str> = stdout
+        .lines()
+        .skip(1)
+        .map(|l| l.rsplit('\t').next().unwrap())
+        .collect();
+    assert_eq!(ics, vec!["1.0000", "1.3333", "1.0000"]);
+}
@@ -95,3 +95,23 @@ fn command_fas_multiz_custom_matrix() {
 
     tempdir.close().unwrap();
 }
+
+#[test]
+fn command_fas_multiz_parallel_matches_sequential() {
+    let inputs = [
+        "tests/fas/S288cvsRM11_1a.slice.fas",
+        "tests/fas/S288cvsSpar.slice.fas",
+    ];
+
+    let (sequential, _) = PgrCmd::new()
+        .args(&["fas", "multiz", "-r", "S288c"])
+        .args(&inputs)
+        .run();
+    let (parallel, _) = PgrCmd::new()
+        .args(&["fas", "multiz", "-r", "S288c", "--parallel", "4"])
+        .args(&inputs)
+        .run();
+
+    assert!(sequential.lines().count() > 0);
+    assert_eq!(parallel, sequential);
+}
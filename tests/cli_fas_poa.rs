@@ -113,6 +113,42 @@ fn command_refine_poa() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn command_refine_poa_realigns_gaps() -> anyhow::Result<()> {
+    // Same sequence with the gap placed on opposite sides
+    let input = ">S1.I(+):1-12\nACGTAC--GTACGT\n>S2.I(+):1-12\nACGT--ACGTACGT\n\n";
+    let gap_columns = |block: &str| -> usize {
+        let seqs: Vec<&[u8]> = block
+            .lines()
+            .filter(|l| !l.starts_with('>') && !l.is_empty())
+            .map(|l| l.as_bytes())
+            .collect();
+        (0..seqs[0].len())
+            .filter(|&i| seqs.iter().any(|s| s[i] == b'-'))
+            .count()
+    };
+    assert_eq!(gap_columns(input), 4);
+
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    let output = cmd
+        .arg("fas")
+        .arg("refine")
+        .arg("stdin")
+        .arg("--engine")
+        .arg("builtin")
+        .arg("--gap-open")
+        .arg("-10")
+        .write_stdin(input)
+        .output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+
+    assert_eq!(stdout.lines().count(), 5);
+    assert_eq!(gap_columns(&stdout), 0);
+    assert!(stdout.contains(">S2.I(+):1-12\nACGTACGTACGT\n"));
+
+    Ok(())
+}
+
 #[test]
 fn command_refine_default() -> anyhow::Result<()> {
     let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
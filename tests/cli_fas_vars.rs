@@ -225,3 +225,109 @@ fn command_to_xlsx_outgroup() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn command_to_xlsx_summary() -> anyhow::Result<()> {
+    let temp_file = NamedTempFile::new()?.into_temp_path();
+    let temp_path = temp_file.to_str().unwrap();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    cmd.arg("fas")
+        .arg("to-xlsx")
+        .arg("tests/fas/example.fas")
+        .arg("--no-color")
+        .arg("-o")
+        .arg(temp_path)
+        .assert()
+        .success();
+
+    let mut workbook: calamine::Xlsx<_> = calamine::open_workbook(temp_path).unwrap();
+    let sheet = workbook.worksheet_range("summary").unwrap();
+
+    // header + 3 blocks
+    assert_eq!(sheet.height(), 4);
+    assert_eq!(sheet.get_value((0, 2)).unwrap().to_string(), "conservation");
+    assert_eq!(sheet.get_value((0, 3)).unwrap().to_string(), "YJM789");
+    assert_eq!(
+        sheet.get_value((1, 0)).unwrap().to_string(),
+        "S288c.I(+):13267-13287"
+    );
+
+    // The variation sheet is unchanged apart from colors
+    let sheet = workbook.worksheet_range_at(0).unwrap().unwrap();
+    assert_eq!(
+        sheet.get_value((1, 1)).unwrap().to_string(),
+        "G".to_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn command_to_xlsx_summary_by_species() -> anyhow::Result<()> {
+    let tempdir = tempfile::TempDir::new()?;
+    let infile = tempdir.path().join("reorder.fas");
+    let outfile = tempdir.path().join("out.xlsx");
+
+    // The second block lists its species in another order, and adds one
+    std::fs::write(
+        &infile,
+        "\
+>S288c.I(+):1-10
+AAAAAAAAAA
+>A.I(+):1-10
+AAAAAAAAAA
+>B.I(+):1-10
+AAAAATTTTT
+
+>S288c.I(+):21-30
+AAAAAAAAAA
+>C.I(+):1-10
+TTTTTTTTTA
+>B.I(+):11-20
+AAAAAAAAAT
+>A.I(+):11-20
+AAAAAAAAAA
+
+",
+    )?;
+
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    cmd.arg("fas")
+        .arg("to-xlsx")
+        .arg(&infile)
+        .arg("-o")
+        .arg(&outfile)
+        .assert()
+        .success();
+
+    let mut workbook: calamine::Xlsx<_> = calamine::open_workbook(&outfile).unwrap();
+    let sheet = workbook.worksheet_range("summary").unwrap();
+
+    let cell = |r: u32, c: u32| sheet.get_value((r, c)).unwrap().to_string();
+    assert_eq!(
+        (cell(0, 3), cell(0, 4), cell(0, 5)),
+        ("A".into(), "B".into(), "C".into())
+    );
+    let identity = |r: u32, c: u32| cell(r, c).parse::<f64>().unwrap();
+    for (r, c, expected) in [
+        (1, 3, 1.0),
+        (1, 4, 0.5),
+        (2, 3, 1.0),
+        (2, 4, 0.9),
+        (2, 5, 0.1),
+    ] {
+        assert!(
+            (identity(r, c) - expected).abs() < 1e-6,
+            "row {} col {}",
+            r,
+            c
+        );
+    }
+    // C is absent from the first block
+    assert!(sheet
+        .get_value((1, 5))
+        .is_none_or(|v| v.to_string().is_empty()));
+
+    Ok(())
+}
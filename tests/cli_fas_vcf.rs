@@ -119,7 +119,7 @@ fn command_vcf_sizes_contig_real() {
 
 #[test]
 fn command_vcf_ydl_expected_rows() {
-    let stdout = run_vcf(&["tests/fas_vcf/YDL184C.fas"]);
+    let stdout = run_vcf(&["--no-indels", "tests/fas_vcf/YDL184C.fas"]);
 
     let mut rows: std::collections::HashMap<i32, Vec<String>> = std::collections::HashMap::new();
     for line in stdout.lines() {
@@ -152,3 +152,88 @@ fn command_vcf_ydl_expected_rows() {
     let gt3 = &r3[9..];
     assert_eq!(gt3, ["0", "0", "0", "0", "1", "0"]);
 }
+
+#[test]
+fn command_vcf_biallelic_genotypes() {
+    let input = ">S1.I(+):1-5\nACGTA\n>S2.I(+):1-5\nACTTA\n\n";
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "to-vcf", "stdin"])
+        .stdin(input)
+        .run();
+
+    let rows: Vec<&str> = stdout.lines().filter(|l| !l.starts_with('#')).collect();
+    assert_eq!(rows, ["I\t3\t.\tG\tT\t.\t.\t.\tGT\t0\t1"]);
+}
+
+#[test]
+fn command_vcf_gap_genotype() {
+    // S3 has a gap at the variable column and an N at the last one
+    let input = ">S1.I(+):1-5\nACGTA\n>S2.I(+):1-5\nACTTC\n>S3.I(+):1-4\nAC-TN\n\n";
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "to-vcf", "--no-indels", "stdin"])
+        .stdin(input)
+        .run();
+
+    let rows: Vec<&str> = stdout.lines().filter(|l| !l.starts_with('#')).collect();
+    assert_eq!(
+        rows,
+        [
+            "I\t3\t.\tG\tT\t.\t.\t.\tGT\t0\t1\t.",
+            "I\t5\t.\tA\tC\t.\t.\t.\tGT\t0\t1\t.",
+        ]
+    );
+}
+
+#[test]
+fn command_vcf_insertion() {
+    let input = ">S1.I(+):1-4\nAC-GT\n>S2.I(+):1-5\nACAGT\n\n";
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "to-vcf", "stdin"])
+        .stdin(input)
+        .run();
+
+    let rows: Vec<&str> = stdout.lines().filter(|l| !l.starts_with('#')).collect();
+    assert_eq!(rows, ["I\t2\t.\tC\tCA\t.\t.\t.\tGT\t0\t1"]);
+}
+
+#[test]
+fn command_vcf_deletion() {
+    let input = ">S1.I(+):1-6\nACGTAC\n>S2.I(+):1-4\nAC--AC\n\n";
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "to-vcf", "stdin"])
+        .stdin(input)
+        .run();
+
+    // Two gap columns coalesce into one record anchored on the preceding base
+    let rows: Vec<&str> = stdout.lines().filter(|l| !l.starts_with('#')).collect();
+    assert_eq!(rows, ["I\t2\t.\tCGT\tC\t.\t.\t.\tGT\t0\t1"]);
+
+    // --no-indels keeps substitutions only
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "to-vcf", "--no-indels", "stdin"])
+        .stdin(input)
+        .run();
+    assert_eq!(stdout.lines().filter(|l| !l.starts_with('#')).count(), 0);
+}
+
+#[test]
+fn command_vcf_indel_anchor() {
+    // The anchor base differs too; it is reported once, inside the indel record
+    let input = ">S1.I(+):1-4\nAC-GT\n>S2.I(+):1-5\nATAGT\n\n";
+    let (stdout, _) = PgrCmd::new()
+        .args(&["fas", "to-vcf", "stdin"])
+        .stdin(input)
+        .run();
+
+    let rows: Vec<&str> = stdout.lines().filter(|l| !l.starts_with('#')).collect();
+    assert_eq!(rows, ["I\t2\t.\tC\tTA\t.\t.\t.\tGT\t0\t1"]);
+
+    // An indel at the block start has no anchor base
+    let input = ">S1.I(+):1-3\n-CGT\n>S2.I(+):1-4\nACGT\n\n";
+    let (stdout, stderr) = PgrCmd::new()
+        .args(&["fas", "to-vcf", "stdin"])
+        .stdin(input)
+        .run();
+    assert_eq!(stdout.lines().filter(|l| !l.starts_with('#')).count(), 0);
+    assert!(stderr.contains("skipped the indel"), "{}", stderr);
+}
@@ -163,3 +163,62 @@ fn run_ucsc_test(name: &str) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_lav_to_psl_two_stanzas() -> anyhow::Result<()> {
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    let assert = cmd
+        .arg("lav")
+        .arg("to-psl")
+        .arg("--strict")
+        .arg("tests/lav/twoStanza.lav")
+        .assert()
+        .success();
+
+    let output = std::str::from_utf8(&assert.get_output().stdout)?.to_string();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 2);
+
+    assert_eq!(
+        lines[0],
+        "34\t6\t0\t0\t1\t5\t1\t10\t+\tq1\t500\t20\t65\tchr1\t1000\t10\t60\t2\t20,20,\t20,45,\t10,40,"
+    );
+
+    let fields: Vec<&str> = lines[1].split('\t').collect();
+    assert_eq!(fields[8], "-");
+    assert_eq!(fields[9], "q2");
+    assert_eq!(fields[13], "chr2");
+    assert_eq!(fields[17], "3");
+    assert_eq!(fields[18], "10,10,10,");
+    assert_eq!(fields[19], "0,10,30,");
+    assert_eq!(fields[20], "0,20,40,");
+
+    Ok(())
+}
+
+#[test]
+fn test_lav_to_psl_overlapping_blocks() {
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    let input = r#"#:lav
+s {
+    "/path/target.fa" 1 1000
+    "/path/query.fa" 1 500
+}
+h {
+    ">target.fa"
+    ">query.fa"
+}
+a {
+    s 100
+    l 1 1 20 20 95
+    l 11 21 30 40 95
+}
+"#;
+
+    cmd.arg("lav")
+        .arg("to-psl")
+        .write_stdin(input)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("overlaps"));
+}
@@ -122,3 +122,83 @@ fn command_maf_to_paf_with_gaps() {
     assert!(stdout.contains("bi:f:"), "bi tag present");
     assert!(stdout.contains("ms:i:200"), "score tag present");
 }
+
+#[test]
+fn command_maf_stat() {
+    let (stdout, _) = PgrCmd::new()
+        .args(&["maf", "stat", "tests/maf/example.maf"])
+        .run();
+
+    assert!(stdout.contains("blocks\t2\n"));
+    assert!(stdout.contains("species\t4\n"));
+    assert!(stdout.contains("columns\t138\n")); // 34 + 104
+    assert!(stdout.contains("rows:S288c\t2\n"));
+    assert!(stdout.contains("rows:Spar\t2\n"));
+    assert_eq!(stdout.lines().count(), 7);
+}
+
+#[test]
+fn command_maf_stat_malformed_block() {
+    let maf = "##maf version=1\n\
+               a score=1\n\
+               s ref.chr1 0 4 + 100 ACGT\n\
+               s qry.chr1 0 4 + 100 ACGT\n\
+               \n\
+               a score=2\n\
+               s ref.chr1 10 4 + 100 ACGT\n\
+               s qry.chr1 10 3 + 100 ACG\n";
+
+    let (_, stderr) = PgrCmd::new()
+        .args(&["maf", "stat", "stdin"])
+        .stdin(maf)
+        .run_fail();
+
+    assert!(stderr.contains("malformed block 2"));
+}
+
+#[test]
+fn command_maf_filter_min_score() {
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "maf",
+            "filter",
+            "tests/maf/example.maf",
+            "--min-score",
+            "100000",
+        ])
+        .run();
+
+    assert!(stdout.starts_with("##maf version=1"));
+    assert_eq!(stdout.matches("\na score=").count(), 1);
+    assert!(stdout.contains("a score=514600.0"));
+    assert!(!stdout.contains("a score=36468.0"));
+    assert_eq!(stdout.matches("\ns ").count(), 4);
+}
+
+#[test]
+fn command_maf_filter_require() {
+    let maf = "##maf version=1\n\
+               a score=10\n\
+               s ref.chr1 0 4 + 100 ACGT\n\
+               s qry.chr1 0 4 + 100 ACGT\n\
+               \n\
+               a score=20\n\
+               s ref.chr1 10 4 + 100 ACGT\n\
+               s other.chr2 10 4 + 100 ACGT\n";
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["maf", "filter", "stdin", "--require", "qry"])
+        .stdin(maf)
+        .run();
+
+    assert!(stdout.contains("a score=10.0"));
+    assert!(!stdout.contains("a score=20.0"));
+    assert!(!stdout.contains("other.chr2"));
+
+    // The output is valid MAF and round-trips through the parser
+    let (stdout, _) = PgrCmd::new()
+        .args(&["maf", "stat", "stdin"])
+        .stdin(stdout)
+        .run();
+    assert!(stdout.contains("blocks\t1\n"));
+}
@@ -0,0 +1,136 @@
+#[macro_use]
+#[path = "common/mod.rs"]
+mod common;
+
+use common::PgrCmd;
+
+#[test]
+fn command_mat_mds_line() {
+    let (stdout, _) = PgrCmd::new()
+        .args(&["mat", "mds", "tests/mat/line.phy", "-k", "1"])
+        .run();
+
+    assert_eq!(
+        stdout,
+        "name\tdim1\nA\t-2.5000\nB\t-1.5000\nC\t0.5000\nD\t3.5000\n"
+    );
+}
+
+#[test]
+fn command_mat_mds_lower_triangle() {
+    let (full, _) = PgrCmd::new()
+        .args(&["mat", "mds", "tests/mat/line.phy", "-k", "1"])
+        .run();
+    let (lower, _) = PgrCmd::new()
+        .args(&["mat", "mds", "tests/mat/line_lower.phy", "-k", "1"])
+        .run();
+
+    assert_eq!(full, lower);
+}
+
+#[test]
+fn command_mat_format_symmetrize_avg() {
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "mat",
+            "format",
+            "tests/mat/asym.phy",
+            "--symmetrize",
+            "avg",
+            "--precision",
+            "1",
+        ])
+        .run();
+
+    assert_eq!(
+        stdout,
+        "3\nA\t0.0\t2.0\t3.0\nB\t2.0\t0.0\t4.0\nC\t3.0\t4.0\t0.0\n"
+    );
+}
+
+#[test]
+fn command_mat_format_lower_to_full() {
+    let (stdout, _) = PgrCmd::new()
+        .args(&["mat", "format", "tests/mat/line_lower.phy"])
+        .run();
+
+    assert_eq!(stdout.lines().count(), 5);
+    assert!(stdout.contains("A\t0\t1\t3\t6\n"));
+}
+
+#[test]
+fn command_mat_subset_list_order() {
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "mat",
+            "subset",
+            "tests/mat/line.phy",
+            "--list",
+            "tests/mat/CA.lst",
+        ])
+        .run();
+    assert_eq!(stdout, "2\nC\t0\t3\nA\t3\t0\n");
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "mat",
+            "subset",
+            "tests/mat/line.phy",
+            "--list",
+            "tests/mat/CA.lst",
+            "--keep-order",
+        ])
+        .run();
+    assert_eq!(stdout, "2\nA\t0\t3\nC\t3\t0\n");
+}
+
+#[test]
+fn command_mat_subset_missing_name() {
+    let (_, stderr) = PgrCmd::new()
+        .args(&[
+            "mat",
+            "subset",
+            "tests/mat/line.phy",
+            "--list",
+            "tests/mat/CX.lst",
+        ])
+        .run_fail();
+    assert!(stderr.contains("taxon not found in matrix: X"));
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "mat",
+            "subset",
+            "tests/mat/line.phy",
+            "--list",
+            "tests/mat/CX.lst",
+            "--lenient",
+        ])
+        .run();
+    assert_eq!(stdout, "1\nC\t0\n");
+}
+
+#[test]
+fn command_mat_to_pair_threshold() {
+    let (stdout, _) = PgrCmd::new()
+        .args(&["mat", "to-pair", "tests/mat/tri.phy", "--max-dist", "0.5"])
+        .run();
+    assert_eq!(stdout, "A\tB\t0.1\nB\tC\t0.3\n");
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["mat", "to-pair", "tests/mat/tri.phy", "--min-sim", "0.5"])
+        .run();
+    assert_eq!(stdout, "A\tC\t0.8\n");
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "mat",
+            "to-pair",
+            "tests/mat/tri.phy",
+            "--max-dist",
+            "0.5",
+            "--include-self",
+        ])
+        .run();
+    assert_eq!(stdout, "A\tA\t0\nA\tB\t0.1\nB\tB\t0\nB\tC\t0.3\nC\tC\t0\n");
+}
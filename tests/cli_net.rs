@@ -233,6 +233,77 @@ fn test_net_to_axt_reverse() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn test_net_to_axt_named_2bit() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = TempDir::new()?;
+
+    let t_2bit = create_2bit(&temp, "chrT", ">chrT\nAAAA")?;
+    let q_2bit = create_2bit(&temp, "chrQ", ">chrQ\nTTTTACGTTTTT")?;
+
+    let chain_path = temp.path().join("in.chain");
+    fs::write(&chain_path, "chain 100 chrT 4 + 0 4 chrQ 12 - 0 4 1\n4\n\n")?;
+
+    let net_path = temp.path().join("in.net");
+    fs::write(
+        &net_path,
+        "net chrT 4\n fill 0 4 chrQ - 0 4 id 1 score 100 ali 4\n",
+    )?;
+
+    let out_path = temp.path().join("out.axt");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    cmd.arg("net")
+        .arg("to-axt")
+        .arg(&net_path)
+        .arg(&chain_path)
+        .arg("--t-2bit")
+        .arg(&t_2bit)
+        .arg("--q-2bit")
+        .arg(&q_2bit)
+        .arg("-o")
+        .arg(&out_path)
+        .assert()
+        .success();
+
+    // Minus-strand query fill is reverse-complemented: TTTT -> AAAA
+    let output = fs::read_to_string(&out_path)?;
+    assert_eq!(output, "0 chrT 1 4 chrQ 1 4 - 364\nAAAA\nAAAA\n\n");
+
+    // Query 2bit lacking chrQ aborts with the chrom name
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    cmd.arg("net")
+        .arg("to-axt")
+        .arg(&net_path)
+        .arg(&chain_path)
+        .arg("--t-2bit")
+        .arg(&t_2bit)
+        .arg("--q-2bit")
+        .arg(&t_2bit)
+        .arg("-o")
+        .arg(&out_path)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Query sequence chrQ not found"));
+
+    // A positional 2bit file and its named form can't be combined
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    cmd.arg("net")
+        .arg("to-axt")
+        .arg(&net_path)
+        .arg(&chain_path)
+        .arg(&t_2bit)
+        .arg(&q_2bit)
+        .arg("--t-2bit")
+        .arg(&t_2bit)
+        .arg("-o")
+        .arg(&out_path)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("cannot be used with"));
+
+    Ok(())
+}
+
 // --- net split tests ---
 
 #[test]
@@ -372,6 +443,60 @@ fn test_net_subset_split_on_insert() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn test_net_subset_window() -> Result<(), Box<dyn std::error::Error>> {
+    let mut chain_file = NamedTempFile::new()?;
+    writeln!(
+        chain_file,
+        "chain 1000 chr1 1000 + 0 1000 chr2 1000 + 0 1000 1"
+    )?;
+    writeln!(chain_file, "1000")?;
+    writeln!(chain_file)?;
+    writeln!(
+        chain_file,
+        "chain 900 chr3 1000 + 0 500 chr2 1000 + 0 500 3"
+    )?;
+    writeln!(chain_file, "500")?;
+    writeln!(chain_file)?;
+
+    let mut net_file = NamedTempFile::new()?;
+    writeln!(net_file, "net chr1 1000")?;
+    writeln!(net_file, " fill 0 500 chr2 + 0 500 id 1 score 500 ali 500")?;
+    writeln!(net_file, "  gap 200 100 chr2 + 200 100")?;
+    writeln!(
+        net_file,
+        "   fill 200 100 chr2 + 200 100 id 2 score 100 ali 100"
+    )?;
+    writeln!(
+        net_file,
+        " fill 800 100 chr2 + 800 100 id 1 score 100 ali 100"
+    )?;
+    writeln!(net_file, "net chr3 1000")?;
+    writeln!(net_file, " fill 0 500 chr2 + 0 500 id 3 score 900 ali 500")?;
+
+    let out_file = NamedTempFile::new()?;
+    let out_path = out_file.path().to_str().unwrap();
+
+    assert_cmd::Command::cargo_bin("pgr")
+        .unwrap()
+        .arg("net")
+        .arg("subset")
+        .arg(net_file.path().to_str().unwrap())
+        .arg(chain_file.path().to_str().unwrap())
+        .arg(out_path)
+        .args(["--chrom", "chr1", "--start", "101", "--end", "300"])
+        .assert()
+        .success();
+
+    // Only the part of the first fill inside chr1:101-300 is kept.
+    let output = std::fs::read_to_string(out_path)?;
+    assert!(output.contains("chain 1000 chr1 1000 + 100 300 chr2 1000 + 100 300 1"));
+    assert!(!output.contains("+ 800 900"));
+    assert!(!output.contains("chr3"));
+
+    Ok(())
+}
+
 // --- net filter tests ---
 
 #[test]
@@ -465,6 +590,19 @@ fn test_net_class_basic() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn command_net_class_summary() {
+    let (_, stderr) = PgrCmd::new()
+        .args(&["net", "class", "tests/pgr/net/cat.net", "--summary"])
+        .run();
+
+    assert!(stderr.contains("class\tcount\tbases\tali\n"));
+    // The single top-level fill: 17594 bp, ali 5349.
+    assert!(stderr.contains("top\t1\t17594\t5349\n"));
+    assert!(stderr.contains("inv\t1\t271\t224\n"));
+    assert!(stderr.contains("syn\t0\t0\t0\n"));
+}
+
 #[test]
 fn command_net_filter_mutually_exclusive() {
     let (_, stderr) = PgrCmd::new()
@@ -106,3 +106,207 @@ fn command_pl_ucsc_help() -> anyhow::Result<()> {
     assert!(stdout.contains("UCSC chain/net pipeline"));
     Ok(())
 }
+
+#[test]
+fn command_pl_prefilter_parallel_matches_serial() -> anyhow::Result<()> {
+    let input = "tests/index/final.contigs.fa";
+    let ref_file = "tests/pl/IBPA.fa";
+
+    let tempdir = TempDir::new()?;
+    let serial = tempdir.path().join("serial.tsv");
+    let parallel = tempdir.path().join("parallel.tsv");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    cmd.arg("pl")
+        .arg("prefilter")
+        .arg(input)
+        .arg(ref_file)
+        .arg("--chunk-size")
+        .arg("100000")
+        .arg("-p")
+        .arg("1")
+        .arg("-o")
+        .arg(&serial)
+        .assert()
+        .success();
+
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    let output = cmd
+        .arg("pl")
+        .arg("prefilter")
+        .arg(input)
+        .arg(ref_file)
+        .arg("--chunk-size")
+        .arg("100000")
+        .arg("-p")
+        .arg("4")
+        .arg("--progress")
+        .arg("-o")
+        .arg(&parallel)
+        .output()?;
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("==> Finished 1/"));
+
+    let serial = std::fs::read_to_string(&serial)?;
+    assert!(serial.contains("k81_25"));
+    assert_eq!(serial, std::fs::read_to_string(&parallel)?);
+
+    Ok(())
+}
+
+#[test]
+fn command_pl_p2m_dry_run() -> anyhow::Result<()> {
+    let tempdir = TempDir::new()?;
+    let outdir = tempdir.path().join("PL-p2m");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    let output = cmd
+        .arg("pl")
+        .arg("p2m")
+        .arg("tests/fas/S288cvsRM11_1a.slice.fas")
+        .arg("tests/fas/S288cvsSpar.slice.fas")
+        .arg("--dry-run")
+        .arg("-o")
+        .arg(&outdir)
+        .output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let steps: Vec<&str> = stdout.lines().filter(|l| !l.starts_with(' ')).collect();
+    assert_eq!(
+        steps,
+        vec![
+            "1. pgr fas name - first",
+            "2. pgr fas cover",
+            "3. spanr compare",
+            "4. pgr fas slice",
+            "5. pgr fas join",
+            "6. pgr fas name && pgr fas subset",
+        ]
+    );
+    assert_eq!(stdout.matches(" fas cover ").count(), 2);
+    assert!(stdout.contains(&format!("+ {}/join.subset.fas", outdir.display())));
+
+    // Nothing is created
+    assert!(!outdir.exists());
+    assert_eq!(std::fs::read_dir(tempdir.path())?.count(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn command_pl_ucsc_dry_run() -> anyhow::Result<()> {
+    let tempdir = TempDir::new()?;
+    let outdir = tempdir.path().join("maf");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    let output = cmd
+        .arg("pl")
+        .arg("ucsc")
+        .arg("tests/pgr/pseudocat.fa")
+        .arg("tests/pgr/pseudopig.fa")
+        .arg("tests/pgr/lastz.psl")
+        .arg("--dry-run")
+        .arg("-o")
+        .arg(&outdir)
+        .output()?;
+    assert!(output.status.success());
+
+    // The commands that run, batched merge included
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("$ chainMergeSort pslChain/lastz.chain > all.1.chain.tmp\n"));
+    assert!(stdout.contains("$ chainMergeSort all.1.chain.tmp > all.chain\n"));
+    assert!(stdout.contains(&format!("+ {}/<chr>.maf", outdir.display())));
+
+    // Nothing is created
+    assert!(!outdir.exists());
+
+    Ok(())
+}
+
+#[test]
+fn command_pl_ucsc_resume_needs_done_marker() -> anyhow::Result<()> {
+    let tempdir = TempDir::new()?;
+    let outdir = tempdir.path().join("maf");
+    std::fs::create_dir_all(&outdir)?;
+
+    let ucsc_resume = || {
+        assert_cmd::Command::cargo_bin("pgr")
+            .unwrap()
+            .arg("pl")
+            .arg("ucsc")
+            .arg("tests/pgr/pseudocat.fa")
+            .arg("tests/pgr/pseudopig.fa")
+            .arg("tests/pgr/lastz.psl")
+            .arg("--resume")
+            .arg("-o")
+            .arg(&outdir)
+            .output()
+    };
+
+    // .maf files left by an interrupted run are not enough
+    std::fs::write(outdir.join("cat.maf"), "##maf version=1\n")?;
+    let output = ucsc_resume()?;
+    assert!(!String::from_utf8(output.stderr)?.contains("up to date"));
+
+    std::fs::write(outdir.join(".ucsc.done"), "")?;
+    let output = ucsc_resume()?;
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("up to date, skipped"));
+
+    Ok(())
+}
+
+#[test]
+fn command_pl_prefilter_resume() -> anyhow::Result<()> {
+    let input = "tests/index/final.contigs.fa";
+    let ref_file = "tests/pl/IBPA.fa";
+
+    let tempdir = TempDir::new()?;
+    let outfile = tempdir.path().join("out.tsv");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    cmd.arg("pl")
+        .arg("prefilter")
+        .arg(input)
+        .arg(ref_file)
+        .arg("-o")
+        .arg(&outfile)
+        .assert()
+        .success();
+    let modified = std::fs::metadata(&outfile)?.modified()?;
+
+    // All outputs are present: no work is done
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    let output = cmd
+        .arg("pl")
+        .arg("prefilter")
+        .arg(input)
+        .arg(ref_file)
+        .arg("--resume")
+        .arg("-o")
+        .arg(&outfile)
+        .output()?;
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("up to date, skipped"));
+    assert_eq!(std::fs::metadata(&outfile)?.modified()?, modified);
+
+    // --force overrides --resume
+    let mut cmd = assert_cmd::Command::cargo_bin("pgr").unwrap();
+    let output = cmd
+        .arg("pl")
+        .arg("prefilter")
+        .arg(input)
+        .arg(ref_file)
+        .arg("--resume")
+        .arg("--force")
+        .arg("-o")
+        .arg(&outfile)
+        .output()?;
+    assert!(output.status.success());
+    assert!(!String::from_utf8(output.stderr)?.contains("up to date"));
+
+    Ok(())
+}
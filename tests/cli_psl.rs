@@ -55,6 +55,39 @@ fn test_histo_apq_base() {
     assert_eq!(output_content, expected);
 }
 
+#[test]
+fn test_histo_identity_bins() {
+    let temp = TempDir::new().unwrap();
+    let input = temp.path().join("ident.psl");
+    // Identities: 1.0, 0.95, 0.85, 0.5, 0.05
+    let psl = [(100, 0), (95, 5), (85, 15), (50, 50), (5, 95)]
+        .iter()
+        .enumerate()
+        .map(|(i, (m, mm))| {
+            format!(
+                "{}\t{}\t0\t0\t0\t0\t0\t0\t+\tq{}\t100\t0\t100\tt\t1000\t0\t100\t1\t100,\t0,\t0,\n",
+                m, mm, i
+            )
+        })
+        .collect::<String>();
+    fs::write(&input, psl).unwrap();
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "psl",
+            "histo",
+            "--field",
+            "identity",
+            "--bins",
+            "10",
+            input.to_str().unwrap(),
+        ])
+        .run();
+
+    let expected = "0\t1\n0.1\t0\n0.2\t0\n0.3\t0\n0.4\t0\n0.5\t1\n0.6\t0\n0.7\t0\n0.8\t1\n0.9\t2\n";
+    assert_eq!(stdout, expected);
+}
+
 #[test]
 fn test_histo_apq_multi() {
     let temp = TempDir::new().unwrap();
@@ -166,6 +199,57 @@ fn test_to_chain_fix_strand() {
     assert_eq!(output_content, expected_content);
 }
 
+#[test]
+fn test_to_chain_score_matrix() {
+    let temp = TempDir::new().unwrap();
+    let t_fa = temp.path().join("t.fa");
+    let q_fa = temp.path().join("q.fa");
+    // Target has one extra base between the blocks; the last query base mismatches
+    fs::write(&t_fa, ">t\nACGTACGTACGTTGCATGCAA\n").unwrap();
+    fs::write(&q_fa, ">q\nACGTACGTACTTGCATGCAT\n").unwrap();
+    let t_2bit = temp.path().join("t.2bit");
+    let q_2bit = temp.path().join("q.2bit");
+    for (fa, twobit) in [(&t_fa, &t_2bit), (&q_fa, &q_2bit)] {
+        PgrCmd::new()
+            .args(&[
+                "fa",
+                "to-2bit",
+                fa.to_str().unwrap(),
+                "-o",
+                twobit.to_str().unwrap(),
+            ])
+            .run();
+    }
+
+    let input = temp.path().join("in.psl");
+    fs::write(
+        &input,
+        "19\t1\t0\t0\t0\t0\t1\t1\t+\tq\t20\t0\t20\tt\t21\t0\t21\t2\t10,10,\t0,10,\t0,11,\n",
+    )
+    .unwrap();
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&[
+            "psl",
+            "to-chain",
+            input.to_str().unwrap(),
+            "--score",
+            "matrix",
+            "--target-2bit",
+            t_2bit.to_str().unwrap(),
+            "--query-2bit",
+            q_2bit.to_str().unwrap(),
+        ])
+        .run();
+    // 19 matches * 100 - 1 mismatch * 100 - 350 (loose, 1 bp target gap)
+    assert!(stdout.starts_with("chain 1450 t 21 + 0 21 q 20 + 0 20 1\n"));
+
+    let (stdout, _) = PgrCmd::new()
+        .args(&["psl", "to-chain", input.to_str().unwrap()])
+        .run();
+    assert!(stdout.starts_with("chain 17 "));
+}
+
 #[test]
 fn test_to_chain_fail_neg_strand() {
     let temp = TempDir::new().unwrap();
@@ -524,6 +608,31 @@ fn test_psl_swap_mrna_no_rc() {
     assert_eq!(output_content, expected_content);
 }
 
+#[test]
+fn test_psl_swap_round_trip() {
+    let temp = TempDir::new().unwrap();
+    // Contains minus-strand multi-block records
+    let input = get_path("swap", "input", "mrna.psl");
+    let once = temp.path().join("once.psl");
+    let twice = temp.path().join("twice.psl");
+
+    for (infile, outfile) in [(&input, &once), (&once, &twice)] {
+        PgrCmd::new()
+            .args(&[
+                "psl",
+                "swap",
+                infile.to_str().unwrap(),
+                "-o",
+                outfile.to_str().unwrap(),
+            ])
+            .run();
+    }
+
+    let input_content = fs::read_to_string(&input).unwrap();
+    let twice_content = fs::read_to_string(&twice).unwrap();
+    assert_eq!(twice_content, input_content);
+}
+
 #[test]
 fn test_psl_swap_trans() {
     let temp = TempDir::new().unwrap();